@@ -1,11 +1,11 @@
 use crate::errors::DepGraphError;
 use crate::node::Node;
-use moon_config::ProjectLanguage;
+use moon_config::{PlatformType, ProjectLanguage};
 use moon_lang::SupportedLanguage;
 use moon_logger::{color, debug, map_list, trace};
 use moon_project::Project;
 use moon_project_graph::ProjectGraph;
-use moon_task::{Target, TargetError, TargetProjectScope, TouchedFilePaths};
+use moon_task::{Target, TargetError, TargetProjectScope, TaskDependencyConfig, TouchedFilePaths};
 use petgraph::algo::toposort;
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::DiGraph;
@@ -20,6 +20,7 @@ const LOG_TARGET: &str = "moon:dep-graph";
 fn get_lang_from_project(project: &Project) -> SupportedLanguage {
     match &project.config.language {
         ProjectLanguage::JavaScript | ProjectLanguage::TypeScript => SupportedLanguage::Node,
+        ProjectLanguage::Python => SupportedLanguage::Python,
         _ => SupportedLanguage::System,
     }
 }
@@ -119,7 +120,7 @@ impl DepGraph {
 
                     if project.tasks.contains_key(task_id)
                         && self
-                            .insert_target(&project.id, task_id, projects, touched_files)?
+                            .insert_target(&project.id, task_id, None, projects, touched_files)?
                             .is_some()
                     {
                         inserted_count += 1;
@@ -135,7 +136,7 @@ impl DepGraph {
                 let project = projects.load(project_id)?;
 
                 if self
-                    .insert_target(&project.id, task_id, projects, touched_files)?
+                    .insert_target(&project.id, task_id, None, projects, touched_files)?
                     .is_some()
                 {
                     inserted_count += 1;
@@ -243,7 +244,11 @@ impl DepGraph {
             root_nodes = next_root_nodes;
         }
 
-        Ok(batches.into_iter().rev().collect())
+        Ok(batches
+            .into_iter()
+            .rev()
+            .flat_map(|batch| self.split_target_variants(batch))
+            .collect())
     }
 
     pub fn sync_project(
@@ -316,6 +321,38 @@ impl DepGraph {
         format!("{:?}", dot)
     }
 
+    // Variants of the same target (deps with custom args or env vars) write to the
+    // same outputs, so split them into sequential batches to avoid running in parallel.
+    fn split_target_variants(&self, mut batch: Vec<NodeIndex>) -> BatchedTopoSort {
+        let mut batches: BatchedTopoSort = vec![];
+
+        batch.sort();
+
+        for ix in batch {
+            let target = match self.get_node_from_index(&ix) {
+                Some(Node::RunTarget(dep)) => Some(&dep.target),
+                _ => None,
+            };
+
+            let index = batches.iter().position(|split| {
+                target.is_none()
+                    || split.iter().all(|split_ix| {
+                        !matches!(
+                            self.get_node_from_index(split_ix),
+                            Some(Node::RunTarget(dep)) if Some(&dep.target) == target
+                        )
+                    })
+            });
+
+            match index {
+                Some(index) => batches[index].push(ix),
+                None => batches.push(vec![ix]),
+            };
+        }
+
+        batches
+    }
+
     #[track_caller]
     fn detect_cycle(&self) -> Result<(), DepGraphError> {
         use petgraph::algo::kosaraju_scc;
@@ -336,12 +373,21 @@ impl DepGraph {
         &mut self,
         project_id: &str,
         task_id: &str,
+        overrides: Option<&TaskDependencyConfig>,
         projects: &ProjectGraph,
         touched_files: Option<&TouchedFilePaths>,
     ) -> Result<Option<NodeIndex>, DepGraphError> {
         let project = projects.load(project_id)?;
         let target_id = Target::format(&project.id, task_id)?;
-        let node = Node::RunTarget(target_id.clone());
+
+        // Targets with custom args or env vars are distinct nodes
+        let node = Node::RunTarget(match overrides {
+            Some(overrides) => TaskDependencyConfig {
+                target: target_id.clone(),
+                ..overrides.clone()
+            },
+            None => TaskDependencyConfig::new(&target_id),
+        });
 
         if let Some(index) = self.get_index_from_node(&node) {
             return Ok(Some(*index));
@@ -367,8 +413,12 @@ impl DepGraph {
             color::target(&target_id),
         );
 
-        // We should install deps & sync projects *before* running targets
-        let install_deps_index = self.install_project_deps(&project.id, projects)?;
+        // We should install deps & sync projects *before* running targets.
+        // Bun tasks install with Bun, regardless of the project's language.
+        let install_deps_index = match project.get_task(task_id)?.platform {
+            PlatformType::Bun => self.install_deps(SupportedLanguage::Bun),
+            _ => self.install_project_deps(&project.id, projects)?,
+        };
         let sync_project_index = self.sync_project(&project.id, projects)?;
         let run_target_index = self.get_or_insert_node(node);
 
@@ -387,16 +437,17 @@ impl DepGraph {
             trace!(
                 target: LOG_TARGET,
                 "Adding dependencies {} from target {}",
-                map_list(&task.deps, |f| color::symbol(f)),
+                map_list(&task.deps, |d| color::symbol(d.label())),
                 color::target(&target_id),
             );
 
-            for dep_target_id in &task.deps {
-                let dep_target = Target::parse(dep_target_id)?;
+            for dep in &task.deps {
+                let dep_target = Target::parse(&dep.target)?;
 
                 if let Some(run_dep_target_index) = self.insert_target(
                     &dep_target.project_id.unwrap(),
                     &dep_target.task_id,
+                    Some(dep),
                     projects,
                     touched_files,
                 )? {
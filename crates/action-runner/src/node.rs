@@ -1,12 +1,12 @@
 use moon_lang::SupportedLanguage;
 use moon_project::ProjectID;
-use moon_task::TargetID;
+use moon_task::TaskDependencyConfig;
 use std::hash::{Hash, Hasher};
 
 #[derive(Clone, Eq)]
 pub enum Node {
     InstallDeps(SupportedLanguage),
-    RunTarget(TargetID),
+    RunTarget(TaskDependencyConfig),
     SetupToolchain,
     SyncProject(SupportedLanguage, ProjectID),
 }
@@ -15,7 +15,7 @@ impl Node {
     pub fn label(&self) -> String {
         match self {
             Node::InstallDeps(lang) => format!("Install{}Deps", lang),
-            Node::RunTarget(id) => format!("RunTarget({})", id),
+            Node::RunTarget(target) => format!("RunTarget({})", target.label()),
             Node::SetupToolchain => "SetupToolchain".into(),
             Node::SyncProject(lang, id) => format!("Sync{}Project({})", lang, id),
         }
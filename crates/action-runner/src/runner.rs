@@ -3,8 +3,8 @@ use crate::errors::{ActionRunnerError, DepGraphError};
 use crate::node::Node;
 use console::Term;
 use moon_action::{
-    install_node_deps, run_target, setup_toolchain, sync_node_project, Action, ActionContext,
-    ActionStatus,
+    install_bun_deps, install_node_deps, install_python_deps, run_target, setup_toolchain,
+    sync_node_project, Action, ActionContext, ActionStatus,
 };
 use moon_error::MoonError;
 use moon_lang::SupportedLanguage;
@@ -29,10 +29,12 @@ async fn run_action(
 ) -> Result<(), ActionRunnerError> {
     let result = match node {
         Node::InstallDeps(lang) => match lang {
+            SupportedLanguage::Bun => install_bun_deps(action, context, workspace).await,
             SupportedLanguage::Node => install_node_deps(action, context, workspace).await,
+            SupportedLanguage::Python => install_python_deps(action, context, workspace).await,
             _ => Ok(ActionStatus::Passed),
         },
-        Node::RunTarget(target_id) => run_target(action, context, workspace, target_id).await,
+        Node::RunTarget(target) => run_target(action, context, workspace, target).await,
         Node::SetupToolchain => setup_toolchain(action, context, workspace).await,
         Node::SyncProject(lang, project_id) => match lang {
             SupportedLanguage::Node => {
@@ -43,6 +43,7 @@ async fn create_tasks_project_graph() -> ProjectGraph {
             ("build-c".to_owned(), "build-c".to_owned()),
             ("chain".to_owned(), "chain".to_owned()),
             ("cycle".to_owned(), "cycle".to_owned()),
+            ("depOverrides".to_owned(), "dep-overrides".to_owned()),
             ("inputA".to_owned(), "input-a".to_owned()),
             ("inputB".to_owned(), "input-b".to_owned()),
             ("inputC".to_owned(), "input-c".to_owned()),
@@ -201,6 +202,41 @@ mod run_target {
         );
     }
 
+    #[tokio::test]
+    async fn creates_distinct_nodes_for_dep_overrides() {
+        let projects = create_tasks_project_graph().await;
+
+        let mut graph = DepGraph::default();
+        graph
+            .run_target(
+                &Target::new("depOverrides", "serve").unwrap(),
+                &projects,
+                None,
+            )
+            .unwrap();
+        graph
+            .run_target(
+                &Target::new("depOverrides", "bundle").unwrap(),
+                &projects,
+                None,
+            )
+            .unwrap();
+
+        assert_snapshot!(graph.to_dot());
+
+        assert_eq!(
+            sort_batches(graph.sort_batched_topological().unwrap()),
+            vec![
+                vec![NodeIndex::new(0)],
+                vec![NodeIndex::new(1), NodeIndex::new(2)],
+                // Variants of the same target never run in parallel
+                vec![NodeIndex::new(4)],
+                vec![NodeIndex::new(5)],
+                vec![NodeIndex::new(3)]
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn avoids_dupe_targets() {
         let projects = create_project_graph().await;
@@ -4,10 +4,23 @@ use moon_task::{TargetError, TaskError};
 use moon_toolchain::ToolchainError;
 use moon_vcs::VcsError;
 use moon_workspace::WorkspaceError;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ActionError {
+    #[error("Failed to parse env file <path>{0}</path>: {1}")]
+    InvalidEnvFile(PathBuf, String),
+
+    #[error("Target <id>{0}</id> requires Bun, but it has not been configured in <file>.moon/workspace.yml</file>.")]
+    MissingBunToolchain(String),
+
+    #[error("Target <id>{0}</id> requires Deno, but it has not been configured in <file>.moon/workspace.yml</file>.")]
+    MissingDenoToolchain(String),
+
+    #[error("Target <id>{0}</id> requires Python, but it has not been configured in <file>.moon/workspace.yml</file>.")]
+    MissingPythonToolchain(String),
+
     #[error(transparent)]
     Moon(#[from] MoonError),
 
@@ -0,0 +1,103 @@
+use crate::action::{Action, ActionStatus};
+use crate::context::ActionContext;
+use crate::errors::ActionError;
+use moon_error::map_io_to_fs_error;
+use moon_lang::has_vendor_installed_dependencies;
+use moon_lang_bun::{BUN, BUN_INSTALL};
+use moon_logger::{debug, warn};
+use moon_terminal::{label_checkpoint, Checkpoint};
+use moon_utils::{fs, is_ci, is_offline};
+use moon_workspace::Workspace;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const LOG_TARGET: &str = "moon:action:install-bun-deps";
+
+pub async fn install_bun_deps(
+    _action: &mut Action,
+    context: &ActionContext,
+    workspace: Arc<RwLock<Workspace>>,
+) -> Result<ActionStatus, ActionError> {
+    let workspace = workspace.read().await;
+
+    let bun = match workspace.toolchain.get_bun() {
+        Some(bun) => bun,
+        None => {
+            debug!(
+                target: LOG_TARGET,
+                "Bun has not been configured, skipping Bun dependencies"
+            );
+
+            return Ok(ActionStatus::Skipped);
+        }
+    };
+
+    let mut cache = workspace.cache.cache_workspace_state().await?;
+
+    // Get the last modified time of the root lockfile
+    let lockfile_name = bun.get_lock_filename();
+    let lockfile = workspace.root.join(&lockfile_name);
+    let mut last_modified = 0;
+
+    if lockfile.exists() {
+        let lockfile_metadata = fs::metadata(&lockfile).await?;
+
+        last_modified = cache.to_millis(
+            lockfile_metadata
+                .modified()
+                .map_err(|e| map_io_to_fs_error(e, lockfile.clone()))?,
+        );
+    }
+
+    // If a `package.json` has been modified manually, we should account for that
+    let has_modified_manifests = context
+        .touched_files
+        .iter()
+        .any(|f| f.ends_with(&BUN_INSTALL.manifest_filename) || f.ends_with(&lockfile_name));
+
+    // Install deps if the lockfile has been modified
+    // since the last time dependencies were installed!
+    if has_modified_manifests
+        || last_modified == 0
+        || last_modified > cache.item.last_bun_install_time
+    {
+        debug!(target: LOG_TARGET, "Installing Bun dependencies");
+
+        // When in CI, we can avoid installing dependencies because
+        // we can assume they've already been installed before moon runs!
+        if is_ci() && has_vendor_installed_dependencies(&workspace.root, &BUN) {
+            warn!(
+                target: LOG_TARGET,
+                "In a CI environment and dependencies already exist, skipping install"
+            );
+
+            return Ok(ActionStatus::Skipped);
+        }
+
+        if is_offline() {
+            warn!(
+                target: LOG_TARGET,
+                "No internet connection, assuming offline and skipping install"
+            );
+
+            return Ok(ActionStatus::Skipped);
+        }
+
+        println!("{}", label_checkpoint("bun install", Checkpoint::Pass));
+
+        bun.install_dependencies(&workspace.toolchain).await?;
+
+        // Update the cache with the timestamp
+        cache.item.last_bun_install_time = cache.now_millis();
+        cache.save().await?;
+
+        return Ok(ActionStatus::Passed);
+    }
+
+    debug!(
+        target: LOG_TARGET,
+        "Lockfile has not changed since last install, skipping Bun dependencies",
+    );
+
+    Ok(ActionStatus::Skipped)
+}
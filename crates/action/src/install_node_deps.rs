@@ -55,9 +55,11 @@ fn add_package_manager(workspace: &Workspace, package_json: &mut PackageJson) ->
     false
 }
 
-/// Add `engines` constraint to root `package.json`.
+/// Add `engines` constraint to root `package.json`. Versions detected from
+/// the workspace are not synced, as `engines` may be their source.
 fn add_engines_constraint(workspace: &Workspace, package_json: &mut PackageJson) -> bool {
     if workspace.config.node.add_engines_constraint
+        && workspace.config.node.version_configured
         && package_json.add_engine("node", &workspace.config.node.version)
     {
         debug!(
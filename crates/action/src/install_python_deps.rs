@@ -0,0 +1,138 @@
+use crate::action::{Action, ActionStatus};
+use crate::context::ActionContext;
+use crate::errors::ActionError;
+use moon_config::{ProjectLanguage, PythonVenvScope};
+use moon_error::map_io_to_fs_error;
+use moon_lang_python::{python, PIP};
+use moon_logger::{color, debug, warn};
+use moon_terminal::{label_checkpoint, Checkpoint};
+use moon_utils::{fs, is_offline};
+use moon_workspace::Workspace;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const LOG_TARGET: &str = "moon:action:install-python-deps";
+
+pub async fn install_python_deps(
+    _action: &mut Action,
+    context: &ActionContext,
+    workspace: Arc<RwLock<Workspace>>,
+) -> Result<ActionStatus, ActionError> {
+    let workspace = workspace.read().await;
+
+    let python = match workspace.toolchain.get_python() {
+        Some(python) => python,
+        None => {
+            debug!(
+                target: LOG_TARGET,
+                "Python has not been configured, skipping Python dependencies"
+            );
+
+            return Ok(ActionStatus::Skipped);
+        }
+    };
+
+    // Determine which directories own a virtual environment
+    let mut roots: Vec<PathBuf> = vec![];
+
+    match python.config.venv_scope {
+        PythonVenvScope::Project => {
+            for project_id in workspace.projects.ids() {
+                let project = workspace.projects.load(&project_id)?;
+
+                if matches!(project.config.language, ProjectLanguage::Python) {
+                    roots.push(project.root);
+                }
+            }
+        }
+        PythonVenvScope::Workspace => {
+            roots.push(workspace.root.clone());
+        }
+    };
+
+    let mut cache = workspace.cache.cache_workspace_state().await?;
+    let mut installed = false;
+
+    for root in roots {
+        let venv_dir = python.get_venv_dir(&workspace.root, &root);
+        let venv_key = root.to_string_lossy().to_string();
+        let last_install_time = cache
+            .item
+            .last_python_install_times
+            .get(&venv_key)
+            .copied()
+            .unwrap_or_default();
+
+        let deps_file = match python::find_dependencies_file(&root) {
+            Some(file) => file,
+            None => {
+                debug!(
+                    target: LOG_TARGET,
+                    "No requirements.txt or pyproject.toml found in {}, skipping",
+                    color::path(&root)
+                );
+
+                continue;
+            }
+        };
+
+        // Get the last modified time of the dependencies file
+        let deps_file_metadata = fs::metadata(&deps_file).await?;
+        let last_modified = cache.to_millis(
+            deps_file_metadata
+                .modified()
+                .map_err(|e| map_io_to_fs_error(e, deps_file.clone()))?,
+        );
+
+        // If a dependencies file has been modified manually, we should account for that
+        let has_modified_manifests = context.touched_files.iter().any(|f| {
+            f.starts_with(&root)
+                && (f.ends_with(PIP.lock_filenames[0]) || f.ends_with(PIP.manifest_filename))
+        });
+
+        // Install deps if the virtual environment doesn't exist, or the
+        // dependencies file has been modified since the last install!
+        if !venv_dir.exists() || has_modified_manifests || last_modified > last_install_time {
+            if is_offline() {
+                warn!(
+                    target: LOG_TARGET,
+                    "No internet connection, assuming offline and skipping install into {}",
+                    color::path(&venv_dir)
+                );
+
+                continue;
+            }
+
+            debug!(
+                target: LOG_TARGET,
+                "Installing Python dependencies into {}",
+                color::path(&venv_dir)
+            );
+
+            println!("{}", label_checkpoint("pip install", Checkpoint::Pass));
+
+            python.create_venv(&venv_dir).await?;
+            python.install_dependencies(&venv_dir, &root).await?;
+
+            // Track the timestamp per root, as each has its own dependencies file
+            let now = cache.now_millis();
+
+            cache.item.last_python_install_times.insert(venv_key, now);
+            installed = true;
+        }
+    }
+
+    if installed {
+        cache.save().await?;
+
+        return Ok(ActionStatus::Passed);
+    }
+
+    debug!(
+        target: LOG_TARGET,
+        "Dependencies have not changed since last install, skipping Python dependencies",
+    );
+
+    Ok(ActionStatus::Skipped)
+}
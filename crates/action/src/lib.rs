@@ -1,7 +1,9 @@
 mod action;
 mod context;
 mod errors;
+mod install_bun_deps;
 mod install_node_deps;
+mod install_python_deps;
 mod run_target;
 mod setup_toolchain;
 mod sync_node_project;
@@ -10,7 +12,9 @@ mod target;
 pub use action::*;
 pub use context::*;
 pub use errors::ActionError;
+pub use install_bun_deps::*;
 pub use install_node_deps::*;
+pub use install_python_deps::*;
 pub use run_target::*;
 pub use setup_toolchain::*;
 pub use sync_node_project::*;
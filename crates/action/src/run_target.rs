@@ -1,10 +1,10 @@
 use crate::action::{Action, ActionStatus};
 use crate::context::ActionContext;
 use crate::errors::ActionError;
-use crate::target::{node, system, HydrateFrom, TargetRunner};
+use crate::target::{bun, deno, node, python, system, HydrateFrom, TargetRunner};
 use moon_config::PlatformType;
 use moon_logger::{color, debug};
-use moon_task::Target;
+use moon_task::{Target, TaskDependencyConfig};
 use moon_terminal::Checkpoint;
 use moon_workspace::Workspace;
 use std::sync::Arc;
@@ -16,13 +16,20 @@ pub async fn run_target(
     action: &mut Action,
     context: &ActionContext,
     workspace: Arc<RwLock<Workspace>>,
-    target_id: &str,
+    target: &TaskDependencyConfig,
 ) -> Result<ActionStatus, ActionError> {
-    let (project_id, task_id) = Target::parse(target_id)?.ids()?;
+    let (project_id, task_id) = Target::parse(&target.target)?.ids()?;
     let workspace = workspace.read().await;
     let project = workspace.projects.load(&project_id)?;
-    let task = project.get_task(&task_id)?;
-    let mut runner = TargetRunner::new(&workspace, &project, task).await?;
+    let mut task = project.get_task(&task_id)?.clone();
+
+    // Dependencies may run the target with their own args and env vars,
+    // which are also included when hashing
+    task.args.extend(target.args.iter().cloned());
+    task.env.extend(target.env.clone());
+
+    let task = &task;
+    let mut runner = TargetRunner::new(&workspace, &project, task, target).await?;
 
     debug!(
         target: LOG_TARGET,
@@ -47,22 +54,44 @@ pub async fn run_target(
     if task.options.cache {
         let common_hasher = runner.create_common_hasher(context).await?;
 
-        let platform_hasher = match task.platform {
-            PlatformType::Node => node::create_target_hasher(&workspace, &project)?,
-            _ => node::create_target_hasher(&workspace, &project)?,
+        let cache_location = match task.platform {
+            PlatformType::Bun => {
+                let platform_hasher = bun::create_target_hasher(&workspace, &project)?;
+
+                runner.is_cached(common_hasher, platform_hasher).await?
+            }
+            PlatformType::Deno => {
+                let platform_hasher = deno::create_target_hasher(&workspace, &project)?;
+
+                runner.is_cached(common_hasher, platform_hasher).await?
+            }
+            PlatformType::Python => {
+                let platform_hasher = python::create_target_hasher(&workspace, &project).await?;
+
+                runner.is_cached(common_hasher, platform_hasher).await?
+            }
+            _ => {
+                let platform_hasher = node::create_target_hasher(&workspace, &project)?;
+
+                runner.is_cached(common_hasher, platform_hasher).await?
+            }
         };
 
-        if let Some(cache_location) = runner.is_cached(common_hasher, platform_hasher).await? {
+        if let Some(cache_location) = cache_location {
             // Only hydrate when the hash is different from the previous build,
             // as we can assume the outputs from the previous build still exist?
-            if matches!(cache_location, HydrateFrom::LocalCache) {
-                runner.hydrate_outputs().await?;
-            }
+            // If the archive is corrupt, fallthrough and run the target.
+            let hydrated = match cache_location {
+                HydrateFrom::LocalCache => runner.hydrate_outputs().await?,
+                HydrateFrom::PreviousOutput => true,
+            };
 
-            runner.print_checkpoint(Checkpoint::Pass, "(cached)");
-            runner.print_cache_item();
+            if hydrated {
+                runner.print_checkpoint(Checkpoint::Pass, "(cached)");
+                runner.print_cache_item();
 
-            return Ok(ActionStatus::Cached);
+                return Ok(ActionStatus::Cached);
+            }
         }
     }
 
@@ -74,10 +103,13 @@ pub async fn run_target(
     };
 
     let mut command = match task.platform {
+        PlatformType::Bun => bun::create_target_command(&workspace, task)?,
+        PlatformType::Deno => deno::create_target_command(&workspace, task)?,
         PlatformType::Node => {
             node::create_target_command(context, &workspace, &project, task).await?
         }
-        _ => system::create_target_command(task, working_dir),
+        PlatformType::Python => python::create_target_command(&workspace, &project, task)?,
+        _ => system::create_target_command(task, working_dir, &context.passthrough_args),
     };
 
     command
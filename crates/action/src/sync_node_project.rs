@@ -248,6 +248,7 @@ pub async fn sync_node_project(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use moon_config::constants::EXTENDS_CACHE_TTL;
     use moon_config::GlobalProjectConfig;
     use moon_utils::test::create_sandbox;
 
@@ -261,6 +262,7 @@ mod tests {
             fixture.path(),
             &GlobalProjectConfig::default(),
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -293,6 +295,7 @@ mod tests {
             fixture.path(),
             &GlobalProjectConfig::default(),
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -332,6 +335,7 @@ mod tests {
             fixture.path(),
             &GlobalProjectConfig::default(),
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
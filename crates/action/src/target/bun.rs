@@ -0,0 +1,67 @@
+use crate::errors::ActionError;
+use moon_lang_node::package::PackageJson;
+use moon_platform_bun::BunTargetHasher;
+use moon_project::Project;
+use moon_task::Task;
+use moon_toolchain::{get_path_env_var, Executable};
+use moon_utils::process::Command;
+use moon_workspace::Workspace;
+
+/// Runs a task command through our toolchain's installed Bun binary.
+/// The `bun` and `bunx` commands are executed directly, while all other
+/// commands are treated as a package binary and executed with `bun run`.
+/// This would look something like the following:
+///
+/// ~/.moon/tools/bun/1.2.3/bun run eslint --cache --color --fix
+pub fn create_target_command(workspace: &Workspace, task: &Task) -> Result<Command, ActionError> {
+    let bun = match workspace.toolchain.get_bun() {
+        Some(bun) => bun,
+        None => return Err(ActionError::MissingBunToolchain(task.target.clone())),
+    };
+
+    let mut args = vec![];
+
+    match task.command.as_str() {
+        "bun" => {}
+        "bunx" => {
+            args.push("x");
+        }
+        bin => {
+            args.extend(["run", bin]);
+        }
+    };
+
+    // Create the command
+    let mut command = Command::new(bun.get_bin_path());
+
+    command.args(&args).args(&task.args).envs(&task.env).env(
+        "PATH",
+        get_path_env_var(bun.get_bin_path().parent().unwrap()),
+    );
+
+    Ok(command)
+}
+
+pub fn create_target_hasher(
+    workspace: &Workspace,
+    project: &Project,
+) -> Result<BunTargetHasher, ActionError> {
+    let bun_version = match &workspace.config.bun {
+        Some(bun_config) => bun_config.version.clone(),
+        None => String::new(),
+    };
+
+    let mut hasher = BunTargetHasher::new(bun_version);
+
+    // Hash root configs first
+    if let Some(root_package) = PackageJson::read(&workspace.root)? {
+        hasher.hash_package_json(&root_package);
+    }
+
+    // Hash project configs second so they can override
+    if let Some(package) = PackageJson::read(&project.root)? {
+        hasher.hash_package_json(&package);
+    }
+
+    Ok(hasher)
+}
@@ -0,0 +1,75 @@
+use crate::errors::ActionError;
+use moon_lang_deno::{deno, DENO_LOCK_FILENAME};
+use moon_platform_deno::DenoTargetHasher;
+use moon_project::Project;
+use moon_task::Task;
+use moon_toolchain::{get_path_env_var, Executable};
+use moon_utils::fs;
+use moon_utils::process::Command;
+use moon_workspace::Workspace;
+use std::path::Path;
+
+/// Runs a task command through our toolchain's installed Deno binary.
+/// The `deno` command is executed directly, while all other commands
+/// are treated as a module and executed with `deno run`.
+/// This would look something like the following:
+///
+/// ~/.moon/tools/deno/1.2.3/deno run --allow-net server.ts
+pub fn create_target_command(workspace: &Workspace, task: &Task) -> Result<Command, ActionError> {
+    let deno = match workspace.toolchain.get_deno() {
+        Some(deno) => deno,
+        None => return Err(ActionError::MissingDenoToolchain(task.target.clone())),
+    };
+
+    let mut args = vec![];
+
+    if task.command != "deno" {
+        args.extend(["run", task.command.as_str()]);
+    }
+
+    // Create the command
+    let mut command = Command::new(deno.get_bin_path());
+
+    command.args(&args).args(&task.args).envs(&task.env).env(
+        "PATH",
+        get_path_env_var(deno.get_bin_path().parent().unwrap()),
+    );
+
+    Ok(command)
+}
+
+fn hash_deno_files(hasher: &mut DenoTargetHasher, dir: &Path) -> Result<(), ActionError> {
+    if let Some(config_path) = deno::find_config_file(dir) {
+        hasher.hash_deno_json(fs::sync_read_json_string(&config_path)?);
+    }
+
+    let lock_path = dir.join(DENO_LOCK_FILENAME);
+
+    if lock_path.exists() {
+        hasher.hash_deno_lock(fs::sync_read_json_string(&lock_path)?);
+    }
+
+    Ok(())
+}
+
+pub fn create_target_hasher(
+    workspace: &Workspace,
+    project: &Project,
+) -> Result<DenoTargetHasher, ActionError> {
+    let deno_version = match &workspace.config.deno {
+        Some(deno_config) => deno_config.version.clone(),
+        None => String::new(),
+    };
+
+    let mut hasher = DenoTargetHasher::new(deno_version);
+
+    // Hash root configs first
+    hash_deno_files(&mut hasher, &workspace.root)?;
+
+    // Hash project configs second, unless the project is the root
+    if project.root != workspace.root {
+        hash_deno_files(&mut hasher, &project.root)?;
+    }
+
+    Ok(hasher)
+}
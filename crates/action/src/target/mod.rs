@@ -0,0 +1,8 @@
+pub mod bun;
+pub mod deno;
+pub mod node;
+pub mod python;
+mod runner;
+pub mod system;
+
+pub use runner::*;
@@ -0,0 +1,100 @@
+use crate::errors::ActionError;
+use moon_error::map_io_to_fs_error;
+use moon_lang_python::{python, PIP};
+use moon_platform_python::PythonTargetHasher;
+use moon_project::Project;
+use moon_task::Task;
+use moon_toolchain::{Executable, Installable};
+use moon_utils::path::to_virtual_string;
+use moon_utils::process::Command;
+use moon_workspace::Workspace;
+use std::fs;
+use std::path::Path;
+
+/// Runs a task command within the project's virtual environment. Binaries
+/// installed into the virtual environment take precedence, while `python`
+/// falls back to the configured interpreter. All other commands are
+/// executed with the virtual environment prepended to `PATH`.
+/// This would look something like the following:
+///
+/// /path/to/workspace/.venv/bin/pytest --verbose
+pub fn create_target_command(
+    workspace: &Workspace,
+    project: &Project,
+    task: &Task,
+) -> Result<Command, ActionError> {
+    let python = match workspace.toolchain.get_python() {
+        Some(python) => python,
+        None => return Err(ActionError::MissingPythonToolchain(task.target.clone())),
+    };
+
+    let venv_dir = python.get_venv_dir(&workspace.root, &project.root);
+    let venv_bin = python::get_venv_bin_dir(&venv_dir).join(python::get_bin_name(&task.command));
+
+    // Create the command
+    let mut command = if venv_bin.exists() {
+        Command::new(venv_bin)
+    } else if task.command == "python" || task.command == "python3" {
+        Command::new(python.get_bin_path())
+    } else {
+        Command::new(&task.command)
+    };
+
+    command
+        .args(&task.args)
+        .envs(&task.env)
+        .env("PATH", python.get_venv_path_env_var(&venv_dir))
+        .env("VIRTUAL_ENV", &venv_dir);
+
+    Ok(command)
+}
+
+fn hash_lockfiles(
+    hasher: &mut PythonTargetHasher,
+    workspace_root: &Path,
+    dir: &Path,
+) -> Result<(), ActionError> {
+    for lockfile in PIP.lock_filenames {
+        let lockfile_path = dir.join(lockfile);
+
+        if lockfile_path.exists() {
+            let contents = fs::read_to_string(&lockfile_path)
+                .map_err(|e| map_io_to_fs_error(e, lockfile_path.clone()))?;
+
+            hasher.hash_lockfile(
+                to_virtual_string(
+                    lockfile_path
+                        .strip_prefix(workspace_root)
+                        .unwrap_or(&lockfile_path),
+                )?,
+                contents,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The interpreter is not managed by the toolchain, so hash the version
+/// that is actually installed, instead of the configured requirement.
+pub async fn create_target_hasher(
+    workspace: &Workspace,
+    project: &Project,
+) -> Result<PythonTargetHasher, ActionError> {
+    let python_version = match workspace.toolchain.get_python() {
+        Some(python) => python.get_installed_version().await?,
+        None => String::new(),
+    };
+
+    let mut hasher = PythonTargetHasher::new(python_version);
+
+    // Hash root lockfiles first
+    hash_lockfiles(&mut hasher, &workspace.root, &workspace.root)?;
+
+    // Hash project lockfiles second, unless the project is the root
+    if project.root != workspace.root {
+        hash_lockfiles(&mut hasher, &workspace.root, &project.root)?;
+    }
+
+    Ok(hasher)
+}
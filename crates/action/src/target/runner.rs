@@ -0,0 +1,645 @@
+use crate::action::Attempt;
+use crate::context::ActionContext;
+use crate::errors::ActionError;
+use moon_cache::{CacheItem, RunTargetState};
+use moon_config::TaskOutputStyle;
+use moon_error::{map_io_to_fs_error, MoonError};
+use moon_hasher::{convert_paths_to_strings, to_hash, Digest, Hasher, Sha256, TargetHasher};
+use moon_logger::{color, debug, warn};
+use moon_project::Project;
+use moon_task::{Task, TaskDependencyConfig};
+use moon_terminal::{label_checkpoint, Checkpoint};
+use moon_utils::{
+    fs, is_ci, is_test_env, path,
+    process::{self, output_to_string, Command, Output},
+    time,
+};
+use moon_workspace::Workspace;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+const LOG_TARGET: &str = "moon:action:run-target";
+
+pub enum HydrateFrom {
+    LocalCache,
+    PreviousOutput,
+}
+
+pub struct TargetRunner<'a> {
+    pub cache: CacheItem<RunTargetState>,
+
+    // Hash of the last successful run, persisted until the command succeeds
+    previous_hash: String,
+
+    project: &'a Project,
+
+    task: &'a Task,
+
+    workspace: &'a Workspace,
+}
+
+impl<'a> TargetRunner<'a> {
+    pub async fn new(
+        workspace: &'a Workspace,
+        project: &'a Project,
+        task: &'a Task,
+        target: &TaskDependencyConfig,
+    ) -> Result<TargetRunner<'a>, MoonError> {
+        // Variants with custom args or env vars have their own run state
+        let cache = match target.get_variant_hash() {
+            Some(variant_hash) => {
+                workspace
+                    .cache
+                    .cache_run_target_variant_state(&task.target, &variant_hash)
+                    .await?
+            }
+            None => workspace.cache.cache_run_target_state(&task.target).await?,
+        };
+
+        Ok(TargetRunner {
+            previous_hash: cache.item.hash.clone(),
+            cache,
+            project,
+            task,
+            workspace,
+        })
+    }
+
+    /// Cache outputs to the `.moon/cache/out` folder and to the cloud,
+    /// so that subsequent builds are faster, and any local outputs
+    /// can be rehydrated easily.
+    pub async fn cache_outputs(&self) -> Result<(), ActionError> {
+        let hash = &self.cache.item.hash;
+
+        if !hash.is_empty() && !self.task.outputs.is_empty() {
+            self.workspace
+                .cache
+                .create_hash_archive(hash, &self.project.root, &self.task.outputs)
+                .await?;
+
+            self.workspace
+                .cache
+                .save_target_outputs_hash(&self.task.target, hash)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// If we are cached (hash match), hydrate the project with the
+    /// cached task outputs found in the hashed archive. Returns false
+    /// if the archive could not be hydrated, and the target must run.
+    pub async fn hydrate_outputs(&mut self) -> Result<bool, ActionError> {
+        let hash = self.cache.item.hash.clone();
+
+        if hash.is_empty() {
+            return Ok(false);
+        }
+
+        // Remove previous outputs so we avoid stale artifacts
+        for output in &self.task.output_paths {
+            fs::remove(output).await?;
+        }
+
+        // Hydrate outputs from the cache
+        let hydrated = self
+            .workspace
+            .cache
+            .hydrate_from_hash_archive(&hash, &self.project.root)
+            .await?;
+
+        if !hydrated {
+            self.cache.item.cache_hits -= 1;
+            self.save_cache_miss().await?;
+
+            return Ok(false);
+        }
+
+        // Update the run state with the new hash
+        self.cache.save().await?;
+
+        self.workspace
+            .cache
+            .save_target_outputs_hash(&self.task.target, &hash)
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Save a cache miss immediately, as failed commands never save the run state.
+    /// The previous hash is persisted until the command succeeds, otherwise
+    /// a failed run would be treated as a cache hit on the next run.
+    async fn save_cache_miss(&mut self) -> Result<(), ActionError> {
+        let hash = std::mem::replace(&mut self.cache.item.hash, self.previous_hash.clone());
+
+        self.cache.item.cache_misses += 1;
+        self.cache.save().await?;
+        self.cache.item.hash = hash;
+
+        Ok(())
+    }
+
+    /// Create a hasher that is shared amongst all platforms.
+    /// Primarily includes task information.
+    pub async fn create_common_hasher(
+        &self,
+        context: &ActionContext,
+    ) -> Result<TargetHasher, ActionError> {
+        let vcs = &self.workspace.vcs;
+        let task = &self.task;
+        let project = &self.project;
+        let workspace = &self.workspace;
+        let globset = task.create_globset()?;
+        let mut hasher = TargetHasher::new();
+
+        hasher.hash_archive_settings(workspace.config.action_runner.archive_preserve_mtimes);
+        hasher.hash_project_deps(self.project.get_dependency_ids());
+        hasher.hash_task(task);
+        hasher.hash_args(&context.passthrough_args);
+
+        // For input files, hash them with the vcs layer first
+        if !task.input_paths.is_empty() {
+            let mut files = convert_paths_to_strings(&task.input_paths, &workspace.root)?;
+
+            // Sort for deterministic caching within the vcs layer
+            files.sort();
+
+            if !files.is_empty() {
+                hasher.hash_inputs(vcs.get_file_hashes(&files).await?);
+            }
+        }
+
+        // For input globs, it's much more performant to:
+        //  `git ls-tree` -> match against glob patterns
+        // Then it is to:
+        //  glob + walk the file system -> `git hash-object`
+        if !task.input_globs.is_empty() {
+            let mut hashed_file_tree = vcs.get_file_tree_hashes(&project.source).await?;
+
+            // Input globs are absolute paths, so we must do the same
+            hashed_file_tree
+                .retain(|k, _| globset.matches(&workspace.root.join(k)).unwrap_or(false));
+
+            hasher.hash_inputs(hashed_file_tree);
+        }
+
+        // Include local file changes so that development builds work.
+        // Also run this LAST as it should take highest precedence!
+        let local_files = vcs.get_touched_files().await?;
+
+        if !local_files.all.is_empty() {
+            // Only hash files that are within the task's inputs
+            let mut files = local_files
+                .all
+                .into_iter()
+                .filter(|f| {
+                    // Deleted files will crash `git hash-object`
+                    !local_files.deleted.contains(f)
+                        && globset.matches(&workspace.root.join(f)).unwrap_or(false)
+                })
+                .collect::<Vec<String>>();
+
+            // Sort for deterministic caching within the vcs layer
+            files.sort();
+
+            if !files.is_empty() {
+                hasher.hash_inputs(vcs.get_file_hashes(&files).await?);
+            }
+        }
+
+        // Env files are commonly gitignored, and ignored files are skipped
+        // by the vcs layer, so hash the file's contents directly
+        if let Some(env_file) = self.get_env_file() {
+            if env_file.exists() {
+                let contents = tokio::fs::read(&env_file)
+                    .await
+                    .map_err(|e| map_io_to_fs_error(e, env_file.clone()))?;
+                let file =
+                    path::to_string(env_file.strip_prefix(&workspace.root).unwrap_or(&env_file))?;
+
+                hasher.hash_inputs(BTreeMap::from([(
+                    file,
+                    format!("{:x}", Sha256::digest(&contents)),
+                )]));
+            }
+        }
+
+        Ok(hasher)
+    }
+
+    fn get_env_file(&self) -> Option<PathBuf> {
+        self.task
+            .options
+            .env_file
+            .as_ref()
+            .map(|file| path::expand_root_path(file, &self.workspace.root, &self.project.root))
+    }
+
+    /// Load environment variables from the task's env file, if configured.
+    /// Variables explicitly defined in the task's `env` take precedence.
+    pub fn load_env_file(&self) -> Result<HashMap<String, String>, ActionError> {
+        let mut env_vars = HashMap::new();
+
+        let env_file = match self.get_env_file() {
+            Some(file) => file,
+            None => return Ok(env_vars),
+        };
+
+        if !env_file.exists() {
+            debug!(
+                target: LOG_TARGET,
+                "Env file {} does not exist, skipping",
+                color::path(&env_file)
+            );
+
+            return Ok(env_vars);
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Loading env vars from {}",
+            color::path(&env_file)
+        );
+
+        let iter = dotenvy::from_path_iter(&env_file)
+            .map_err(|e| ActionError::InvalidEnvFile(env_file.clone(), e.to_string()))?;
+
+        for item in iter {
+            let (key, value) =
+                item.map_err(|e| ActionError::InvalidEnvFile(env_file.clone(), e.to_string()))?;
+
+            if !self.task.env.contains_key(&key) {
+                env_vars.insert(key, value);
+            }
+        }
+
+        Ok(env_vars)
+    }
+
+    /// Create environment variables to pass to the child process. Precedence
+    /// (highest first) is: moon variables, task `env`, the env file, and
+    /// lastly the inherited system environment.
+    pub async fn create_env_vars(&self) -> Result<HashMap<String, String>, ActionError> {
+        let mut env_vars = self.load_env_file()?;
+
+        env_vars.insert(
+            "MOON_CACHE_DIR".to_owned(),
+            path::to_string(&self.workspace.cache.dir)?,
+        );
+        env_vars.insert("MOON_PROJECT_ID".to_owned(), self.project.id.clone());
+        env_vars.insert(
+            "MOON_PROJECT_ROOT".to_owned(),
+            path::to_string(&self.project.root)?,
+        );
+        env_vars.insert(
+            "MOON_PROJECT_SOURCE".to_owned(),
+            self.project.source.clone(),
+        );
+        env_vars.insert("MOON_TARGET".to_owned(), self.task.target.clone());
+        env_vars.insert(
+            "MOON_TOOLCHAIN_DIR".to_owned(),
+            path::to_string(&self.workspace.toolchain.dir)?,
+        );
+        env_vars.insert(
+            "MOON_WORKSPACE_ROOT".to_owned(),
+            path::to_string(&self.workspace.root)?,
+        );
+        env_vars.insert(
+            "MOON_WORKING_DIR".to_owned(),
+            path::to_string(&self.workspace.working_dir)?,
+        );
+
+        // Store runtime data on the file system so that downstream commands can utilize it
+        let runfile = self
+            .workspace
+            .cache
+            .create_runfile(&self.project.id, self.project)
+            .await?;
+
+        env_vars.insert(
+            "MOON_PROJECT_RUNFILE".to_owned(),
+            path::to_string(&runfile.path)?,
+        );
+
+        Ok(env_vars)
+    }
+
+    /// Hash the target based on all current parameters and return early
+    /// if this target hash has already been cached. Based on the state
+    /// of the target and project, determine the hydration strategy as well.
+    pub async fn is_cached(
+        &mut self,
+        common_hasher: impl Hasher + Serialize,
+        platform_hasher: impl Hasher + Serialize,
+    ) -> Result<Option<HydrateFrom>, ActionError> {
+        let hash = to_hash(&common_hasher, &platform_hasher);
+
+        debug!(
+            target: LOG_TARGET,
+            "Generated hash {} for target {}",
+            color::symbol(&hash),
+            color::id(&self.task.target)
+        );
+
+        // Hash is the same as the previous build, so simply abort!
+        // However, ensure the outputs also exist and were not written by another
+        // variant of this target, otherwise we should hydrate.
+        if self.cache.item.hash == hash && self.has_outputs() && self.owns_outputs(&hash) {
+            debug!(
+                target: LOG_TARGET,
+                "Cache hit for hash {}, reusing previous build",
+                color::symbol(&hash),
+            );
+
+            self.cache.item.cache_hits += 1;
+            self.cache.save().await?;
+
+            return Ok(Some(HydrateFrom::PreviousOutput));
+        }
+
+        self.cache.item.hash = hash.clone();
+
+        // Refresh the hash manifest
+        self.workspace
+            .cache
+            .create_hash_manifest(&hash, &(common_hasher, platform_hasher))
+            .await?;
+
+        // Hash exists in the cache, so hydrate from it
+        if self.workspace.cache.is_hash_cached(&hash) {
+            debug!(
+                target: LOG_TARGET,
+                "Cache hit for hash {}, hydrating from local cache",
+                color::symbol(&hash),
+            );
+
+            // Run state is saved after outputs have been hydrated
+            self.cache.item.cache_hits += 1;
+
+            return Ok(Some(HydrateFrom::LocalCache));
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Cache miss for hash {}, continuing run",
+            color::symbol(&hash),
+        );
+
+        self.save_cache_miss().await?;
+
+        Ok(None)
+    }
+
+    /// Return true if this target is a no-op.
+    pub fn is_no_op(&self) -> bool {
+        self.task.is_no_op()
+    }
+
+    /// Verify that all task outputs exist for the current target.
+    /// TODO: We dont verify contents, should we?
+    pub fn has_outputs(&self) -> bool {
+        self.task.output_paths.iter().all(|p| p.exists())
+    }
+
+    /// Verify that the current outputs were created by a run with the provided hash.
+    /// Variants of a target (deps with custom args or env vars) share the same outputs.
+    pub fn owns_outputs(&self, hash: &str) -> bool {
+        self.task.outputs.is_empty()
+            || self
+                .workspace
+                .cache
+                .get_target_outputs_hash(&self.task.target)
+                == hash
+    }
+
+    /// Run the command as a child process and capture its output. If the process fails
+    /// and `retry_count` is greater than 0, attempt the process again in case it passes.
+    pub async fn run_command(
+        &mut self,
+        context: &ActionContext,
+        command: &mut Command,
+    ) -> Result<Vec<Attempt>, ActionError> {
+        command.envs(self.create_env_vars().await?);
+
+        // Shell commands include passthrough args within the command line
+        if !context.passthrough_args.is_empty() && self.task.get_shell().is_none() {
+            command.args(&context.passthrough_args);
+        }
+
+        if self
+            .workspace
+            .config
+            .action_runner
+            .inherit_colors_for_piped_tasks
+        {
+            command.inherit_colors();
+        }
+
+        let attempt_total = self.task.options.retry_count + 1;
+        let mut attempt_index = 1;
+        let mut attempts = vec![];
+        let is_primary = context.primary_targets.contains(&self.task.target);
+        let is_real_ci = is_ci() && !is_test_env();
+        let stream_output = match self.task.options.output_style {
+            Some(TaskOutputStyle::Stream) => true,
+            Some(TaskOutputStyle::OnExit) => false,
+            None => is_primary || is_real_ci,
+        };
+        let output;
+
+        loop {
+            let mut attempt = Attempt::new(attempt_index);
+
+            let possible_output = if stream_output {
+                // Print label *before* output is streamed since it may stay open forever,
+                // or it may use ANSI escape codes to alter the terminal.
+                self.print_target_label(Checkpoint::Pass, &attempt, attempt_total);
+                self.print_target_command(&context.passthrough_args);
+
+                // If this target matches the primary target (the last task to run),
+                // then we want to stream the output directly to the parent (inherit mode).
+                command
+                    .exec_stream_and_capture_output(if !is_primary {
+                        Some(&self.task.target)
+                    } else {
+                        None
+                    })
+                    .await
+            } else {
+                self.print_target_label(Checkpoint::Start, &attempt, attempt_total);
+                self.print_target_command(&context.passthrough_args);
+
+                // Otherwise we run the process in the background and write the output
+                // once it has completed.
+                command.exec_capture_output().await
+            };
+
+            attempt.done();
+
+            match possible_output {
+                // zero and non-zero exit codes
+                Ok(out) => {
+                    if stream_output {
+                        self.handle_streamed_output(&attempt, attempt_total, &out);
+                    } else {
+                        self.handle_captured_output(&attempt, attempt_total, &out);
+                    }
+
+                    attempts.push(attempt);
+
+                    if out.status.success() {
+                        output = out;
+                        break;
+                    } else if attempt_index >= attempt_total {
+                        return Err(ActionError::Moon(command.output_to_error(&out, false)));
+                    } else {
+                        attempt_index += 1;
+
+                        warn!(
+                            target: LOG_TARGET,
+                            "Target {} failed, running again with attempt {}",
+                            color::target(&self.task.target),
+                            attempt_index
+                        );
+                    }
+                }
+                // process itself failed
+                Err(error) => {
+                    return Err(ActionError::Moon(error));
+                }
+            }
+        }
+
+        // Write the cache with the result and output
+        self.cache.item.exit_code = output.status.code().unwrap_or(0);
+        self.cache.item.last_run_time = self.cache.now_millis();
+        self.cache.item.stderr = output_to_string(&output.stderr);
+        self.cache.item.stdout = output_to_string(&output.stdout);
+        self.cache.save().await?;
+
+        Ok(attempts)
+    }
+
+    pub fn print_cache_item(&self) {
+        let item = &self.cache.item;
+
+        if !item.stderr.is_empty() {
+            eprintln!("{}", item.stderr.trim());
+            eprintln!();
+        }
+
+        if !item.stdout.is_empty() {
+            println!("{}", item.stdout.trim());
+            println!();
+        }
+    }
+
+    pub fn print_checkpoint(&self, checkpoint: Checkpoint, comment: &str) {
+        println!(
+            "{} {}",
+            label_checkpoint(&self.task.target, checkpoint),
+            color::muted(comment)
+        );
+    }
+
+    pub fn print_target_command(&self, passthrough_args: &[String]) {
+        if !self.workspace.config.action_runner.log_running_command {
+            return;
+        }
+
+        let project = &self.project;
+        let task = &self.task;
+
+        let mut args = vec![];
+        args.extend(&task.args);
+        args.extend(passthrough_args);
+
+        let command_line = if args.is_empty() {
+            task.command.clone()
+        } else {
+            format!("{} {}", task.command, process::join_args(args))
+        };
+
+        let working_dir =
+            if task.options.run_from_workspace_root || project.root == self.workspace.root {
+                String::from("workspace")
+            } else {
+                format!(
+                    ".{}{}",
+                    std::path::MAIN_SEPARATOR,
+                    project
+                        .root
+                        .strip_prefix(&self.workspace.root)
+                        .unwrap()
+                        .to_string_lossy(),
+                )
+            };
+
+        let suffix = format!("(in {})", working_dir);
+        let message = format!("{} {}", command_line, color::muted(suffix));
+
+        println!("{}", color::muted_light(message));
+    }
+
+    pub fn print_target_label(&self, checkpoint: Checkpoint, attempt: &Attempt, attempt_total: u8) {
+        let failed = matches!(checkpoint, Checkpoint::Fail);
+        let mut label = label_checkpoint(&self.task.target, checkpoint);
+        let mut comments = vec![];
+
+        if attempt.index > 1 {
+            comments.push(format!("{}/{}", attempt.index, attempt_total));
+        }
+
+        if let Some(duration) = attempt.duration {
+            comments.push(time::elapsed(duration));
+        }
+
+        if !comments.is_empty() {
+            let metadata = color::muted(format!("({})", comments.join(", ")));
+
+            label = format!("{} {}", label, metadata);
+        };
+
+        if failed {
+            eprintln!("{}", label);
+        } else {
+            println!("{}", label);
+        }
+    }
+
+    // Print label *after* output has been captured, so parallel tasks
+    // aren't intertwined and the labels align with the output.
+    fn handle_captured_output(&self, attempt: &Attempt, attempt_total: u8, output: &Output) {
+        self.print_target_label(
+            if output.status.success() {
+                Checkpoint::Pass
+            } else {
+                Checkpoint::Fail
+            },
+            attempt,
+            attempt_total,
+        );
+
+        let stderr = output_to_string(&output.stderr);
+        let stdout = output_to_string(&output.stdout);
+
+        if !stderr.is_empty() {
+            eprintln!("{}", stderr.trim());
+            eprintln!();
+        }
+
+        if !stdout.is_empty() {
+            println!("{}", stdout.trim());
+            println!();
+        }
+    }
+
+    // Only print the label when the process has failed,
+    // as the actual output has already been streamed to the console.
+    fn handle_streamed_output(&self, attempt: &Attempt, attempt_total: u8, output: &Output) {
+        if !output.status.success() {
+            self.print_target_label(Checkpoint::Fail, attempt, attempt_total);
+        }
+    }
+}
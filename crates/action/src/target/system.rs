@@ -0,0 +1,63 @@
+use moon_task::Task;
+use moon_utils::process::{join_args_for_shell, join_shell_args, Command};
+use std::path::Path;
+
+// Passthrough args must be a part of the command line, as args
+// appended after the command line are not passed to the command.
+// Unlike the task's args, they are always quoted, as they come from
+// the user's shell and should never be interpreted a second time.
+fn create_shell_command(
+    shell: &str,
+    task: &Task,
+    args: &[String],
+    passthrough_args: &[String],
+) -> Command {
+    let mut command_line = vec![task.command.clone()];
+    command_line.extend(args.iter().cloned());
+
+    let mut command_line = join_shell_args(shell, command_line);
+
+    if !passthrough_args.is_empty() {
+        command_line.push(' ');
+        command_line.push_str(&join_args_for_shell(shell, passthrough_args));
+    }
+
+    let mut cmd = Command::new_shell(shell, &command_line);
+    cmd.envs(&task.env);
+    cmd
+}
+
+#[cfg(not(windows))]
+pub fn create_target_command(task: &Task, _cwd: &Path, passthrough_args: &[String]) -> Command {
+    if let Some(shell) = task.get_shell() {
+        return create_shell_command(shell, task, &task.args, passthrough_args);
+    }
+
+    let mut cmd = Command::new(&task.command);
+    cmd.args(&task.args).envs(&task.env);
+    cmd
+}
+
+#[cfg(windows)]
+pub fn create_target_command(task: &Task, cwd: &Path, passthrough_args: &[String]) -> Command {
+    use moon_utils::process::is_windows_script;
+
+    let mut args = vec![];
+
+    for arg in &task.args {
+        // cmd.exe requires an absolute path to batch files
+        if is_windows_script(arg) {
+            args.push(cwd.join(arg).to_string_lossy().to_string());
+        } else {
+            args.push(arg.to_owned());
+        }
+    }
+
+    if let Some(shell) = task.get_shell() {
+        return create_shell_command(shell, task, &args, passthrough_args);
+    }
+
+    let mut cmd = Command::new(&task.command);
+    cmd.args(&args).envs(&task.env);
+    cmd
+}
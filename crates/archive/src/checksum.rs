@@ -0,0 +1,90 @@
+use crate::errors::ArchiveError;
+use moon_error::map_io_to_fs_error;
+use moon_logger::{color, trace, warn};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:archive:checksum";
+
+fn hash_file(path: &Path) -> Result<String, ArchiveError> {
+    let mut file = File::open(path).map_err(|e| map_io_to_fs_error(e, path.to_path_buf()))?;
+    let mut sha = Sha256::new();
+
+    io::copy(&mut file, &mut sha)?;
+
+    Ok(format!("{:x}", sha.finalize()))
+}
+
+/// Return the path to the checksum sidecar for the provided archive,
+/// which is the archive path with a `.sha256` extension appended.
+pub fn get_checksum_path<T: AsRef<Path>>(archive_file: T) -> PathBuf {
+    let archive_file = archive_file.as_ref();
+    let mut name = archive_file.file_name().unwrap_or_default().to_os_string();
+
+    name.push(".sha256");
+
+    archive_file.with_file_name(name)
+}
+
+/// Generate a SHA256 checksum of the archive and write it to a sidecar file.
+/// This should be called *after* the archive has been completely written.
+pub fn create_checksum<T: AsRef<Path>>(archive_file: T) -> Result<String, ArchiveError> {
+    let archive_file = archive_file.as_ref();
+    let checksum_file = get_checksum_path(archive_file);
+    let checksum = hash_file(archive_file)?;
+
+    trace!(
+        target: LOG_TARGET,
+        "Writing checksum {} for archive {}",
+        color::symbol(&checksum),
+        color::path(archive_file)
+    );
+
+    fs::write(&checksum_file, &checksum)
+        .map_err(|e| map_io_to_fs_error(e, checksum_file.to_path_buf()))?;
+
+    Ok(checksum)
+}
+
+/// Verify the archive against its checksum sidecar. If the sidecar is missing,
+/// the archive was created before checksums were introduced, and can not be
+/// verified, so is assumed to be valid. Only a checksum mismatch is invalid.
+/// Callers must move the sidecar into place *before* the archive for this to hold.
+pub fn verify_checksum<T: AsRef<Path>>(archive_file: T) -> Result<bool, ArchiveError> {
+    let archive_file = archive_file.as_ref();
+    let checksum_file = get_checksum_path(archive_file);
+
+    if !archive_file.exists() {
+        return Ok(false);
+    }
+
+    if !checksum_file.exists() {
+        trace!(
+            target: LOG_TARGET,
+            "Archive {} is missing a checksum, unable to verify",
+            color::path(archive_file)
+        );
+
+        return Ok(true);
+    }
+
+    let expected = fs::read_to_string(&checksum_file)
+        .map_err(|e| map_io_to_fs_error(e, checksum_file.to_path_buf()))?;
+    let actual = hash_file(archive_file)?;
+
+    if expected.trim() != actual {
+        warn!(
+            target: LOG_TARGET,
+            "Archive {} does not match its checksum (expected {}, received {})",
+            color::path(archive_file),
+            color::symbol(expected.trim()),
+            color::symbol(&actual)
+        );
+
+        return Ok(false);
+    }
+
+    Ok(true)
+}
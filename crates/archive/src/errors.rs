@@ -1,9 +1,18 @@
 use moon_error::MoonError;
+use std::path::PathBuf;
 use thiserror::Error;
 use zip::result::ZipError;
 
 #[derive(Error, Debug)]
 pub enum ArchiveError {
+    #[error(
+        "Archive entry <path>{0}</path> would be unpacked outside of the destination directory."
+    )]
+    EntryOutsideDestination(PathBuf),
+
+    #[error("Archive symlink <path>{0}</path> points to <path>{1}</path>, which is outside of the destination directory.")]
+    LinkOutsideDestination(PathBuf, PathBuf),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
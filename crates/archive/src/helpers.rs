@@ -1,7 +1,8 @@
+use crate::errors::ArchiveError;
 use moon_error::{map_io_to_fs_error, MoonError};
 use moon_utils::path;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 pub fn ensure_dir(dir: &Path) -> Result<(), MoonError> {
     if !dir.exists() {
@@ -22,3 +23,116 @@ pub fn prepend_name(name: &str, prefix: &str) -> String {
 
     path::normalize(parts).to_string_lossy().to_string()
 }
+
+fn canonicalize(path: &Path) -> Result<PathBuf, MoonError> {
+    path.canonicalize()
+        .map_err(|e| map_io_to_fs_error(e, path.to_path_buf()))
+}
+
+/// Return the target of a symlink when it's relative and resolves to a location
+/// within the root. Otherwise return `None`, and the link should be followed.
+pub fn read_contained_link(root: &Path, link: &Path) -> Option<PathBuf> {
+    let target = fs::read_link(link).ok()?;
+
+    if target.is_absolute() {
+        return None;
+    }
+
+    let resolved = path::normalize(link.parent()?.join(&target));
+
+    if resolved.starts_with(path::normalize(root)) {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+/// Ensure an entry path is relative and does not traverse upwards,
+/// otherwise it could be written outside of the destination directory.
+pub fn validate_entry_path(entry_path: &Path) -> Result<(), ArchiveError> {
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => {}
+            _ => {
+                return Err(ArchiveError::EntryOutsideDestination(
+                    entry_path.to_path_buf(),
+                ));
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Create the parent directory of an output path, and ensure that it physically
+/// resides within the destination directory, as previously unpacked symlinks
+/// may be part of the path. Returns the canonical parent directory.
+pub fn prepare_output_path(output_dir: &Path, output_path: &Path) -> Result<PathBuf, ArchiveError> {
+    let parent_dir = output_path.parent().unwrap_or(output_dir);
+
+    ensure_dir(parent_dir)?;
+
+    let parent_dir = canonicalize(parent_dir)?;
+
+    if !parent_dir.starts_with(canonicalize(output_dir)?) {
+        return Err(ArchiveError::EntryOutsideDestination(
+            output_path.to_path_buf(),
+        ));
+    }
+
+    Ok(parent_dir)
+}
+
+/// Ensure a symlink target is relative and resolves to a location
+/// within the destination directory.
+pub fn validate_link_target(
+    output_dir: &Path,
+    output_path: &Path,
+    target: &Path,
+) -> Result<(), ArchiveError> {
+    let parent_dir = prepare_output_path(output_dir, output_path)?;
+    let mut seen_normal = false;
+
+    // Only leading ".." are allowed, as traversing upwards from a nested
+    // symlink (`a/link/..`) can't be resolved without touching the file system
+    for component in target.components() {
+        let valid = match component {
+            Component::CurDir => true,
+            Component::ParentDir => !seen_normal,
+            Component::Normal(_) => {
+                seen_normal = true;
+                true
+            }
+            _ => false,
+        };
+
+        if !valid {
+            return Err(ArchiveError::LinkOutsideDestination(
+                output_path.to_path_buf(),
+                target.to_path_buf(),
+            ));
+        }
+    }
+
+    if !path::normalize(parent_dir.join(target)).starts_with(canonicalize(output_dir)?) {
+        return Err(ArchiveError::LinkOutsideDestination(
+            output_path.to_path_buf(),
+            target.to_path_buf(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Remove a previously unpacked symlink at the output path, so that
+/// writing to the path does not write through to the link's target.
+pub fn remove_existing_link(output_path: &Path) -> Result<(), MoonError> {
+    if let Ok(meta) = fs::symlink_metadata(output_path) {
+        if meta.file_type().is_symlink() {
+            fs::remove_file(output_path)
+                .map_err(|e| map_io_to_fs_error(e, output_path.to_path_buf()))?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
+mod checksum;
 mod errors;
 mod helpers;
 mod tar;
 mod zip;
 
+pub use crate::checksum::*;
 pub use crate::tar::*;
 pub use crate::zip::*;
 pub use errors::ArchiveError;
@@ -1,22 +1,221 @@
 use crate::errors::ArchiveError;
-use crate::helpers::{ensure_dir, prepend_name};
+use crate::helpers::{
+    ensure_dir, prepare_output_path, prepend_name, read_contained_link, validate_entry_path,
+    validate_link_target,
+};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use moon_error::map_io_to_fs_error;
-use moon_logger::{color, debug, map_list, trace};
-use std::fs::File;
+use moon_logger::{color, debug, map_list, trace, warn};
+use std::fs::{self, File};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use tar::{Archive, Builder};
+use tar::{Archive, Builder, EntryType, Header, HeaderMode};
+use zstd::stream::read::Decoder as ZstdDecoder;
+use zstd::stream::write::Encoder as ZstdEncoder;
 
 const LOG_TARGET: &str = "moon:archive:tar";
 
+fn pack_path<W: Write>(
+    archive: &mut Builder<W>,
+    input_root: &Path,
+    input_src: &Path,
+    name: &Path,
+    mode: HeaderMode,
+) -> Result<(), ArchiveError> {
+    let handle_error = |e| map_io_to_fs_error(e, input_src.to_path_buf());
+    let meta = fs::symlink_metadata(input_src).map_err(handle_error)?;
+
+    // Symlinks that stay within the root are preserved as-is,
+    // while all others are followed so the archive is self-contained
+    if meta.file_type().is_symlink() {
+        if let Some(target) = read_contained_link(input_root, input_src) {
+            trace!(
+                target: LOG_TARGET,
+                "Packing symlink {} (to {})",
+                color::path(input_src),
+                color::path(&target)
+            );
+
+            let mut header = Header::new_gnu();
+            header.set_metadata_in_mode(&meta, mode);
+            header.set_size(0);
+
+            archive.append_link(&mut header, name, target)?;
+
+            return Ok(());
+        }
+    }
+
+    if input_src.is_file() {
+        trace!(
+            target: LOG_TARGET,
+            "Packing file {}",
+            color::path(input_src)
+        );
+
+        let mut fh = File::open(input_src).map_err(handle_error)?;
+
+        archive.append_file(name, &mut fh)?;
+    } else if input_src.is_dir() {
+        trace!(
+            target: LOG_TARGET,
+            "Packing directory {}",
+            color::path(input_src)
+        );
+
+        archive.append_dir(name, input_src)?;
+
+        let mut entries = fs::read_dir(input_src)
+            .map_err(handle_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(handle_error)?;
+
+        // Sort for deterministic archives
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            pack_path(
+                archive,
+                input_root,
+                &entry.path(),
+                &name.join(entry.file_name()),
+                mode,
+            )?;
+        }
+    } else {
+        warn!(
+            target: LOG_TARGET,
+            "Unable to pack {} as it's a broken symlink, skipping",
+            color::path(input_src)
+        );
+    }
+
+    Ok(())
+}
+
+fn pack<W: Write>(
+    archive: &mut Builder<W>,
+    input_root: &Path,
+    files: &[String],
+    base_prefix: Option<&str>,
+    preserve_mtime: bool,
+) -> Result<(), ArchiveError> {
+    let prefix = base_prefix.unwrap_or_default();
+
+    // Deterministic headers use a fixed modification time (and ownership)
+    let mode = if preserve_mtime {
+        HeaderMode::Complete
+    } else {
+        HeaderMode::Deterministic
+    };
+
+    archive.mode(mode);
+
+    for file in files {
+        pack_path(
+            archive,
+            input_root,
+            &input_root.join(file),
+            &PathBuf::from(prepend_name(file, prefix)),
+            mode,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn unpack<R: Read>(
+    mut archive: Archive<R>,
+    output_dir: &Path,
+    remove_prefix: Option<&str>,
+    preserve_mtime: bool,
+) -> Result<(), ArchiveError> {
+    ensure_dir(output_dir)?;
+
+    // Restore modification times so that tools relying on them
+    // do not consider hydrated outputs as stale
+    archive.set_preserve_mtime(preserve_mtime);
+
+    let strip_prefix = |path: PathBuf| -> PathBuf {
+        if let Some(prefix) = remove_prefix {
+            if path.starts_with(prefix) {
+                return path.strip_prefix(&prefix).unwrap().to_owned();
+            }
+        }
+
+        path
+    };
+
+    for entry_result in archive.entries()? {
+        let mut entry = entry_result?;
+        let path: PathBuf = entry.path()?.to_path_buf();
+
+        validate_entry_path(&path)?;
+
+        // Remove the prefix
+        let output_path = output_dir.join(strip_prefix(path));
+        let entry_type = entry.header().entry_type();
+
+        // Create parent dirs
+        prepare_output_path(output_dir, &output_path)?;
+
+        if entry_type == EntryType::Symlink {
+            let target = entry.link_name()?.unwrap_or_default().to_path_buf();
+
+            validate_link_target(output_dir, &output_path, &target)?;
+
+            trace!(
+                target: LOG_TARGET,
+                "Unpacking symlink {} (to {})",
+                color::path(&output_path),
+                color::path(&target)
+            );
+        } else if entry_type == EntryType::Link {
+            let source = entry.link_name()?.unwrap_or_default().to_path_buf();
+
+            validate_entry_path(&source)?;
+
+            let source_path = output_dir.join(strip_prefix(source));
+
+            trace!(
+                target: LOG_TARGET,
+                "Unpacking hard link {} (to {})",
+                color::path(&output_path),
+                color::path(&source_path)
+            );
+
+            if fs::symlink_metadata(&output_path).is_ok() {
+                fs::remove_file(&output_path)
+                    .map_err(|e| map_io_to_fs_error(e, output_path.to_path_buf()))?;
+            }
+
+            fs::hard_link(&source_path, &output_path)
+                .map_err(|e| map_io_to_fs_error(e, output_path.to_path_buf()))?;
+
+            continue;
+        } else {
+            trace!(
+                target: LOG_TARGET,
+                "Unpacking file {}",
+                color::path(&output_path)
+            );
+        }
+
+        entry.unpack(&output_path)?;
+    }
+
+    Ok(())
+}
+
 #[track_caller]
 pub fn tar<I: AsRef<Path>, O: AsRef<Path>>(
     input_root: I,
     files: &[String],
     output_file: O,
     base_prefix: Option<&str>,
+    preserve_mtime: bool,
 ) -> Result<(), ArchiveError> {
     let input_root = input_root.as_ref();
     let output_file = output_file.as_ref();
@@ -38,34 +237,49 @@ pub fn tar<I: AsRef<Path>, O: AsRef<Path>>(
 
     // Add the files to the archive
     let mut archive = Builder::new(tar_gz);
-    let prefix = base_prefix.unwrap_or_default();
 
-    for file in files {
-        let input_src = input_root.join(file);
+    pack(&mut archive, input_root, files, base_prefix, preserve_mtime)?;
 
-        if input_src.is_file() {
-            trace!(
-                target: LOG_TARGET,
-                "Packing file {}",
-                color::path(&input_src)
-            );
+    archive.into_inner()?.finish()?;
 
-            let mut fh = File::open(&input_src)
-                .map_err(|e| map_io_to_fs_error(e, input_src.to_path_buf()))?;
+    Ok(())
+}
 
-            archive.append_file(prepend_name(file, prefix), &mut fh)?;
-        } else {
-            trace!(
-                target: LOG_TARGET,
-                "Packing directory {}",
-                color::path(&input_src)
-            );
+#[track_caller]
+pub fn tar_zstd<I: AsRef<Path>, O: AsRef<Path>>(
+    input_root: I,
+    files: &[String],
+    output_file: O,
+    base_prefix: Option<&str>,
+    level: i32,
+    preserve_mtime: bool,
+) -> Result<(), ArchiveError> {
+    let input_root = input_root.as_ref();
+    let output_file = output_file.as_ref();
 
-            archive.append_dir_all(prepend_name(file, prefix), input_src)?;
-        }
-    }
+    debug!(
+        target: LOG_TARGET,
+        "Packing tar archive from {} with {} to {} (zstd level {})",
+        color::path(input_root),
+        map_list(files, |f| color::file(f)),
+        color::path(output_file),
+        level,
+    );
+
+    // Create .tar
+    let tar =
+        File::create(output_file).map_err(|e| map_io_to_fs_error(e, output_file.to_path_buf()))?;
+
+    // Compress to .tar.zst
+    let tar_zst = ZstdEncoder::new(tar, level)?;
+
+    // Add the files to the archive
+    let mut archive = Builder::new(tar_zst);
+
+    pack(&mut archive, input_root, files, base_prefix, preserve_mtime)?;
 
-    archive.finish()?;
+    // The zstd frame must be finished explicitly, otherwise the archive is truncated
+    archive.into_inner()?.finish()?;
 
     Ok(())
 }
@@ -75,6 +289,7 @@ pub fn untar<I: AsRef<Path>, O: AsRef<Path>>(
     input_file: I,
     output_dir: O,
     remove_prefix: Option<&str>,
+    preserve_mtime: bool,
 ) -> Result<(), ArchiveError> {
     let input_file = input_file.as_ref();
     let output_dir = output_dir.as_ref();
@@ -86,8 +301,6 @@ pub fn untar<I: AsRef<Path>, O: AsRef<Path>>(
         color::path(output_dir),
     );
 
-    ensure_dir(output_dir)?;
-
     // Open .tar.gz file
     let tar_gz =
         File::open(input_file).map_err(|e| map_io_to_fs_error(e, input_file.to_path_buf()))?;
@@ -96,34 +309,33 @@ pub fn untar<I: AsRef<Path>, O: AsRef<Path>>(
     let tar = GzDecoder::new(tar_gz);
 
     // Unpack the archive into the output dir
-    let mut archive = Archive::new(tar);
-
-    for entry_result in archive.entries()? {
-        let mut entry = entry_result?;
-        let mut path: PathBuf = entry.path()?.to_owned().to_path_buf();
-
-        // Remove the prefix
-        if let Some(prefix) = remove_prefix {
-            if path.starts_with(prefix) {
-                path = path.strip_prefix(&prefix).unwrap().to_owned();
-            }
-        }
+    unpack(Archive::new(tar), output_dir, remove_prefix, preserve_mtime)
+}
 
-        let output_path = output_dir.join(path);
+#[track_caller]
+pub fn untar_zstd<I: AsRef<Path>, O: AsRef<Path>>(
+    input_file: I,
+    output_dir: O,
+    remove_prefix: Option<&str>,
+    preserve_mtime: bool,
+) -> Result<(), ArchiveError> {
+    let input_file = input_file.as_ref();
+    let output_dir = output_dir.as_ref();
 
-        // Create parent dirs
-        if let Some(parent_dir) = output_path.parent() {
-            ensure_dir(parent_dir)?;
-        }
+    debug!(
+        target: LOG_TARGET,
+        "Unpacking zstd tar archive {} to {}",
+        color::path(input_file),
+        color::path(output_dir),
+    );
 
-        entry.unpack(&output_path)?;
+    // Open .tar.zst file
+    let tar_zst =
+        File::open(input_file).map_err(|e| map_io_to_fs_error(e, input_file.to_path_buf()))?;
 
-        trace!(
-            target: LOG_TARGET,
-            "Unpacking file {}",
-            color::path(&output_path)
-        );
-    }
+    // Decompress to .tar
+    let tar = ZstdDecoder::new(tar_zst)?;
 
-    Ok(())
+    // Unpack the archive into the output dir
+    unpack(Archive::new(tar), output_dir, remove_prefix, preserve_mtime)
 }
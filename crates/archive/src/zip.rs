@@ -1,29 +1,87 @@
 use crate::errors::ArchiveError;
-use crate::helpers::{ensure_dir, prepend_name};
+use crate::helpers::{
+    ensure_dir, prepare_output_path, prepend_name, read_contained_link, remove_existing_link,
+    validate_entry_path,
+};
+use filetime::{set_file_mtime, FileTime};
 use moon_error::map_io_to_fs_error;
-use moon_logger::{color, debug, map_list, trace};
+use moon_logger::{color, debug, map_list, trace, warn};
 use moon_utils::path::to_string;
+use moon_utils::time::chrono::{self, Datelike, NaiveDate, Timelike, Utc};
 use std::fs::{self, File};
 use std::io;
 use std::io::prelude::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use zip::write::FileOptions;
-use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
 
 const LOG_TARGET: &str = "moon:archive:zip";
 
+// Zip timestamps have no timezone, so we always treat them as UTC
+fn to_zip_time(time: SystemTime) -> Option<DateTime> {
+    let time: chrono::DateTime<Utc> = time.into();
+
+    DateTime::from_date_and_time(
+        time.year().try_into().ok()?,
+        time.month() as u8,
+        time.day() as u8,
+        time.hour() as u8,
+        time.minute() as u8,
+        time.second() as u8,
+    )
+    .ok()
+}
+
+fn from_zip_time(time: DateTime) -> Option<FileTime> {
+    let time = NaiveDate::from_ymd_opt(time.year() as i32, time.month() as u32, time.day() as u32)?
+        .and_hms_opt(
+            time.hour() as u32,
+            time.minute() as u32,
+            time.second() as u32,
+        )?;
+
+    Some(FileTime::from_unix_time(time.timestamp(), 0))
+}
+
 fn zip_contents<P: AsRef<str>>(
     archive: &mut ZipWriter<File>,
     path: &Path,
     root: &Path,
     prefix: P,
+    preserve_mtime: bool,
 ) -> Result<(), ArchiveError> {
     let prefix = prefix.as_ref();
-    let name = to_string(path.strip_prefix(root).unwrap())?;
+    let name = prepend_name(&to_string(path.strip_prefix(root).unwrap())?, prefix);
+    let meta = fs::symlink_metadata(path).map_err(|e| map_io_to_fs_error(e, path.to_path_buf()))?;
 
     #[allow(unused_mut)] // windows
     let mut options = FileOptions::default().compression_method(CompressionMethod::Stored);
 
+    // When not preserved, the zip default (1980-01-01) is used instead
+    if preserve_mtime {
+        if let Some(time) = meta.modified().ok().and_then(to_zip_time) {
+            options = options.last_modified_time(time);
+        }
+    }
+
+    // Symlinks that stay within the root are preserved as-is,
+    // while all others are followed so the archive is self-contained
+    if meta.file_type().is_symlink() {
+        if let Some(target) = read_contained_link(root, path) {
+            trace!(
+                target: LOG_TARGET,
+                "Zipping symlink {} (to {})",
+                color::path(path),
+                color::path(&target)
+            );
+
+            archive.add_symlink(name, to_string(&target)?, options)?;
+
+            return Ok(());
+        }
+    }
+
     if path.is_file() {
         #[cfg(unix)]
         {
@@ -34,7 +92,7 @@ fn zip_contents<P: AsRef<str>>(
 
         trace!(target: LOG_TARGET, "Zipping file {}", color::path(&path));
 
-        archive.start_file(prepend_name(&name, prefix), options)?;
+        archive.start_file(name, options)?;
         archive.write_all(&fs::read(path)?)?;
 
         return Ok(());
@@ -43,15 +101,26 @@ fn zip_contents<P: AsRef<str>>(
     if path.is_dir() {
         archive.add_directory(name, options)?;
 
-        for entry in fs::read_dir(path)? {
-            let path = entry?.path();
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
 
-            zip_contents(archive, &path, root, prefix)?;
+        // Sort for deterministic archives
+        entries.sort();
+
+        for path in entries {
+            zip_contents(archive, &path, root, prefix, preserve_mtime)?;
         }
 
         return Ok(());
     }
 
+    warn!(
+        target: LOG_TARGET,
+        "Unable to zip {} as it's a broken symlink, skipping",
+        color::path(path)
+    );
+
     Ok(())
 }
 
@@ -61,6 +130,7 @@ pub fn zip<I: AsRef<Path>, O: AsRef<Path>>(
     files: &[String],
     output_file: O,
     base_prefix: Option<&str>,
+    preserve_mtime: bool,
 ) -> Result<(), ArchiveError> {
     let input_root = input_root.as_ref();
     let output_file = output_file.as_ref();
@@ -84,7 +154,7 @@ pub fn zip<I: AsRef<Path>, O: AsRef<Path>>(
     for file in files {
         let input_src = input_root.join(file);
 
-        zip_contents(&mut archive, &input_src, input_root, prefix)?;
+        zip_contents(&mut archive, &input_src, input_root, prefix, preserve_mtime)?;
     }
 
     archive.finish()?;
@@ -97,6 +167,7 @@ pub fn unzip<I: AsRef<Path>, O: AsRef<Path>>(
     input_file: I,
     output_dir: O,
     remove_prefix: Option<&str>,
+    preserve_mtime: bool,
 ) -> Result<(), ArchiveError> {
     let input_file = input_file.as_ref();
     let output_dir = output_dir.as_ref();
@@ -122,9 +193,15 @@ pub fn unzip<I: AsRef<Path>, O: AsRef<Path>>(
 
         let mut path = match file.enclosed_name() {
             Some(path) => path.to_owned(),
-            None => continue,
+            None => {
+                return Err(ArchiveError::EntryOutsideDestination(PathBuf::from(
+                    file.name(),
+                )));
+            }
         };
 
+        validate_entry_path(&path)?;
+
         // Remove the prefix
         if let Some(prefix) = remove_prefix {
             if path.starts_with(prefix) {
@@ -136,17 +213,54 @@ pub fn unzip<I: AsRef<Path>, O: AsRef<Path>>(
         let handle_error = |e: io::Error| map_io_to_fs_error(e, output_path.to_path_buf());
 
         // Create parent dirs
-        if let Some(parent_dir) = &output_path.parent() {
-            ensure_dir(parent_dir)?;
-        }
+        prepare_output_path(output_dir, &output_path)?;
 
         // If a folder, create the dir
         if file.is_dir() {
             ensure_dir(&output_path)?;
+
+            continue;
+        }
+
+        // If a symlink, validate and recreate the link on a nix machine.
+        // The link's contents are the target path.
+        #[cfg(unix)]
+        {
+            use crate::helpers::validate_link_target;
+
+            if file
+                .unix_mode()
+                .map(|mode| mode & 0o170000 == 0o120000)
+                .unwrap_or(false)
+            {
+                let mut target = String::new();
+                file.read_to_string(&mut target).map_err(handle_error)?;
+
+                let target = PathBuf::from(target);
+
+                validate_link_target(output_dir, &output_path, &target)?;
+
+                trace!(
+                    target: LOG_TARGET,
+                    "Unzipping symlink {} (to {})",
+                    color::path(&output_path),
+                    color::path(&target)
+                );
+
+                if fs::symlink_metadata(&output_path).is_ok() {
+                    fs::remove_file(&output_path).map_err(handle_error)?;
+                }
+
+                std::os::unix::fs::symlink(&target, &output_path).map_err(handle_error)?;
+
+                continue;
+            }
         }
 
         // If a file, copy it to the output dir
         if file.is_file() {
+            remove_existing_link(&output_path)?;
+
             let mut out = File::create(&output_path).map_err(handle_error)?;
 
             io::copy(&mut file, &mut out).map_err(handle_error)?;
@@ -167,6 +281,13 @@ pub fn unzip<I: AsRef<Path>, O: AsRef<Path>>(
                         .map_err(handle_error)?;
                 }
             }
+
+            // Restore the modification time
+            if preserve_mtime {
+                if let Some(time) = from_zip_time(file.last_modified()) {
+                    set_file_mtime(&output_path, time).map_err(handle_error)?;
+                }
+            }
         }
     }
 
@@ -0,0 +1,79 @@
+use moon_archive::{create_checksum, get_checksum_path, tar, verify_checksum};
+use moon_utils::string_vec;
+use moon_utils::test::create_sandbox;
+use std::fs;
+
+#[test]
+fn appends_extension_for_sidecar() {
+    let fixture = create_sandbox("archives");
+
+    assert_eq!(
+        get_checksum_path(fixture.path().join("out.tar.gz")),
+        fixture.path().join("out.tar.gz.sha256")
+    );
+}
+
+#[test]
+fn creates_sidecar() {
+    let fixture = create_sandbox("archives");
+    let archive = fixture.path().join("out.tar.gz");
+
+    tar(
+        fixture.path(),
+        &string_vec!["file.txt"],
+        &archive,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let checksum = create_checksum(&archive).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(fixture.path().join("out.tar.gz.sha256")).unwrap(),
+        checksum
+    );
+}
+
+#[test]
+fn verifies_valid_archive() {
+    let fixture = create_sandbox("archives");
+    let archive = fixture.path().join("out.tar.gz");
+
+    tar(fixture.path(), &string_vec!["folder"], &archive, None, true).unwrap();
+    create_checksum(&archive).unwrap();
+
+    assert!(verify_checksum(&archive).unwrap());
+}
+
+#[test]
+fn passes_verification_without_sidecar() {
+    let fixture = create_sandbox("archives");
+    let archive = fixture.path().join("out.tar.gz");
+
+    tar(fixture.path(), &string_vec!["folder"], &archive, None, true).unwrap();
+
+    assert!(verify_checksum(&archive).unwrap());
+}
+
+#[test]
+fn fails_verification_without_archive() {
+    let fixture = create_sandbox("archives");
+
+    assert!(!verify_checksum(fixture.path().join("missing.tar.gz")).unwrap());
+}
+
+#[test]
+fn fails_verification_for_truncated_archive() {
+    let fixture = create_sandbox("archives");
+    let archive = fixture.path().join("out.tar.gz");
+
+    tar(fixture.path(), &string_vec!["folder"], &archive, None, true).unwrap();
+    create_checksum(&archive).unwrap();
+
+    let bytes = fs::read(&archive).unwrap();
+
+    fs::write(&archive, &bytes[0..bytes.len() / 2]).unwrap();
+
+    assert!(!verify_checksum(&archive).unwrap());
+}
@@ -1,4 +1,4 @@
-use moon_archive::{tar, untar};
+use moon_archive::{tar, tar_zstd, untar, untar_zstd};
 use moon_utils::string_vec;
 use moon_utils::test::create_sandbox;
 use std::fs;
@@ -16,7 +16,7 @@ fn tars_file() {
     let input = fixture.path();
     let archive = fixture.path().join("out.tar.gz");
 
-    tar(&input, &string_vec!["file.txt"], &archive, None).unwrap();
+    tar(&input, &string_vec!["file.txt"], &archive, None, true).unwrap();
 
     assert!(archive.exists());
     assert_ne!(archive.metadata().unwrap().len(), 0);
@@ -24,7 +24,7 @@ fn tars_file() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("file.txt").exists());
@@ -49,6 +49,7 @@ fn tars_file_with_prefix() {
         &string_vec!["file.txt"],
         &archive,
         Some("some/prefix"),
+        true,
     )
     .unwrap();
 
@@ -58,7 +59,7 @@ fn tars_file_with_prefix() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("some/prefix/file.txt").exists());
@@ -83,6 +84,7 @@ fn tars_file_with_prefix_thats_removed() {
         &string_vec!["file.txt"],
         &archive,
         Some("some/prefix"),
+        true,
     )
     .unwrap();
 
@@ -92,7 +94,7 @@ fn tars_file_with_prefix_thats_removed() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, Some("some/prefix")).unwrap();
+    untar(&archive, &output, Some("some/prefix"), true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("file.txt").exists());
@@ -117,6 +119,7 @@ fn tars_nested_file_and_preserves_path() {
         &string_vec!["folder/nested/other.js"],
         &archive,
         None,
+        true,
     )
     .unwrap();
 
@@ -126,7 +129,7 @@ fn tars_nested_file_and_preserves_path() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("folder/nested/other.js").exists());
@@ -151,6 +154,7 @@ fn tars_file_and_dir_explicitly() {
         &string_vec!["folder/nested", "file.txt"],
         &archive,
         None,
+        true,
     )
     .unwrap();
 
@@ -160,7 +164,7 @@ fn tars_file_and_dir_explicitly() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("file.txt").exists());
@@ -186,7 +190,7 @@ fn tars_dir() {
     let input = fixture.path();
     let archive = fixture.path().join("out.tar.gz");
 
-    tar(&input, &string_vec!["folder"], &archive, None).unwrap();
+    tar(&input, &string_vec!["folder"], &archive, None, true).unwrap();
 
     assert!(archive.exists());
     assert_ne!(archive.metadata().unwrap().len(), 0);
@@ -194,7 +198,7 @@ fn tars_dir() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("folder/file.js").exists());
@@ -224,6 +228,7 @@ fn tars_dir_with_prefix() {
         &string_vec!["folder"],
         &archive,
         Some("some/prefix"),
+        true,
     )
     .unwrap();
 
@@ -233,7 +238,7 @@ fn tars_dir_with_prefix() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, None).unwrap();
+    untar(&archive, &output, None, true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("some/prefix/folder/file.js").exists());
@@ -263,6 +268,7 @@ fn tars_dir_with_prefix_thats_removed() {
         &string_vec!["folder"],
         &archive,
         Some("some/prefix"),
+        true,
     )
     .unwrap();
 
@@ -272,7 +278,7 @@ fn tars_dir_with_prefix_thats_removed() {
     // Unpack
     let output = fixture.path().join("out");
 
-    untar(&archive, &output, Some("some/prefix")).unwrap();
+    untar(&archive, &output, Some("some/prefix"), true).unwrap();
 
     assert!(output.exists());
     assert!(output.join("folder/file.js").exists());
@@ -288,3 +294,384 @@ fn tars_dir_with_prefix_thats_removed() {
         &output.join("folder/nested/other.js")
     ));
 }
+
+mod zstd {
+    use super::*;
+
+    #[test]
+    fn tars_file() {
+        let fixture = create_sandbox("archives");
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.zst");
+
+        tar_zstd(&input, &string_vec!["file.txt"], &archive, None, 3, true).unwrap();
+
+        assert!(archive.exists());
+        assert_ne!(archive.metadata().unwrap().len(), 0);
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar_zstd(&archive, &output, None, true).unwrap();
+
+        assert!(output.exists());
+        assert!(output.join("file.txt").exists());
+
+        // Compare
+        assert!(file_contents_match(
+            &input.join("file.txt"),
+            &output.join("file.txt")
+        ));
+    }
+
+    #[test]
+    fn tars_dir_with_high_level() {
+        let fixture = create_sandbox("archives");
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.zst");
+
+        tar_zstd(&input, &string_vec!["folder"], &archive, None, 19, true).unwrap();
+
+        assert!(archive.exists());
+        assert_ne!(archive.metadata().unwrap().len(), 0);
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar_zstd(&archive, &output, None, true).unwrap();
+
+        assert!(output.join("folder/file.js").exists());
+        assert!(output.join("folder/nested/other.js").exists());
+
+        // Compare
+        assert!(file_contents_match(
+            &input.join("folder/nested/other.js"),
+            &output.join("folder/nested/other.js")
+        ));
+    }
+
+    #[test]
+    fn errors_for_truncated_archive() {
+        let fixture = create_sandbox("archives");
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.zst");
+
+        tar_zstd(&input, &string_vec!["folder"], &archive, None, 3, true).unwrap();
+
+        // Truncate
+        let bytes = fs::read(&archive).unwrap();
+
+        fs::write(&archive, &bytes[0..bytes.len() / 2]).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        assert!(untar_zstd(&archive, &output, None, true).is_err());
+    }
+}
+
+#[cfg(unix)]
+mod symlinks {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn preserves_relative_links_within_root() {
+        let fixture = create_sandbox("archives");
+
+        symlink("nested/other.js", fixture.path().join("folder/link.js")).unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.gz");
+
+        tar(&input, &string_vec!["folder"], &archive, None, true).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar(&archive, &output, None, true).unwrap();
+
+        let link = output.join("folder/link.js");
+
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("nested/other.js"));
+        assert!(file_contents_match(
+            &input.join("folder/nested/other.js"),
+            &link
+        ));
+    }
+
+    #[test]
+    fn follows_links_outside_of_root() {
+        let fixture = create_sandbox("archives");
+        let external = create_sandbox("archives");
+
+        symlink(
+            external.path().join("file.txt"),
+            fixture.path().join("folder/link.txt"),
+        )
+        .unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.gz");
+
+        tar(&input, &string_vec!["folder"], &archive, None, true).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar(&archive, &output, None, true).unwrap();
+
+        let link = output.join("folder/link.txt");
+
+        assert!(link.symlink_metadata().unwrap().file_type().is_file());
+        assert!(file_contents_match(
+            &external.path().join("file.txt"),
+            &link
+        ));
+    }
+
+    #[test]
+    fn preserves_links_with_zstd() {
+        let fixture = create_sandbox("archives");
+
+        symlink("../file.txt", fixture.path().join("folder/link.txt")).unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.zst");
+
+        tar_zstd(
+            &input,
+            &string_vec!["file.txt", "folder"],
+            &archive,
+            None,
+            3,
+            true,
+        )
+        .unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar_zstd(&archive, &output, None, true).unwrap();
+
+        let link = output.join("folder/link.txt");
+
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("../file.txt"));
+        assert!(file_contents_match(&input.join("file.txt"), &link));
+    }
+}
+
+#[cfg(unix)]
+mod permissions {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn preserves_executable_bit() {
+        let fixture = create_sandbox("archives");
+
+        fs::set_permissions(
+            fixture.path().join("file.txt"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.gz");
+
+        tar(&input, &string_vec!["file.txt"], &archive, None, true).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar(&archive, &output, None, true).unwrap();
+
+        let mode = output
+            .join("file.txt")
+            .metadata()
+            .unwrap()
+            .permissions()
+            .mode();
+
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
+
+mod mtimes {
+    use super::*;
+    use filetime::{set_file_mtime, FileTime};
+
+    #[test]
+    fn preserves_modified_time() {
+        let fixture = create_sandbox("archives");
+        let mtime = FileTime::from_unix_time(1_600_000_000, 0);
+
+        set_file_mtime(fixture.path().join("file.txt"), mtime).unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.gz");
+
+        tar(&input, &string_vec!["file.txt"], &archive, None, true).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar(&archive, &output, None, true).unwrap();
+
+        let meta = output.join("file.txt").metadata().unwrap();
+
+        assert_eq!(
+            FileTime::from_last_modification_time(&meta).unix_seconds(),
+            mtime.unix_seconds()
+        );
+    }
+
+    #[test]
+    fn doesnt_preserve_modified_time_when_disabled() {
+        let fixture = create_sandbox("archives");
+        let mtime = FileTime::from_unix_time(1_600_000_000, 0);
+
+        set_file_mtime(fixture.path().join("file.txt"), mtime).unwrap();
+
+        // Pack
+        let input = fixture.path();
+        let archive = fixture.path().join("out.tar.gz");
+
+        tar(&input, &string_vec!["file.txt"], &archive, None, false).unwrap();
+
+        // Unpack
+        let output = fixture.path().join("out");
+
+        untar(&archive, &output, None, false).unwrap();
+
+        let meta = output.join("file.txt").metadata().unwrap();
+
+        assert!(FileTime::from_last_modification_time(&meta).unix_seconds() > mtime.unix_seconds());
+    }
+}
+
+mod escaping {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use moon_archive::ArchiveError;
+    use tar::{Builder, EntryType, Header};
+
+    fn create_archive(path: &Path, name: &str, link: Option<&str>) {
+        let mut builder = Builder::new(GzEncoder::new(
+            fs::File::create(path).unwrap(),
+            Compression::fast(),
+        ));
+        let mut header = Header::new_gnu();
+        let data = b"content";
+
+        // Write the name directly, as the builder refuses to write `..` paths
+        header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name.as_bytes());
+        header.set_mode(0o644);
+
+        if let Some(link) = link {
+            header.set_entry_type(EntryType::Symlink);
+            header.set_link_name(link).unwrap();
+            header.set_size(0);
+            header.set_cksum();
+            builder.append(&header, &[][..]).unwrap();
+        } else {
+            header.set_entry_type(EntryType::Regular);
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append(&header, &data[..]).unwrap();
+        }
+
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn refuses_parent_paths() {
+        let fixture = create_sandbox("archives");
+        let archive = fixture.path().join("out.tar.gz");
+
+        create_archive(&archive, "../evil.txt", None);
+
+        let output = fixture.path().join("out");
+        let result = untar(&archive, &output, None, true);
+
+        assert!(matches!(
+            result,
+            Err(ArchiveError::EntryOutsideDestination(_))
+        ));
+        assert!(!fixture.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn refuses_absolute_paths() {
+        let fixture = create_sandbox("archives");
+        let archive = fixture.path().join("out.tar.gz");
+
+        create_archive(&archive, "/tmp/evil.txt", None);
+
+        let output = fixture.path().join("out");
+
+        assert!(matches!(
+            untar(&archive, &output, None, true),
+            Err(ArchiveError::EntryOutsideDestination(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_links_outside_of_destination() {
+        let fixture = create_sandbox("archives");
+        let archive = fixture.path().join("out.tar.gz");
+
+        create_archive(&archive, "link.txt", Some("../file.txt"));
+
+        let output = fixture.path().join("out");
+
+        assert!(matches!(
+            untar(&archive, &output, None, true),
+            Err(ArchiveError::LinkOutsideDestination(_, _))
+        ));
+        assert!(!output.join("link.txt").exists());
+    }
+
+    #[test]
+    fn refuses_absolute_links() {
+        let fixture = create_sandbox("archives");
+        let archive = fixture.path().join("out.tar.gz");
+
+        create_archive(&archive, "link.txt", Some("/etc/passwd"));
+
+        let output = fixture.path().join("out");
+
+        assert!(matches!(
+            untar(&archive, &output, None, true),
+            Err(ArchiveError::LinkOutsideDestination(_, _))
+        ));
+    }
+
+    #[test]
+    fn refuses_traversing_through_links() {
+        let fixture = create_sandbox("archives");
+        let archive = fixture.path().join("out.tar.gz");
+
+        create_archive(&archive, "link.txt", Some("folder/../../file.txt"));
+
+        let output = fixture.path().join("out");
+
+        assert!(matches!(
+            untar(&archive, &output, None, true),
+            Err(ArchiveError::LinkOutsideDestination(_, _))
+        ));
+    }
+}
@@ -1,16 +1,31 @@
 use crate::helpers::{is_readable, is_writable, LOG_TARGET};
 use crate::items::{CacheItem, ProjectsState, RunTargetState, WorkspaceState};
 use crate::runfiles::CacheRunfile;
-use moon_archive::{tar, untar};
+use crate::stats::{collect_dir_stats, collect_target_stats, CacheStats};
+use moon_archive::{
+    create_checksum, get_checksum_path, tar, tar_zstd, untar, untar_zstd, verify_checksum,
+};
+use moon_config::ArchiveFormat;
 use moon_constants::CONFIG_DIRNAME;
-use moon_error::MoonError;
-use moon_logger::{color, debug, trace};
+use moon_error::{map_io_to_fs_error, MoonError};
+use moon_logger::{color, debug, trace, warn};
 use moon_utils::fs;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::path::{Path, PathBuf};
 
 pub struct CacheEngine {
+    /// Compression level to use when archiving with zstd.
+    pub archive_compression_level: i32,
+
+    /// Format to archive task outputs with.
+    pub archive_format: ArchiveFormat,
+
+    /// Preserve and restore file modification times within archives.
+    pub archive_preserve_mtimes: bool,
+
     /// The `.moon/cache` directory relative to workspace root.
     /// Contains cached items pertaining to runs and processes.
     pub dir: PathBuf,
@@ -43,6 +58,9 @@ impl CacheEngine {
         fs::create_dir_all(&outputs_dir).await?;
 
         Ok(CacheEngine {
+            archive_compression_level: 3,
+            archive_format: ArchiveFormat::default(),
+            archive_preserve_mtimes: true,
             dir,
             hashes_dir,
             runs_dir,
@@ -65,6 +83,26 @@ impl CacheEngine {
         .await
     }
 
+    /// Load the run state for a variant of a target, as created by task deps
+    /// with custom args or env vars, so that it's tracked separately from the base.
+    pub async fn cache_run_target_variant_state(
+        &self,
+        target_id: &str,
+        variant_hash: &str,
+    ) -> Result<CacheItem<RunTargetState>, MoonError> {
+        CacheItem::load(
+            self.get_target_dir(target_id)
+                .join(variant_hash)
+                .join("lastRunState.json"),
+            RunTargetState {
+                target: String::from(target_id),
+                ..RunTargetState::default()
+            },
+            0,
+        )
+        .await
+    }
+
     pub async fn cache_projects_state(&self) -> Result<CacheItem<ProjectsState>, MoonError> {
         CacheItem::load(
             self.dir.join("projectsState.json"),
@@ -83,6 +121,19 @@ impl CacheEngine {
         .await
     }
 
+    /// Walk the cache directories and gather statistics about archives,
+    /// manifests, and the last run state of every target.
+    pub async fn collect_stats(&self) -> Result<CacheStats, MoonError> {
+        debug!(target: LOG_TARGET, "Collecting cache statistics");
+
+        Ok(CacheStats {
+            archives: collect_dir_stats(&self.outputs_dir).await?,
+            manifests: collect_dir_stats(&self.hashes_dir).await?,
+            runs: collect_dir_stats(&self.runs_dir).await?,
+            targets: collect_target_stats(&self.runs_dir).await?,
+        })
+    }
+
     pub async fn create_hash_archive(
         &self,
         hash: &str,
@@ -100,13 +151,31 @@ impl CacheEngine {
             }
 
             // New implementation uses tar archives! Very cool.
-            tar(
-                project_root,
-                outputs,
-                self.get_hash_archive_path(hash),
-                None,
-            )
-            .map_err(|e| MoonError::Generic(e.to_string()))?;
+            let archive_path = self.get_hash_archive_path(hash);
+
+            // Archive to a temporary path first, so that an archive never exists
+            // at its final path without a checksum (killed processes, etc).
+            // The path is unique, as other processes may archive the same hash.
+            let mut temp_name = archive_path.file_name().unwrap_or_default().to_os_string();
+            temp_name.push(format!(".{}.tmp", get_temp_suffix()));
+
+            let temp_path = archive_path.with_file_name(temp_name);
+
+            if let Err(error) = self.write_hash_archive(project_root, outputs, &temp_path) {
+                // Never leave partial archives behind
+                let _ = std::fs::remove_file(&temp_path);
+                let _ = std::fs::remove_file(get_checksum_path(&temp_path));
+
+                return Err(error);
+            }
+
+            // Move the checksum into place before the archive, so that an archive
+            // without a sidecar can only have been created before checksums existed
+            rename_file(
+                get_checksum_path(&temp_path),
+                get_checksum_path(&archive_path),
+            )?;
+            rename_file(temp_path, archive_path)?;
         }
 
         Ok(())
@@ -144,14 +213,27 @@ impl CacheEngine {
             trace!(target: LOG_TARGET, "Deleting hash {}", color::symbol(hash));
 
             fs::remove_file(self.get_hash_manifest_path(hash)).await?;
-            fs::remove_file(self.get_hash_archive_path(hash)).await?;
+
+            for ext in ["tar.gz", "tar.zst"] {
+                let archive_path = self.outputs_dir.join(format!("{}.{}", hash, ext));
+
+                fs::remove_file(get_checksum_path(&archive_path)).await?;
+                fs::remove_file(archive_path).await?;
+            }
         }
 
         Ok(())
     }
 
     pub fn get_hash_archive_path(&self, hash: &str) -> PathBuf {
-        self.outputs_dir.join(format!("{}.tar.gz", hash))
+        self.outputs_dir.join(format!(
+            "{}.{}",
+            hash,
+            match self.archive_format {
+                ArchiveFormat::Gzip => "tar.gz",
+                ArchiveFormat::Zstd => "tar.zst",
+            }
+        ))
     }
 
     pub fn get_hash_manifest_path(&self, hash: &str) -> PathBuf {
@@ -168,26 +250,155 @@ impl CacheEngine {
         self.runs_dir.join(path)
     }
 
+    /// Return the hash of the run that last created or hydrated the target's outputs.
+    /// Variants of a target write to the same outputs, so a matching run state
+    /// alone does not guarantee that the outputs on disk belong to that run.
+    pub fn get_target_outputs_hash(&self, target_id: &str) -> String {
+        let path = self.get_target_dir(target_id).join("lastOutputsHash");
+
+        if !is_readable() || !path.exists() {
+            return String::new();
+        }
+
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    pub async fn save_target_outputs_hash(
+        &self,
+        target_id: &str,
+        hash: &str,
+    ) -> Result<(), MoonError> {
+        if is_writable() {
+            let dir = self.get_target_dir(target_id);
+
+            fs::create_dir_all(&dir).await?;
+            fs::write(dir.join("lastOutputsHash"), hash).await?;
+        }
+
+        Ok(())
+    }
+
     /// Check to see if a build with the provided hash has been cached.
     /// We only check for the archive, as the manifest is purely for local debugging!
+    /// Archives are verified when hydrating, as hashing them here would be wasteful.
     pub fn is_hash_cached(&self, hash: &str) -> bool {
         self.get_hash_archive_path(hash).exists()
     }
 
+    /// Hydrate the project with the outputs from the archive of the provided hash.
+    /// Archives are verified against their checksum before unpacking, and those
+    /// that are corrupt (or fail to unpack) are removed. Returns false when the
+    /// outputs were not hydrated, which should be treated as a cache miss.
     pub async fn hydrate_from_hash_archive(
         &self,
         hash: &str,
         project_root: &Path,
-    ) -> Result<(), MoonError> {
-        if is_readable() {
-            let archive_path = self.get_hash_archive_path(hash);
+    ) -> Result<bool, MoonError> {
+        let archive_path = self.get_hash_archive_path(hash);
 
-            if archive_path.exists() {
-                untar(archive_path, project_root, None)
-                    .map_err(|e| MoonError::Generic(e.to_string()))?;
-            }
+        if !is_readable() || !archive_path.exists() {
+            return Ok(false);
+        }
+
+        // Archives created before checksums were introduced do not have a sidecar
+        let has_checksum = get_checksum_path(&archive_path).exists();
+
+        if !verify_checksum(&archive_path).unwrap_or(false) {
+            self.remove_corrupt_archive(hash, &archive_path);
+
+            return Ok(false);
+        }
+
+        let result = match self.archive_format {
+            ArchiveFormat::Gzip => untar(
+                &archive_path,
+                project_root,
+                None,
+                self.archive_preserve_mtimes,
+            ),
+            ArchiveFormat::Zstd => untar_zstd(
+                &archive_path,
+                project_root,
+                None,
+                self.archive_preserve_mtimes,
+            ),
+        };
+
+        if let Err(error) = result {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to unpack archive for hash {}: {}",
+                color::symbol(hash),
+                error
+            );
+
+            self.remove_corrupt_archive(hash, &archive_path);
+
+            return Ok(false);
+        }
+
+        // The archive unpacked successfully, so write the missing checksum,
+        // allowing corruption to be detected in the future
+        if !has_checksum && is_writable() {
+            create_checksum(&archive_path).map_err(|e| MoonError::Generic(e.to_string()))?;
+        }
+
+        Ok(true)
+    }
+
+    fn remove_corrupt_archive(&self, hash: &str, archive_path: &Path) {
+        warn!(
+            target: LOG_TARGET,
+            "Archive for hash {} is corrupt, removing and treating as a cache miss",
+            color::symbol(hash)
+        );
+
+        if is_writable() {
+            let _ = std::fs::remove_file(get_checksum_path(archive_path));
+            let _ = std::fs::remove_file(archive_path);
+        }
+    }
+
+    fn write_hash_archive(
+        &self,
+        project_root: &Path,
+        outputs: &[String],
+        archive_path: &Path,
+    ) -> Result<(), MoonError> {
+        match self.archive_format {
+            ArchiveFormat::Gzip => tar(
+                project_root,
+                outputs,
+                archive_path,
+                None,
+                self.archive_preserve_mtimes,
+            ),
+            ArchiveFormat::Zstd => tar_zstd(
+                project_root,
+                outputs,
+                archive_path,
+                None,
+                self.archive_compression_level,
+                self.archive_preserve_mtimes,
+            ),
         }
+        .map_err(|e| MoonError::Generic(e.to_string()))?;
+
+        create_checksum(archive_path).map_err(|e| MoonError::Generic(e.to_string()))?;
 
         Ok(())
     }
 }
+
+// Unique per process and call, so that concurrent archives never share a temporary file
+fn get_temp_suffix() -> String {
+    format!(
+        "{}-{:x}",
+        std::process::id(),
+        RandomState::new().build_hasher().finish()
+    )
+}
+
+fn rename_file(from: PathBuf, to: PathBuf) -> Result<(), MoonError> {
+    std::fs::rename(&from, &to).map_err(|e| map_io_to_fs_error(e, from))
+}
@@ -4,7 +4,7 @@ use moon_logger::{color, trace};
 use moon_utils::fs;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::time::SystemTime;
 
@@ -86,6 +86,12 @@ impl<T: DeserializeOwned + Serialize> CacheItem<T> {
 #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RunTargetState {
+    #[serde(default)]
+    pub cache_hits: u64,
+
+    #[serde(default)]
+    pub cache_misses: u64,
+
     pub exit_code: i32,
 
     pub hash: String,
@@ -112,9 +118,16 @@ pub struct ProjectsState {
 #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceState {
+    #[serde(default)]
+    pub last_bun_install_time: u128,
+
     #[serde(default)]
     pub last_node_install_time: u128,
 
+    /// Keyed by the absolute path of the virtual environment's root.
+    #[serde(default)]
+    pub last_python_install_times: BTreeMap<String, u128>,
+
     #[serde(default)]
     pub last_version_check_time: u128,
 }
@@ -2,7 +2,9 @@ mod engine;
 mod helpers;
 mod items;
 mod runfiles;
+mod stats;
 
 pub use engine::CacheEngine;
 pub use helpers::*;
 pub use items::*;
+pub use stats::*;
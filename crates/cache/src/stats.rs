@@ -0,0 +1,186 @@
+use crate::helpers::{to_millis, LOG_TARGET};
+use crate::items::RunTargetState;
+use moon_error::MoonError;
+use moon_logger::{color, trace, warn};
+use moon_utils::fs;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntryStats {
+    pub modified_time: u128,
+
+    pub name: String,
+
+    pub size: u64,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheDirStats {
+    pub count: usize,
+
+    pub newest: Option<CacheEntryStats>,
+
+    pub oldest: Option<CacheEntryStats>,
+
+    pub size: u64,
+}
+
+impl CacheDirStats {
+    pub fn track(&mut self, entry: CacheEntryStats) {
+        self.count += 1;
+        self.size += entry.size;
+
+        if self
+            .newest
+            .as_ref()
+            .map(|newest| entry.modified_time > newest.modified_time)
+            .unwrap_or(true)
+        {
+            self.newest = Some(entry.clone());
+        }
+
+        if self
+            .oldest
+            .as_ref()
+            .map(|oldest| entry.modified_time < oldest.modified_time)
+            .unwrap_or(true)
+        {
+            self.oldest = Some(entry);
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheTargetStats {
+    pub cache_hits: u64,
+
+    pub cache_misses: u64,
+
+    pub exit_code: i32,
+
+    pub hash: String,
+
+    pub last_run_time: u128,
+
+    pub target: String,
+}
+
+impl From<RunTargetState> for CacheTargetStats {
+    fn from(state: RunTargetState) -> Self {
+        CacheTargetStats {
+            cache_hits: state.cache_hits,
+            cache_misses: state.cache_misses,
+            exit_code: state.exit_code,
+            hash: state.hash,
+            last_run_time: state.last_run_time,
+            target: state.target,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    /// Task output archives within `.moon/cache/out`.
+    pub archives: CacheDirStats,
+
+    /// Hash manifests within `.moon/cache/hashes`.
+    pub manifests: CacheDirStats,
+
+    /// Run states and runfiles within `.moon/cache/runs`.
+    pub runs: CacheDirStats,
+
+    /// The last known state of every target that has been ran.
+    pub targets: Vec<CacheTargetStats>,
+}
+
+pub(crate) async fn collect_dir_stats(dir: &Path) -> Result<CacheDirStats, MoonError> {
+    let mut stats = CacheDirStats::default();
+
+    if !dir.exists() {
+        return Ok(stats);
+    }
+
+    trace!(
+        target: LOG_TARGET,
+        "Collecting stats for {}",
+        color::path(dir)
+    );
+
+    for entry in fs::read_dir_all(dir).await? {
+        let path = entry.path();
+
+        // Checksums are metadata for archives, not entries themselves,
+        // and temporary files are archives that are still being written
+        if path
+            .extension()
+            .map(|ext| ext == "sha256" || ext == "tmp")
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let meta = fs::metadata(&path).await?;
+
+        stats.track(CacheEntryStats {
+            modified_time: meta.modified().map(to_millis).unwrap_or(0),
+            name: path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string(),
+            size: meta.len(),
+        });
+    }
+
+    Ok(stats)
+}
+
+pub(crate) async fn collect_target_stats(
+    runs_dir: &Path,
+) -> Result<Vec<CacheTargetStats>, MoonError> {
+    let mut targets = vec![];
+
+    if !runs_dir.exists() {
+        return Ok(targets);
+    }
+
+    for entry in fs::read_dir_all(runs_dir).await? {
+        let path = entry.path();
+
+        if path.file_name().unwrap_or_default() != "lastRunState.json" {
+            continue;
+        }
+
+        // Avoid failing the entire collection because of a single corrupt state
+        match fs::read_json::<_, RunTargetState>(&path).await {
+            Ok(state) => {
+                let target_dir = runs_dir.join(state.target.replace(':', "/"));
+
+                // Variants of a target (deps with custom args or env vars) are
+                // nested within the target's folder, so only report the base state
+                if path.parent() != Some(target_dir.as_path()) {
+                    continue;
+                }
+
+                targets.push(CacheTargetStats::from(state));
+            }
+            Err(error) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Unable to read run state {}, skipping: {}",
+                    color::path(&path),
+                    error
+                );
+            }
+        };
+    }
+
+    targets.sort_by(|a, b| a.target.cmp(&b.target));
+
+    Ok(targets)
+}
@@ -2,6 +2,7 @@ use assert_fs::prelude::*;
 use moon_cache::{to_millis, CacheEngine, ProjectsState, RunTargetState, WorkspaceState};
 use serde::Serialize;
 use serial_test::serial;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 
@@ -262,7 +263,7 @@ mod cache_run_target_state {
 
         assert_eq!(
             fs::read_to_string(item.path).unwrap(),
-            r#"{"exitCode":123,"hash":"","lastRunTime":0,"stderr":"","stdout":"","target":"foo:bar"}"#
+            r#"{"cacheHits":0,"cacheMisses":0,"exitCode":123,"hash":"","lastRunTime":0,"stderr":"","stdout":"","target":"foo:bar"}"#
         );
 
         dir.close().unwrap();
@@ -301,6 +302,79 @@ mod cache_run_target_state {
     }
 }
 
+mod cache_run_target_variant_state {
+    use super::*;
+
+    #[tokio::test]
+    #[serial]
+    async fn stores_separately_from_base_target() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let base = cache.cache_run_target_state("foo:bar").await.unwrap();
+        let variant = cache
+            .cache_run_target_variant_state("foo:bar", "abc123")
+            .await
+            .unwrap();
+
+        assert_ne!(base.path, variant.path);
+        assert_eq!(
+            variant.path,
+            dir.path()
+                .join(".moon/cache/runs/foo/bar/abc123/lastRunState.json")
+        );
+        assert_eq!(variant.item.target, "foo:bar");
+
+        dir.close().unwrap();
+    }
+}
+
+mod target_outputs_hash {
+    use super::*;
+
+    #[tokio::test]
+    #[serial]
+    async fn returns_empty_when_missing() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        assert_eq!(cache.get_target_outputs_hash("foo:bar"), "");
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn saves_and_returns_hash() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        run_with_env("", || cache.save_target_outputs_hash("foo:bar", "abc123"))
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get_target_outputs_hash("foo:bar"), "abc123");
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn doesnt_save_when_cache_is_read_only() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        run_with_env("read", || {
+            cache.save_target_outputs_hash("foo:bar", "abc123")
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(cache.get_target_outputs_hash("foo:bar"), "");
+
+        dir.close().unwrap();
+    }
+}
+
 mod cache_workspace_state {
     use super::*;
 
@@ -332,7 +406,9 @@ mod cache_workspace_state {
         assert_eq!(
             item.item,
             WorkspaceState {
+                last_bun_install_time: 0,
                 last_node_install_time: 123,
+                last_python_install_times: BTreeMap::new(),
                 last_version_check_time: 0,
             }
         );
@@ -357,7 +433,9 @@ mod cache_workspace_state {
         assert_eq!(
             item.item,
             WorkspaceState {
+                last_bun_install_time: 0,
                 last_node_install_time: 123,
+                last_python_install_times: BTreeMap::new(),
                 last_version_check_time: 0,
             }
         );
@@ -397,7 +475,7 @@ mod cache_workspace_state {
 
         assert_eq!(
             fs::read_to_string(item.path).unwrap(),
-            r#"{"lastNodeInstallTime":123,"lastVersionCheckTime":0}"#
+            r#"{"lastBunInstallTime":0,"lastNodeInstallTime":123,"lastPythonInstallTimes":{},"lastVersionCheckTime":0}"#
         );
 
         dir.close().unwrap();
@@ -593,3 +671,317 @@ mod create_hash_manifest {
         dir.close().unwrap();
     }
 }
+
+mod collect_stats {
+    use super::*;
+    use moon_cache::CacheTargetStats;
+
+    #[tokio::test]
+    #[serial]
+    async fn returns_empty_stats() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let stats = cache.collect_stats().await.unwrap();
+
+        assert_eq!(stats.archives.count, 0);
+        assert_eq!(stats.manifests.count, 0);
+        assert!(stats.archives.newest.is_none());
+        assert!(stats.targets.is_empty());
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn counts_archives_and_manifests() {
+        let dir = assert_fs::TempDir::new().unwrap();
+
+        dir.child(".moon/cache/hashes/abc.json")
+            .write_str("{}")
+            .unwrap();
+        dir.child(".moon/cache/hashes/def.json")
+            .write_str("{}")
+            .unwrap();
+        dir.child(".moon/cache/out/abc.tar.gz")
+            .write_str("12345")
+            .unwrap();
+        dir.child(".moon/cache/out/abc.tar.gz.sha256")
+            .write_str("checksum")
+            .unwrap();
+        dir.child(".moon/cache/out/def.tar.gz.123-abc.tmp")
+            .write_str("partial")
+            .unwrap();
+
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let stats = cache.collect_stats().await.unwrap();
+
+        assert_eq!(stats.archives.count, 1);
+        assert_eq!(stats.archives.size, 5);
+        assert_eq!(stats.archives.newest.unwrap().name, "abc.tar.gz");
+        assert_eq!(stats.archives.oldest.unwrap().name, "abc.tar.gz");
+        assert_eq!(stats.manifests.count, 2);
+        assert_eq!(stats.manifests.size, 4);
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn tracks_oldest_and_newest() {
+        use filetime::{set_file_mtime, FileTime};
+
+        let dir = assert_fs::TempDir::new().unwrap();
+
+        dir.child(".moon/cache/out/old.tar.gz")
+            .write_str("")
+            .unwrap();
+        dir.child(".moon/cache/out/new.tar.gz")
+            .write_str("")
+            .unwrap();
+
+        set_file_mtime(
+            dir.path().join(".moon/cache/out/old.tar.gz"),
+            FileTime::from_unix_time(1000, 0),
+        )
+        .unwrap();
+
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let stats = cache.collect_stats().await.unwrap();
+
+        assert_eq!(stats.archives.oldest.unwrap().name, "old.tar.gz");
+        assert_eq!(stats.archives.newest.unwrap().name, "new.tar.gz");
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reads_target_states() {
+        let dir = assert_fs::TempDir::new().unwrap();
+
+        dir.child(".moon/cache/runs/foo/bar/lastRunState.json")
+            .write_str(r#"{"cacheHits":3,"cacheMisses":1,"exitCode":0,"hash":"abc","lastRunTime":123,"stderr":"","stdout":"","target":"foo:bar"}"#)
+            .unwrap();
+        dir.child(".moon/cache/runs/baz/qux/lastRunState.json")
+            .write_str(r#"{"exitCode":1,"hash":"def","lastRunTime":456,"stderr":"","stdout":"","target":"baz:qux"}"#)
+            .unwrap();
+        dir.child(".moon/cache/runs/foo/runfile.json")
+            .write_str("{}")
+            .unwrap();
+
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let stats = cache.collect_stats().await.unwrap();
+
+        assert_eq!(stats.runs.count, 3);
+        assert_eq!(
+            stats.targets,
+            vec![
+                CacheTargetStats {
+                    exit_code: 1,
+                    hash: "def".into(),
+                    last_run_time: 456,
+                    target: "baz:qux".into(),
+                    ..CacheTargetStats::default()
+                },
+                CacheTargetStats {
+                    cache_hits: 3,
+                    cache_misses: 1,
+                    exit_code: 0,
+                    hash: "abc".into(),
+                    last_run_time: 123,
+                    target: "foo:bar".into(),
+                },
+            ]
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn skips_target_variant_states() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        let mut state = cache.cache_run_target_state("foo:bar").await.unwrap();
+        state.item.cache_hits = 2;
+        state.item.hash = "abc".into();
+        state.save().await.unwrap();
+
+        let mut variant_state = cache
+            .cache_run_target_variant_state("foo:bar", "variant123")
+            .await
+            .unwrap();
+        variant_state.item.cache_hits = 5;
+        variant_state.item.hash = "def".into();
+        variant_state.save().await.unwrap();
+
+        assert!(dir
+            .path()
+            .join(".moon/cache/runs/foo/bar/variant123/lastRunState.json")
+            .exists());
+
+        let stats = cache.collect_stats().await.unwrap();
+
+        assert_eq!(
+            stats.targets,
+            vec![CacheTargetStats {
+                cache_hits: 2,
+                hash: "abc".into(),
+                target: "foo:bar".into(),
+                ..CacheTargetStats::default()
+            }]
+        );
+
+        dir.close().unwrap();
+    }
+}
+
+mod hash_archives {
+    use super::*;
+    use moon_config::ArchiveFormat;
+    use moon_utils::string_vec;
+
+    #[tokio::test]
+    #[serial]
+    async fn creates_gzip_archive_with_checksum() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        dir.child("project/file.txt").write_str("content").unwrap();
+
+        cache
+            .create_hash_archive(
+                "abc123",
+                &dir.path().join("project"),
+                &string_vec!["file.txt"],
+            )
+            .await
+            .unwrap();
+
+        assert!(cache.outputs_dir.join("abc123.tar.gz").exists());
+        assert!(cache.outputs_dir.join("abc123.tar.gz.sha256").exists());
+        // Temporary files were moved into place
+        assert_eq!(fs::read_dir(&cache.outputs_dir).unwrap().count(), 2);
+        assert!(cache.is_hash_cached("abc123"));
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn creates_and_hydrates_zstd_archive() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mut cache = CacheEngine::create(dir.path()).await.unwrap();
+        cache.archive_format = ArchiveFormat::Zstd;
+
+        dir.child("project/file.txt").write_str("content").unwrap();
+
+        let project_root = dir.path().join("project");
+
+        cache
+            .create_hash_archive("abc123", &project_root, &string_vec!["file.txt"])
+            .await
+            .unwrap();
+
+        assert!(cache.outputs_dir.join("abc123.tar.zst").exists());
+        assert!(cache.is_hash_cached("abc123"));
+
+        fs::remove_file(project_root.join("file.txt")).unwrap();
+
+        assert!(cache
+            .hydrate_from_hash_archive("abc123", &project_root)
+            .await
+            .unwrap());
+
+        assert_eq!(
+            fs::read_to_string(project_root.join("file.txt")).unwrap(),
+            "content"
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn treats_corrupt_archive_as_miss() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let project_root = dir.path().join("project");
+
+        dir.child("project/file.txt").write_str("content").unwrap();
+
+        cache
+            .create_hash_archive("abc123", &project_root, &string_vec!["file.txt"])
+            .await
+            .unwrap();
+
+        let archive_path = cache.outputs_dir.join("abc123.tar.gz");
+        let bytes = fs::read(&archive_path).unwrap();
+
+        fs::write(&archive_path, &bytes[0..bytes.len() / 2]).unwrap();
+
+        assert!(!cache
+            .hydrate_from_hash_archive("abc123", &project_root)
+            .await
+            .unwrap());
+        assert!(!archive_path.exists());
+        assert!(!cache.outputs_dir.join("abc123.tar.gz.sha256").exists());
+        assert!(!cache.is_hash_cached("abc123"));
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn hydrates_archive_without_checksum_and_writes_one() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+        let project_root = dir.path().join("project");
+
+        dir.child("project/file.txt").write_str("content").unwrap();
+
+        cache
+            .create_hash_archive("abc123", &project_root, &string_vec!["file.txt"])
+            .await
+            .unwrap();
+
+        // Archives created before checksums were introduced
+        let checksum_path = cache.outputs_dir.join("abc123.tar.gz.sha256");
+
+        fs::remove_file(&checksum_path).unwrap();
+        fs::remove_file(project_root.join("file.txt")).unwrap();
+
+        assert!(cache.is_hash_cached("abc123"));
+        assert!(cache
+            .hydrate_from_hash_archive("abc123", &project_root)
+            .await
+            .unwrap());
+        assert_eq!(
+            fs::read_to_string(project_root.join("file.txt")).unwrap(),
+            "content"
+        );
+        assert!(checksum_path.exists());
+
+        dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn removes_archive_without_checksum_that_fails_to_unpack() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = CacheEngine::create(dir.path()).await.unwrap();
+
+        dir.child(".moon/cache/out/abc123.tar.gz")
+            .write_str("not an archive")
+            .unwrap();
+
+        assert!(!cache
+            .hydrate_from_hash_archive("abc123", &dir.path().join("project"))
+            .await
+            .unwrap());
+        assert!(!cache.outputs_dir.join("abc123.tar.gz").exists());
+
+        dir.close().unwrap();
+    }
+}
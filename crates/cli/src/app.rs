@@ -45,6 +45,12 @@ pub enum NodeCommands {
 
 #[derive(Debug, Subcommand)]
 pub enum QueryCommands {
+    #[clap(
+        name = "cache",
+        about = "Query for statistics about the cache, its archives, and target runs."
+    )]
+    Cache,
+
     #[clap(
         name = "projects",
         about = "Query for projects within the project graph.",
@@ -101,6 +107,31 @@ pub enum QueryCommands {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum ToolchainCommands {
+    #[clap(
+        name = "list",
+        about = "List all tool versions installed in the toolchain, with their size and when they were last used."
+    )]
+    List {
+        #[clap(long, help = "Print in JSON format")]
+        json: bool,
+    },
+
+    #[clap(
+        name = "prune",
+        about = "Remove tool versions that have not been used by any workspace recently."
+    )]
+    Prune {
+        #[clap(
+            long,
+            default_value_t = 30,
+            help = "Keep versions used by a workspace within this many days"
+        )]
+        days: u64,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     // ENVIRONMENT
@@ -172,8 +203,25 @@ pub enum Commands {
     )]
     Teardown,
 
+    // moon toolchain <command>
+    #[clap(name = "toolchain", about = "Manage tools installed in the toolchain.")]
+    Toolchain {
+        #[clap(subcommand)]
+        command: ToolchainCommands,
+    },
+
     // PROJECTS
 
+    // moon check-config
+    #[clap(
+        name = "check-config",
+        about = "Validate all configuration files in the workspace, and the references between them."
+    )]
+    CheckConfig {
+        #[clap(long, help = "Print in JSON format")]
+        json: bool,
+    },
+
     // moon dep-graph [target]
     #[clap(
         name = "dep-graph",
@@ -214,7 +262,13 @@ pub enum Commands {
         name = "sync",
         about = "Sync all projects in the workspace to a healthy state."
     )]
-    Sync,
+    Sync {
+        #[clap(
+            long,
+            help = "Verify that generated files are up to date, without writing"
+        )]
+        check: bool,
+    },
 
     // JOBS
 
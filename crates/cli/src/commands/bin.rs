@@ -5,9 +5,12 @@ use moon_workspace::Workspace;
 
 #[derive(ValueEnum, Clone, Debug)]
 pub enum BinTools {
+    Bun,
+    Deno,
     Node,
     Npm,
     Pnpm,
+    Python,
     Yarn,
 }
 
@@ -37,12 +40,33 @@ pub async fn bin(tool_type: &BinTools) -> Result<(), Box<dyn std::error::Error>>
     let toolchain = &workspace.toolchain;
 
     match tool_type {
+        BinTools::Bun => match toolchain.get_bun() {
+            Some(bun) => {
+                is_installed(bun, toolchain).await;
+                log_bin_path(bun);
+            }
+            None => not_configured(),
+        },
+        BinTools::Deno => match toolchain.get_deno() {
+            Some(deno) => {
+                is_installed(deno, toolchain).await;
+                log_bin_path(deno);
+            }
+            None => not_configured(),
+        },
         BinTools::Node => {
             let node = toolchain.get_node();
 
             is_installed(node, toolchain).await;
             log_bin_path(node);
         }
+        BinTools::Python => match toolchain.get_python() {
+            Some(python) => {
+                is_installed(python, toolchain).await;
+                log_bin_path(python);
+            }
+            None => not_configured(),
+        },
         BinTools::Npm | BinTools::Pnpm | BinTools::Yarn => {
             let node = toolchain.get_node();
 
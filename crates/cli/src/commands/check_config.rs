@@ -0,0 +1,65 @@
+use console::Term;
+use moon_logger::color;
+use moon_terminal::{create_theme, replace_style_tokens, safe_exit};
+use moon_workspace::check_workspace_configs;
+use std::collections::HashSet;
+
+pub async fn check_config(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let report = check_workspace_configs().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.is_valid() {
+        let theme = create_theme();
+
+        println!(
+            "{} Checked {} config files, all are valid",
+            theme.success_prefix,
+            report.files.len()
+        );
+    } else {
+        let term = Term::buffered_stdout();
+        let mut current_file = "";
+        let mut invalid_files = HashSet::new();
+
+        for issue in &report.issues {
+            if issue.file != current_file {
+                current_file = &issue.file;
+                invalid_files.insert(current_file);
+
+                term.write_line("")?;
+                term.write_line(&color::file(current_file))?;
+            }
+
+            let message = replace_style_tokens(&issue.message);
+
+            match &issue.key {
+                Some(key) => {
+                    term.write_line(&format!(
+                        "  {} {} {}",
+                        color::failure("▪"),
+                        color::symbol(key),
+                        message
+                    ))?;
+                }
+                None => {
+                    term.write_line(&format!("  {} {}", color::failure("▪"), message))?;
+                }
+            };
+        }
+
+        term.write_line("")?;
+        term.write_line(&format!(
+            "Found {} issues in {} config files",
+            report.issues.len(),
+            invalid_files.len()
+        ))?;
+        term.flush()?;
+    }
+
+    if !report.is_valid() {
+        safe_exit(1);
+    }
+
+    Ok(())
+}
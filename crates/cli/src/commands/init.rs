@@ -12,7 +12,7 @@ use moon_logger::color;
 use moon_project::detect_projects_with_globs;
 use moon_terminal::create_theme;
 use moon_utils::{fs, path};
-use moon_vcs::{Git, Svn, Vcs};
+use moon_vcs::{Git, Hg, Svn, Vcs};
 use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs::{read_to_string, OpenOptions};
@@ -251,6 +251,13 @@ async fn detect_vcs(dest_dir: &Path) -> Result<(String, String), AnyError> {
         ));
     }
 
+    if dest_dir.join(".hg").exists() {
+        return Ok((
+            "hg".into(),
+            Hg::new("default", dest_dir).get_local_branch().await?,
+        ));
+    }
+
     if dest_dir.join(".svn").exists() {
         return Ok((
             "svn".into(),
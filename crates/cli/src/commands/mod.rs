@@ -1,4 +1,5 @@
 pub mod bin;
+pub mod check_config;
 pub mod ci;
 pub mod dep_graph;
 pub mod init;
@@ -11,3 +12,4 @@ pub mod run;
 pub mod setup;
 pub mod sync;
 pub mod teardown;
+pub mod toolchain;
@@ -76,10 +76,24 @@ pub async fn project(id: &str, json: bool) -> Result<(), Box<dyn std::error::Err
         for name in project.tasks.keys().sorted() {
             let task = project.tasks.get(name).unwrap();
 
-            term.render_entry(
-                name,
-                color::shell(format!("{} {}", task.command, task.args.join(" "))),
-            )?;
+            let command_line = format!("{} {}", task.command, task.args.join(" "));
+            let mut line = color::shell(command_line.trim());
+
+            if let Some(sources) = project.task_sources.get(name) {
+                line = format!(
+                    "{} {}{}{}",
+                    line,
+                    color::muted_light("("),
+                    sources
+                        .iter()
+                        .map(color::file)
+                        .collect::<Vec<_>>()
+                        .join(&color::muted_light(", ")),
+                    color::muted_light(")"),
+                );
+            }
+
+            term.render_entry(name, line)?;
         }
     }
 
@@ -1,9 +1,20 @@
+pub use crate::queries::cache::query_cache;
 pub use crate::queries::projects::{query_projects, QueryProjectsOptions, QueryProjectsResult};
 pub use crate::queries::touched_files::{
     query_touched_files, QueryTouchedFilesOptions, QueryTouchedFilesResult,
 };
 use moon_workspace::Workspace;
 
+pub async fn cache() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = Workspace::load().await?;
+
+    let result = query_cache(&workspace).await?;
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+
+    Ok(())
+}
+
 pub async fn projects(options: &QueryProjectsOptions) -> Result<(), Box<dyn std::error::Error>> {
     let workspace = Workspace::load().await?;
 
@@ -1,12 +1,116 @@
+use crate::app::BIN_NAME;
 use indicatif::{ProgressBar, ProgressStyle};
 use moon_action_runner::{ActionRunner, DepGraph};
+use moon_config::NodePackageManager;
+use moon_lang_node::NODE;
+use moon_logger::{color, warn};
 use moon_terminal::create_theme;
-use moon_workspace::Workspace;
+use moon_utils::{fs, path, process};
+use moon_vcs::sync_hooks;
+use moon_workspace::{generate_codeowners, get_codeowners_path, Workspace, WorkspaceError};
+use std::env;
 use std::time::Duration;
 
-pub async fn sync() -> Result<(), Box<dyn std::error::Error>> {
+const LOG_TARGET: &str = "moon:sync";
+
+/// Hooks are ran outside of our process and may not have `moon` on `PATH`,
+/// so use the package manager when installed locally (so that the version
+/// pinned in `package.json` is used), otherwise the current binary.
+fn get_moon_command(workspace: &Workspace) -> String {
+    let local_bin = workspace
+        .root
+        .join(NODE.vendor_dir)
+        .join("@moonrepo")
+        .join("cli")
+        .join(BIN_NAME);
+
+    if local_bin.exists() {
+        return match workspace.config.node.package_manager {
+            NodePackageManager::Npm => "npx moon",
+            NodePackageManager::Pnpm => "pnpm exec moon",
+            NodePackageManager::Yarn => "yarn moon",
+        }
+        .to_owned();
+    }
+
+    match env::current_exe() {
+        Ok(exe_path) => {
+            process::join_args([path::standardize_separators(exe_path.to_string_lossy())])
+        }
+        Err(_) => "moon".to_owned(),
+    }
+}
+
+async fn sync_vcs_hooks(workspace: &Workspace) -> Result<(), Box<dyn std::error::Error>> {
+    let hooks = &workspace.config.vcs.hooks;
+
+    match workspace.vcs.get_hooks_dir().await? {
+        Some(hooks_dir) => {
+            sync_hooks(&hooks_dir, hooks, &get_moon_command(workspace)).await?;
+        }
+        None => {
+            if !hooks.is_empty() {
+                warn!(
+                    target: LOG_TARGET,
+                    "Hooks are only supported for git, skipping {}",
+                    color::symbol("vcs.hooks")
+                );
+            }
+        }
+    };
+
+    Ok(())
+}
+
+async fn sync_codeowners(
+    workspace: &Workspace,
+    check: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = match &workspace.config.codeowners {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+
+    let path = get_codeowners_path(&workspace.root, config);
+    let contents = generate_codeowners(config, &workspace.projects)?;
+
+    let current_contents = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+
+    if current_contents == contents {
+        return Ok(());
+    }
+
+    if check {
+        return Err(Box::new(WorkspaceError::CodeownersOutOfDate(path)));
+    }
+
+    fs::create_dir_all(path.parent().unwrap()).await?;
+    fs::write(&path, contents).await?;
+
+    Ok(())
+}
+
+pub async fn sync(check: bool) -> Result<(), Box<dyn std::error::Error>> {
     let theme = create_theme();
 
+    // Only verify generated files, and avoid mutating the workspace
+    if check {
+        let workspace = Workspace::load().await?;
+
+        // Loading the graph enforces project constraints
+        workspace.projects.load_all()?;
+
+        sync_codeowners(&workspace, true).await?;
+
+        println!("{} Generated files are up to date", theme.success_prefix);
+
+        return Ok(());
+    }
+
     let pb = ProgressBar::new_spinner();
     pb.set_message("Syncing projects...");
     pb.enable_steady_tick(Duration::from_millis(50));
@@ -20,6 +124,12 @@ pub async fn sync() -> Result<(), Box<dyn std::error::Error>> {
         project_count += 1;
     }
 
+    if workspace.vcs.is_enabled() {
+        sync_vcs_hooks(&workspace).await?;
+    }
+
+    sync_codeowners(&workspace, false).await?;
+
     let mut runner = ActionRunner::new(workspace);
     let results = runner.run(graph, None).await?;
 
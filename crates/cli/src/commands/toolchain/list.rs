@@ -0,0 +1,55 @@
+use super::{format_size, format_time};
+use console::Term;
+use moon_logger::color;
+use moon_terminal::{ExtendedTerm, Label};
+use moon_workspace::Workspace;
+
+pub async fn list(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = Workspace::load().await?;
+    let tools = workspace.toolchain.list_installed().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&tools)?);
+
+        return Ok(());
+    }
+
+    let term = Term::buffered_stdout();
+
+    if tools.is_empty() {
+        term.write_line("No tools have been installed into the toolchain")?;
+        term.flush()?;
+
+        return Ok(());
+    }
+
+    let mut last_name = "";
+
+    for tool in &tools {
+        if tool.name != last_name {
+            term.write_line("")?;
+            term.render_label(Label::Brand, &tool.name)?;
+
+            last_name = &tool.name;
+        }
+
+        let last_used = match tool.last_used_time {
+            Some(time) => format!("last used {}", format_time(time)),
+            None => String::from("never used"),
+        };
+
+        term.render_entry(
+            &tool.version,
+            format!(
+                "{} {}",
+                format_size(tool.size),
+                color::muted_light(format!("({})", last_used))
+            ),
+        )?;
+    }
+
+    term.write_line("")?;
+    term.flush()?;
+
+    Ok(())
+}
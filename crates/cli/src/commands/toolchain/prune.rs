@@ -0,0 +1,45 @@
+use super::format_size;
+use console::Term;
+use moon_logger::color;
+use moon_terminal::ExtendedTerm;
+use moon_workspace::Workspace;
+use std::time::Duration;
+
+pub async fn prune(days: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = Workspace::load().await?;
+    let removed = workspace
+        .toolchain
+        .prune(Duration::from_secs(days * 86400))
+        .await?;
+
+    let term = Term::buffered_stdout();
+
+    if removed.is_empty() {
+        term.write_line("No unused tools to prune")?;
+        term.flush()?;
+
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    let mut list = vec![];
+
+    for tool in &removed {
+        freed += tool.size;
+        list.push(format!(
+            "{} {}",
+            color::id(&tool.id),
+            color::muted_light(format_size(tool.size))
+        ));
+    }
+
+    term.write_line(&format!(
+        "Pruned {} tool versions, freeing {}",
+        removed.len(),
+        format_size(freed)
+    ))?;
+    term.render_list(list)?;
+    term.flush()?;
+
+    Ok(())
+}
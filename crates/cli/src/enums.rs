@@ -29,6 +29,7 @@ pub enum TouchedStatus {
     All,
     Deleted,
     Modified,
+    Renamed,
     Staged,
     Unstaged,
     Untracked,
@@ -5,6 +5,7 @@ mod helpers;
 pub mod queries;
 
 use crate::commands::bin::bin;
+use crate::commands::check_config::check_config;
 use crate::commands::ci::{ci, CiOptions};
 use crate::commands::dep_graph::dep_graph;
 use crate::commands::init::{init, InitOptions};
@@ -17,8 +18,9 @@ use crate::commands::run::{run, RunOptions};
 use crate::commands::setup::setup;
 use crate::commands::sync::sync;
 use crate::commands::teardown::teardown;
+use crate::commands::toolchain;
 use crate::helpers::setup_colors;
-use app::{App, Commands, MigrateCommands, NodeCommands, QueryCommands};
+use app::{App, Commands, MigrateCommands, NodeCommands, QueryCommands, ToolchainCommands};
 use clap::Parser;
 use console::Term;
 use enums::LogLevel;
@@ -62,6 +64,7 @@ pub async fn run_cli() {
     // Match and run subcommand
     let result = match &args.command {
         Commands::Bin { tool } => bin(tool).await,
+        Commands::CheckConfig { json } => check_config(*json).await,
         Commands::Ci {
             base,
             head,
@@ -103,8 +106,9 @@ pub async fn run_cli() {
         },
         Commands::Project { id, json } => project(id, *json).await,
         Commands::ProjectGraph { id } => project_graph(id).await,
-        Commands::Sync => sync().await,
+        Commands::Sync { check } => sync(*check).await,
         Commands::Query { command } => match command {
+            QueryCommands::Cache => query::cache().await,
             QueryCommands::Projects {
                 alias,
                 id,
@@ -165,6 +169,10 @@ pub async fn run_cli() {
         }
         Commands::Setup => setup().await,
         Commands::Teardown => teardown().await,
+        Commands::Toolchain { command } => match command {
+            ToolchainCommands::List { json } => toolchain::list(*json).await,
+            ToolchainCommands::Prune { days } => toolchain::prune(*days).await,
+        },
     };
 
     if let Err(error) = result {
@@ -0,0 +1,12 @@
+use moon_cache::CacheStats;
+use moon_logger::debug;
+use moon_workspace::{Workspace, WorkspaceError};
+
+const LOG_TARGET: &str = "moon:query:cache";
+
+/// Query statistics about the contents of the `.moon/cache` directory.
+pub async fn query_cache(workspace: &Workspace) -> Result<CacheStats, WorkspaceError> {
+    debug!(target: LOG_TARGET, "Querying for cache statistics");
+
+    Ok(workspace.cache.collect_stats().await?)
+}
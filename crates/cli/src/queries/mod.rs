@@ -1,2 +1,3 @@
+pub mod cache;
 pub mod projects;
 pub mod touched_files;
@@ -88,6 +88,12 @@ pub async fn query_touched_files(
         TouchedStatus::All => touched_files_map.all,
         TouchedStatus::Deleted => touched_files_map.deleted,
         TouchedStatus::Modified => touched_files_map.modified,
+        // Include both sides of the rename
+        TouchedStatus::Renamed => touched_files_map
+            .renamed
+            .into_iter()
+            .flat_map(|(to, from)| [to, from])
+            .collect(),
         TouchedStatus::Staged => touched_files_map.staged,
         TouchedStatus::Unstaged => touched_files_map.unstaged,
         TouchedStatus::Untracked => touched_files_map.untracked,
@@ -0,0 +1,129 @@
+use moon_utils::test::{create_moon_command, create_sandbox, get_assert_stdout_output};
+use predicates::prelude::*;
+use serde_json::Value;
+
+fn find_issue<'a>(issues: &'a [Value], file: &str, key: Option<&str>) -> Option<&'a Value> {
+    issues.iter().find(|issue| {
+        issue["file"] == file
+            && match key {
+                Some(key) => issue["key"] == key,
+                None => true,
+            }
+    })
+}
+
+#[test]
+fn passes_for_valid_workspace() {
+    let fixture = create_sandbox("project-graph/dependencies");
+
+    let assert = create_moon_command(fixture.path())
+        .arg("check-config")
+        .assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("Checked 5 config files"));
+}
+
+#[test]
+fn fails_for_invalid_configs() {
+    let fixture = create_sandbox("check-config");
+
+    let assert = create_moon_command(fixture.path())
+        .arg("check-config")
+        .assert();
+
+    assert
+        .failure()
+        .stdout(predicate::str::contains("apps/broken/moon.yml"))
+        .stdout(predicate::str::contains("apps/deps/moon.yml"))
+        .stdout(predicate::str::contains("Found 4 issues in 2 config files"));
+}
+
+#[test]
+fn outputs_issues_as_json() {
+    let fixture = create_sandbox("check-config");
+
+    let assert = create_moon_command(fixture.path())
+        .arg("check-config")
+        .arg("--json")
+        .assert()
+        .failure();
+
+    let report: Value = serde_json::from_str(&get_assert_stdout_output(&assert)).unwrap();
+    let files = report["files"].as_array().unwrap();
+    let issues = report["issues"].as_array().unwrap();
+
+    assert_eq!(files.len(), 4);
+
+    // Figment errors
+    assert!(find_issue(issues, "apps/broken/moon.yml", None).is_some());
+
+    // dependsOn
+    assert_eq!(
+        find_issue(issues, "apps/deps/moon.yml", Some("dependsOn[1]")).unwrap()["message"],
+        "Unknown project \"missing\""
+    );
+
+    // Task deps
+    let deps_messages = issues
+        .iter()
+        .filter(|issue| issue["key"] == "tasks.test.deps")
+        .map(|issue| issue["message"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        deps_messages,
+        vec![
+            "Target \"valid:unknown\" references an unknown task \"unknown\"",
+            "Target \"other:build\" references an unknown project \"other\"",
+        ]
+    );
+}
+
+#[test]
+fn reports_constraint_violations() {
+    let fixture = create_sandbox("project-graph/constraints");
+
+    let assert = create_moon_command(fixture.path())
+        .arg("check-config")
+        .arg("--json")
+        .assert()
+        .failure();
+
+    let report: Value = serde_json::from_str(&get_assert_stdout_output(&assert)).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+
+    assert_eq!(issues.len(), 3);
+
+    for file in [
+        "tagDenied/moon.yml",
+        "tagNotAllowed/moon.yml",
+        "typeViolation/moon.yml",
+    ] {
+        assert!(find_issue(issues, file, Some("dependsOn[0]")).is_some());
+    }
+}
+
+#[test]
+fn reports_multiple_config_formats() {
+    let fixture = create_sandbox("config-formats");
+
+    std::fs::write(fixture.path().join("json/moon.yml"), "tasks: {}\n").unwrap();
+
+    let assert = create_moon_command(fixture.path())
+        .arg("check-config")
+        .arg("--json")
+        .assert()
+        .failure();
+
+    let report: Value = serde_json::from_str(&get_assert_stdout_output(&assert)).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("moon.yml, moon.json"));
+    assert_eq!(issues[0]["file"], "json/moon.yml");
+}
@@ -2,6 +2,7 @@ use insta::assert_snapshot;
 use moon_utils::test::{
     create_moon_command, create_sandbox, get_assert_output, get_assert_stderr_output_clean,
 };
+use predicates::prelude::*;
 
 #[test]
 fn unknown_project() {
@@ -103,3 +104,99 @@ fn root_level() {
 
     assert_snapshot!(get_assert_output(&assert));
 }
+
+#[test]
+fn with_scoped_global_tasks() {
+    let fixture = create_sandbox("projects");
+
+    std::fs::create_dir_all(fixture.path().join(".moon/tasks")).unwrap();
+    std::fs::write(
+        fixture.path().join(".moon/tasks/typescript.yml"),
+        "tasks:\n  lint:\n    command: eslint\n  typecheck:\n    command: tsc\n",
+    )
+    .unwrap();
+    std::fs::write(
+        fixture.path().join(".moon/tasks/bash.yml"),
+        "tasks:\n  shellcheck:\n    command: shellcheck\n",
+    )
+    .unwrap();
+
+    let assert = create_moon_command(fixture.path())
+        .arg("project")
+        .arg("tasks")
+        .assert();
+
+    assert_snapshot!(get_assert_output(&assert));
+}
+
+mod config_formats {
+    use super::*;
+
+    #[test]
+    fn loads_json_configs() {
+        let fixture = create_sandbox("config-formats");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("project")
+            .arg("json")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("build: webpack (moon.json)"))
+            .stdout(predicate::str::contains(
+                "lint: eslint (.moon/tasks/javascript.toml)",
+            ))
+            .stdout(predicate::str::contains("src/**/*"));
+    }
+
+    #[test]
+    fn loads_toml_configs() {
+        let fixture = create_sandbox("config-formats");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("project")
+            .arg("toml")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("build: vite build (moon.toml)"));
+    }
+
+    #[test]
+    fn errors_when_multiple_formats_exist() {
+        let fixture = create_sandbox("config-formats");
+
+        std::fs::write(
+            fixture.path().join("toml/moon.yml"),
+            "tasks:\n  build:\n    command: vite\n",
+        )
+        .unwrap();
+
+        let assert = create_moon_command(fixture.path())
+            .arg("project")
+            .arg("toml")
+            .assert();
+
+        assert.failure().stderr(predicate::str::contains(
+            "(moon.yml, moon.toml). Only one format may be used.",
+        ));
+    }
+
+    #[test]
+    fn errors_when_multiple_workspace_formats_exist() {
+        let fixture = create_sandbox("config-formats");
+
+        std::fs::write(fixture.path().join(".moon/workspace.yml"), "projects: {}\n").unwrap();
+
+        let assert = create_moon_command(fixture.path())
+            .arg("project")
+            .arg("toml")
+            .assert();
+
+        assert.failure().stderr(predicate::str::contains(
+            "(workspace.yml, workspace.toml). Only one format may be used.",
+        ));
+    }
+}
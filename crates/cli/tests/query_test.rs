@@ -1,9 +1,62 @@
+use moon_cache::CacheStats;
 use moon_cli::enums::TouchedStatus;
 use moon_cli::queries::projects::QueryProjectsResult;
 use moon_cli::queries::touched_files::QueryTouchedFilesResult;
 use moon_utils::string_vec;
 use moon_utils::test::{create_moon_command, create_sandbox, get_assert_output, run_git_command};
 
+mod cache {
+    use super::*;
+    use moon_utils::test::create_sandbox_with_git;
+
+    #[test]
+    fn returns_empty_stats() {
+        let fixture = create_sandbox("projects");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("query")
+            .arg("cache")
+            .assert();
+
+        let json: CacheStats = serde_json::from_str(&get_assert_output(&assert)).unwrap();
+
+        assert_eq!(json.archives.count, 0);
+        assert!(json.targets.is_empty());
+    }
+
+    #[test]
+    fn tracks_hits_and_misses() {
+        let fixture = create_sandbox_with_git("cases");
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("outputs:generateFile")
+            .assert();
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("outputs:generateFile")
+            .assert();
+
+        let assert = create_moon_command(fixture.path())
+            .arg("query")
+            .arg("cache")
+            .assert();
+
+        let json: CacheStats = serde_json::from_str(&get_assert_output(&assert)).unwrap();
+        let target = json
+            .targets
+            .iter()
+            .find(|t| t.target == "outputs:generateFile")
+            .unwrap();
+
+        assert_eq!(json.archives.count, 1);
+        assert_eq!(target.cache_hits, 1);
+        assert_eq!(target.cache_misses, 1);
+        assert!(!target.hash.is_empty());
+    }
+}
+
 mod projects {
     use super::*;
 
@@ -126,6 +179,7 @@ mod projects {
 mod touched_files {
     use super::*;
     use moon_utils::test::create_sandbox_with_git;
+    use std::collections::HashSet;
 
     #[test]
     fn can_change_options() {
@@ -151,4 +205,30 @@ mod touched_files {
         assert_eq!(json.options.status, TouchedStatus::Deleted);
         assert!(!json.options.local);
     }
+
+    #[test]
+    fn returns_both_sides_of_renames() {
+        let fixture = create_sandbox_with_git("cases");
+
+        run_git_command(fixture.path(), |cmd| {
+            cmd.args(["mv", "base/moon.yml", "noop/moved.yml"]);
+        });
+
+        let assert = create_moon_command(fixture.path())
+            .arg("query")
+            .arg("touched-files")
+            .args(["--local", "--status", "renamed"])
+            .assert();
+
+        let json: QueryTouchedFilesResult =
+            serde_json::from_str(&get_assert_output(&assert)).unwrap();
+
+        assert_eq!(
+            json.files,
+            HashSet::from([
+                fixture.path().join("base/moon.yml"),
+                fixture.path().join("noop/moved.yml")
+            ])
+        );
+    }
 }
@@ -495,6 +495,85 @@ mod system {
         assert_snapshot!(get_path_safe_output(&assert, fixture.path()));
     }
 
+    #[test]
+    fn loads_env_file() {
+        let fixture = create_sandbox_with_git("cases");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:envFile")
+            .assert();
+
+        assert_snapshot!(get_assert_output(&assert));
+    }
+
+    #[tokio::test]
+    async fn env_file_changes_hash() {
+        let fixture = create_sandbox_with_git("cases");
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:envFile")
+            .assert();
+
+        let hash1 = extract_hash_from_run(fixture.path(), "system:envFile").await;
+
+        fs::write(fixture.path().join("system/.env"), "MOON_BAR=789").unwrap();
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:envFile")
+            .assert();
+
+        let hash2 = extract_hash_from_run(fixture.path(), "system:envFile").await;
+
+        assert_ne!(hash1, hash2);
+    }
+
+    #[tokio::test]
+    async fn gitignored_env_file_changes_hash() {
+        let fixture = create_sandbox_with_git("cases");
+
+        fs::write(fixture.path().join(".gitignore"), ".env\n").unwrap();
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:envFile")
+            .assert();
+
+        let hash1 = extract_hash_from_run(fixture.path(), "system:envFile").await;
+
+        fs::write(fixture.path().join("system/.env"), "MOON_BAR=789").unwrap();
+
+        create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:envFile")
+            .assert();
+
+        let hash2 = extract_hash_from_run(fixture.path(), "system:envFile").await;
+
+        assert_ne!(hash1, hash2);
+    }
+
+    #[test]
+    fn runs_deps_with_overrides() {
+        let fixture = create_sandbox_with_git("cases");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:depOverrides")
+            .assert();
+
+        let output = get_assert_output(&assert);
+
+        // Ran both with and without the custom args
+        assert!(output.matches("system:passthroughArgs").count() >= 2);
+        assert!(output.contains("--mode production"));
+        assert!(output.contains("MOON_BAZ=from dep"));
+
+        assert.success();
+    }
+
     #[test]
     fn runs_from_project_root() {
         let fixture = create_sandbox_with_git("cases");
@@ -530,6 +609,54 @@ mod system {
 
         assert_snapshot!(get_assert_output(&assert));
     }
+
+    #[test]
+    fn runs_through_shell() {
+        let fixture = create_sandbox_with_git("cases");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:shell")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("HELLO WORLD\ndone"));
+    }
+
+    #[test]
+    fn passes_args_through_shell() {
+        let fixture = create_sandbox_with_git("cases");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:shellArgs")
+            .arg("--")
+            .arg("foo  bar")
+            .arg("baz")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("args: foo  bar baz"));
+    }
+
+    #[test]
+    fn doesnt_interpret_passthrough_args_in_shell() {
+        let fixture = create_sandbox_with_git("cases");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("run")
+            .arg("system:shellArgs")
+            .arg("--")
+            .arg("a && echo injected")
+            .arg("$HOME")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("args: a && echo injected $HOME"));
+    }
 }
 
 #[cfg(windows)]
@@ -11,3 +11,180 @@ fn syncs_all_projects() {
 
     assert.success();
 }
+
+mod hooks {
+    use super::*;
+    use moon_utils::test::create_sandbox_with_git;
+    use std::fs;
+
+    fn append_hooks_config(root: &std::path::Path) {
+        let config_path = root.join(".moon/workspace.yml");
+        let mut config = fs::read_to_string(&config_path).unwrap();
+
+        config.push_str(
+            "\nvcs:\n  hooks:\n    pre-commit:\n      - 'a:lint'\n      - 'echo \"done\"'\n",
+        );
+
+        fs::write(config_path, config).unwrap();
+    }
+
+    #[test]
+    fn writes_configured_hooks() {
+        let fixture = create_sandbox_with_git("project-graph/dependencies");
+
+        append_hooks_config(fixture.path());
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        let hook = fs::read_to_string(fixture.path().join(".git/hooks/pre-commit")).unwrap();
+
+        assert!(hook.contains("# Automatically generated by moon. DO NOT MODIFY!"));
+        assert!(hook.contains("moon run a:lint --affected --status staged"));
+        assert!(hook.contains("echo \"done\""));
+    }
+
+    #[test]
+    fn removes_stale_hooks() {
+        let fixture = create_sandbox_with_git("project-graph/dependencies");
+        let hooks_dir = fixture.path().join(".git/hooks");
+
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(
+            hooks_dir.join("pre-push"),
+            "#!/bin/sh\n# Automatically generated by moon. DO NOT MODIFY!\nmoon run a:test\n",
+        )
+        .unwrap();
+        fs::write(hooks_dir.join("post-merge"), "#!/bin/sh\nnpm install\n").unwrap();
+
+        append_hooks_config(fixture.path());
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        assert!(hooks_dir.join("pre-commit").exists());
+        assert!(!hooks_dir.join("pre-push").exists());
+        assert!(hooks_dir.join("post-merge").exists());
+    }
+
+    #[test]
+    fn respects_hooks_path() {
+        let fixture = create_sandbox_with_git("project-graph/dependencies");
+
+        moon_utils::test::run_git_command(fixture.path(), |cmd| {
+            cmd.args(["config", "core.hooksPath", ".githooks"]);
+        });
+
+        append_hooks_config(fixture.path());
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        assert!(fixture.path().join(".githooks/pre-commit").exists());
+        assert!(!fixture.path().join(".git/hooks/pre-commit").exists());
+    }
+}
+
+mod codeowners {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn generates_github_file() {
+        let fixture = create_sandbox("codeowners");
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        assert_snapshot!(fs::read_to_string(fixture.path().join(".github/CODEOWNERS")).unwrap());
+    }
+
+    #[test]
+    fn generates_gitlab_file() {
+        let fixture = create_sandbox("codeowners");
+        let config_path = fixture.path().join(".moon/workspace.yml");
+        let config = fs::read_to_string(&config_path).unwrap();
+
+        fs::write(
+            &config_path,
+            config.replace("codeowners:\n", "codeowners:\n  provider: gitlab\n"),
+        )
+        .unwrap();
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        assert_snapshot!(fs::read_to_string(fixture.path().join(".gitlab/CODEOWNERS")).unwrap());
+    }
+
+    #[test]
+    fn check_fails_when_out_of_date() {
+        let fixture = create_sandbox("codeowners");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("sync")
+            .arg("--check")
+            .assert();
+
+        assert!(get_assert_output(&assert).contains("is out of date"));
+        assert.failure();
+
+        assert!(!fixture.path().join(".github/CODEOWNERS").exists());
+    }
+
+    #[test]
+    fn check_passes_when_up_to_date() {
+        let fixture = create_sandbox("codeowners");
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .assert()
+            .success();
+
+        create_moon_command(fixture.path())
+            .arg("sync")
+            .arg("--check")
+            .assert()
+            .success();
+    }
+}
+
+mod constraints {
+    use super::*;
+    use predicates::prelude::*;
+
+    #[test]
+    fn fails_for_invalid_edges() {
+        let fixture = create_sandbox("project-graph/constraints");
+
+        let assert = create_moon_command(fixture.path()).arg("sync").assert();
+
+        assert
+            .failure()
+            .stderr(predicate::str::contains("may not depend on"));
+    }
+
+    #[test]
+    fn check_fails_for_invalid_edges() {
+        let fixture = create_sandbox("project-graph/constraints");
+
+        let assert = create_moon_command(fixture.path())
+            .arg("sync")
+            .arg("--check")
+            .assert();
+
+        assert
+            .failure()
+            .stderr(predicate::str::contains("may not depend on"));
+    }
+}
@@ -1 +1,4 @@
 pub const FLAG_PROJECTS_USING_GLOB: &str = "MOON_PROJECTS_USING_GLOBS";
+
+// Seconds that documents fetched for `extends` are cached (1 hour)
+pub const EXTENDS_CACHE_TTL: u64 = 3600;
@@ -22,6 +22,19 @@ pub fn format_figment_errors(errors: Vec<FigmentError>) -> String {
     list.join("\n")
 }
 
+/// Flatten figment errors into a list of key paths and messages.
+pub fn flatten_figment_errors(errors: Vec<FigmentError>) -> Vec<(String, String)> {
+    let mut list = vec![];
+
+    for error in errors {
+        for nested_error in error {
+            list.push((nested_error.path.join("."), nested_error.kind.to_string()));
+        }
+    }
+
+    list
+}
+
 pub fn map_validation_errors_to_figment_errors(
     figment: &Figment,
     validation_errors: &ValidationErrors,
@@ -0,0 +1,184 @@
+use figment::{
+    providers::{Format, Json, Toml, Yaml},
+    value::{Dict, Map},
+    Error as FigmentError, Figment, Profile, Provider,
+};
+use moon_error::MoonError;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// All supported formats, in the order they are searched for.
+    pub const ALL: [ConfigFormat; 3] = [ConfigFormat::Yaml, ConfigFormat::Json, ConfigFormat::Toml];
+
+    /// Detect the format from a file path or URL extension.
+    /// Unknown extensions fallback to YAML.
+    pub fn from_path<T: AsRef<Path>>(path: T) -> ConfigFormat {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+        {
+            "json" => ConfigFormat::Json,
+            "toml" => ConfigFormat::Toml,
+            _ => ConfigFormat::Yaml,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yml",
+        }
+    }
+
+    /// All extensions this format is parsed from, with the preferred one first.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Json => &["json"],
+            ConfigFormat::Toml => &["toml"],
+            ConfigFormat::Yaml => &["yml", "yaml"],
+        }
+    }
+
+    /// Return true if the file name or URL has an extension we can parse.
+    pub fn is_supported(value: &str) -> bool {
+        value.ends_with(".yml")
+            || value.ends_with(".yaml")
+            || value.ends_with(".json")
+            || value.ends_with(".toml")
+    }
+
+    /// Merge the file into the figment using the provider for this format.
+    pub fn merge_file(&self, figment: Figment, path: &Path, profile: &str) -> Figment {
+        match self {
+            ConfigFormat::Json => figment.merge(Json::file(path).profile(profile)),
+            ConfigFormat::Toml => figment.merge(Toml::file(path).profile(profile)),
+            ConfigFormat::Yaml => figment.merge(Yaml::file(path).profile(profile)),
+        }
+    }
+
+    /// Parse string content using the provider for this format.
+    pub fn parse_string(
+        &self,
+        content: &str,
+        profile: Profile,
+    ) -> Result<Map<Profile, Dict>, FigmentError> {
+        match self {
+            ConfigFormat::Json => Json::string(content).profile(profile).data(),
+            ConfigFormat::Toml => Toml::string(content).profile(profile).data(),
+            ConfigFormat::Yaml => Yaml::string(content).profile(profile).data(),
+        }
+    }
+}
+
+/// Merge a config file into the figment, choosing a provider based on its extension.
+pub fn merge_config_file<T: AsRef<Path>>(figment: Figment, path: T, profile: &str) -> Figment {
+    let path = path.as_ref();
+
+    ConfigFormat::from_path(path).merge_file(figment, path, profile)
+}
+
+/// Find a config file within the directory, in any supported format.
+/// The file name's extension is replaced with each format's extension.
+/// Errors if the file exists in more than one format.
+pub fn find_config_file(dir: &Path, file_name: &str) -> Result<Option<PathBuf>, MoonError> {
+    let base = dir.join(file_name);
+    let mut found = vec![];
+
+    for format in ConfigFormat::ALL {
+        for ext in format.extensions() {
+            let path = base.with_extension(ext);
+
+            if path.exists() {
+                found.push(path);
+            }
+        }
+    }
+
+    if found.len() > 1 {
+        return Err(MoonError::MultipleConfigFormats(
+            dir.to_path_buf(),
+            found
+                .iter()
+                .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+
+    Ok(found.pop())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn detects_format_from_extension() {
+        assert_eq!(ConfigFormat::from_path("moon.yml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("moon.yaml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("moon.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("moon.toml"), ConfigFormat::Toml);
+        assert_eq!(
+            ConfigFormat::from_path("https://domain.com/config.toml"),
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    fn finds_file_in_any_format() {
+        let dir = assert_fs::TempDir::new().unwrap();
+
+        assert_eq!(find_config_file(dir.path(), "moon.yml").unwrap(), None);
+
+        dir.child("moon.toml").write_str("").unwrap();
+
+        assert_eq!(
+            find_config_file(dir.path(), "moon.yml").unwrap(),
+            Some(dir.path().join("moon.toml"))
+        );
+    }
+
+    #[test]
+    fn finds_file_with_yaml_extension() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("moon.yaml").write_str("").unwrap();
+
+        assert_eq!(
+            find_config_file(dir.path(), "moon.yml").unwrap(),
+            Some(dir.path().join("moon.yaml"))
+        );
+    }
+
+    #[test]
+    fn errors_when_yml_and_yaml_exist() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("moon.yml").write_str("").unwrap();
+        dir.child("moon.yaml").write_str("").unwrap();
+
+        let error = find_config_file(dir.path(), "moon.yml").unwrap_err();
+
+        assert!(error.to_string().contains("moon.yml, moon.yaml"));
+    }
+
+    #[test]
+    fn errors_when_multiple_formats_exist() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("moon.yml").write_str("").unwrap();
+        dir.child("moon.json").write_str("{}").unwrap();
+
+        let error = find_config_file(dir.path(), "moon.yml").unwrap_err();
+
+        assert!(error.to_string().contains("moon.yml, moon.json"));
+    }
+}
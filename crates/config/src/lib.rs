@@ -1,19 +1,25 @@
 pub mod constants;
 mod errors;
+mod format;
 mod project;
 mod providers;
 mod types;
 mod validators;
 mod workspace;
 
-pub use errors::format_figment_errors;
+pub use errors::{flatten_figment_errors, format_figment_errors};
+pub use figment::Error as FigmentError;
+pub use format::{find_config_file, merge_config_file, ConfigFormat};
 pub use project::dep::*;
 pub use project::global::GlobalProjectConfig;
 pub use project::task::*;
 pub use project::*;
 pub use types::*;
 pub use validator::ValidationErrors;
+pub use workspace::bun::*;
+pub use workspace::deno::*;
 pub use workspace::node::*;
+pub use workspace::python::*;
 pub use workspace::*;
 
 pub fn load_workspace_config_template() -> &'static str {
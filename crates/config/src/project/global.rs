@@ -1,14 +1,14 @@
 // .moon/project.yml
 
 use crate::errors::{create_validation_error, map_validation_errors_to_figment_errors};
+use crate::format::merge_config_file;
 use crate::project::task::TaskConfig;
-use crate::providers::url::Url;
+use crate::project::{ProjectLanguage, ProjectType};
+use crate::providers::url::{find_cache_dir, is_url, Url};
 use crate::types::FileGroups;
 use crate::validators::{validate_extends, validate_id};
-use figment::{
-    providers::{Format, Serialized, Yaml},
-    Error as FigmentError, Figment,
-};
+use figment::{providers::Serialized, Error as FigmentError, Figment};
+use moon_constants as constants;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
@@ -61,16 +61,73 @@ pub struct GlobalProjectConfig {
     /// JSON schema URI.
     #[serde(skip, rename = "$schema")]
     pub schema: String,
+
+    /// File name the config was loaded from, as it may be in any format.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub file_name: Option<String>,
+
+    /// Configs scoped to a project language and type, loaded from
+    /// `.moon/tasks/*.{yml,json,toml}`. Keyed by file name without the extension.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub scoped_configs: BTreeMap<String, GlobalProjectConfig>,
 }
 
 impl GlobalProjectConfig {
-    pub fn load(path: PathBuf) -> Result<GlobalProjectConfig, Vec<FigmentError>> {
+    /// Return all configs that a project with the provided language and type
+    /// inherits from, paired with their relative file path, in the order
+    /// they should be merged: `.moon/project.yml`, `.moon/tasks/<language>.yml`,
+    /// and then `.moon/tasks/<language>-<type>.yml`.
+    pub fn get_inherited_configs(
+        &self,
+        language: &ProjectLanguage,
+        type_of: &ProjectType,
+    ) -> Vec<(String, &GlobalProjectConfig)> {
+        let mut configs = vec![(
+            format!(
+                "{}/{}",
+                constants::CONFIG_DIRNAME,
+                self.file_name
+                    .as_deref()
+                    .unwrap_or(constants::CONFIG_GLOBAL_PROJECT_FILENAME)
+            ),
+            self,
+        )];
+
+        for scope in [language.to_string(), format!("{}-{}", language, type_of)] {
+            if let Some(config) = self.scoped_configs.get(&scope) {
+                configs.push((
+                    format!(
+                        "{}/{}/{}",
+                        constants::CONFIG_DIRNAME,
+                        constants::CONFIG_GLOBAL_TASKS_DIRNAME,
+                        config
+                            .file_name
+                            .clone()
+                            .unwrap_or_else(|| format!("{}.yml", scope))
+                    ),
+                    config,
+                ));
+            }
+        }
+
+        configs
+    }
+
+    pub fn load(
+        path: PathBuf,
+        extends_cache_ttl: u64,
+    ) -> Result<GlobalProjectConfig, Vec<FigmentError>> {
         let profile_name = "globalProject";
         let mut config = GlobalProjectConfig::load_config(
-            Figment::from(
-                Serialized::defaults(GlobalProjectConfig::default()).profile(&profile_name),
+            merge_config_file(
+                Figment::from(
+                    Serialized::defaults(GlobalProjectConfig::default()).profile(&profile_name),
+                ),
+                &path,
+                profile_name,
             )
-            .merge(Yaml::file(&path).profile(&profile_name))
             .select(&profile_name),
         )?;
 
@@ -78,16 +135,22 @@ impl GlobalProjectConfig {
         // and figment providers do not have access to the current config dataset,
         // so we need to double-load this config and extract in the correct order!
         if let Some(extends) = &config.extends {
-            let extended_config =
-                GlobalProjectConfig::load_config(if extends.starts_with("http") {
-                    Figment::from(Url::from(extends.to_owned()).profile(&profile_name))
-                        .select(&profile_name)
-                } else {
-                    Figment::from(
-                        Yaml::file(path.parent().unwrap().join(extends)).profile(&profile_name),
-                    )
-                    .select(&profile_name)
-                })?;
+            let extended_config = GlobalProjectConfig::load_config(if is_url(extends) {
+                Figment::from(
+                    Url::from(extends.to_owned())
+                        .cache_dir(find_cache_dir(path.parent().unwrap()))
+                        .profile(&profile_name)
+                        .ttl(extends_cache_ttl),
+                )
+                .select(&profile_name)
+            } else {
+                merge_config_file(
+                    Figment::new(),
+                    path.parent().unwrap().join(extends),
+                    profile_name,
+                )
+                .select(&profile_name)
+            })?;
 
             // Figment does not merge hash maps but replaces entirely,
             // so we need to manually handle this here!
@@ -125,13 +188,17 @@ impl GlobalProjectConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::EXTENDS_CACHE_TTL;
     use figment;
     use moon_constants as constants;
     use moon_utils::string_vec;
     use std::path::Path;
 
     fn load_jailed_config(root: &Path) -> Result<GlobalProjectConfig, figment::Error> {
-        match GlobalProjectConfig::load(root.join(constants::CONFIG_GLOBAL_PROJECT_FILENAME)) {
+        match GlobalProjectConfig::load(
+            root.join(constants::CONFIG_GLOBAL_PROJECT_FILENAME),
+            EXTENDS_CACHE_TTL,
+        ) {
             Ok(cfg) => Ok(cfg),
             Err(errors) => Err(errors.first().unwrap().clone()),
         }
@@ -160,6 +227,8 @@ fileGroups:
                     )]),
                     tasks: BTreeMap::new(),
                     schema: String::new(),
+                    file_name: None,
+                    scoped_configs: BTreeMap::new(),
                 }
             );
 
@@ -224,7 +293,9 @@ fileGroups:
         }
 
         #[test]
-        #[should_panic(expected = "Must be a YAML document for key \"globalProject.extends\"")]
+        #[should_panic(
+            expected = "Must be a YAML, JSON, or TOML document for key \"globalProject.extends\""
+        )]
         fn not_a_yaml_url() {
             figment::Jail::expect_with(|jail| {
                 jail.create_file(
@@ -239,7 +310,9 @@ fileGroups:
         }
 
         #[test]
-        #[should_panic(expected = "Must be a YAML document for key \"globalProject.extends\"")]
+        #[should_panic(
+            expected = "Must be a YAML, JSON, or TOML document for key \"globalProject.extends\""
+        )]
         fn not_a_yaml_file() {
             figment::Jail::expect_with(|jail| {
                 fs::create_dir_all(jail.directory().join("shared")).unwrap();
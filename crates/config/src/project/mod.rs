@@ -5,19 +5,24 @@ pub mod global;
 pub mod task;
 
 use crate::errors::{create_validation_error, map_validation_errors_to_figment_errors};
+use crate::format::merge_config_file;
+use crate::providers::url::{find_cache_dir, is_url, Url};
 use crate::types::{FileGroups, ProjectID, TaskID};
 use crate::validators::{
-    skip_if_btree_empty, skip_if_default, skip_if_hash_empty, skip_if_vec_empty, validate_id,
+    skip_if_btree_empty, skip_if_default, skip_if_hash_empty, skip_if_vec_empty, validate_extends,
+    validate_id,
 };
 use dep::DependencyConfig;
-use figment::{
-    providers::{Format, Serialized, Yaml},
-    Error as FigmentError, Figment,
-};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use figment::{providers::Serialized, Error as FigmentError, Figment};
+use moon_lang_deno::deno;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::{schema_for, JsonSchema};
+use serde::de::{self, SeqAccess};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::{BTreeMap, HashMap};
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use strum::Display;
 use task::TaskConfig;
 use validator::{Validate, ValidationError};
@@ -49,6 +54,49 @@ fn validate_tasks(map: &BTreeMap<String, TaskConfig>) -> Result<(), ValidationEr
     Ok(())
 }
 
+fn validate_tags(list: &[String]) -> Result<(), ValidationError> {
+    for (index, tag) in list.iter().enumerate() {
+        validate_id(&format!("tags[{}]", index), tag)?;
+    }
+
+    Ok(())
+}
+
+fn validate_extends_list(list: &[String]) -> Result<(), ValidationError> {
+    for item in list {
+        validate_extends(item)?;
+    }
+
+    Ok(())
+}
+
+// Resolve an extends value relative to the config that it was defined in.
+// Local paths are canonicalized so that cycles can be detected reliably.
+fn resolve_extends(source: &str, extends: &str) -> String {
+    if is_url(extends) {
+        return extends.to_owned();
+    }
+
+    if is_url(source) {
+        let base = source
+            .rsplit_once('/')
+            .map(|(base, _)| base)
+            .unwrap_or(source);
+
+        return format!("{}/{}", base, extends.trim_start_matches("./"));
+    }
+
+    let path = Path::new(source)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(extends);
+
+    path.canonicalize()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
 fn validate_channel(value: &str) -> Result<(), ValidationError> {
     if !value.is_empty() && !value.starts_with('#') {
         return Err(create_validation_error(
@@ -70,9 +118,15 @@ pub enum ProjectLanguage {
     #[strum(serialize = "batch")]
     Batch,
 
+    #[strum(serialize = "deno")]
+    Deno,
+
     #[strum(serialize = "javascript")]
     JavaScript,
 
+    #[strum(serialize = "python")]
+    Python,
+
     #[strum(serialize = "typescript")]
     TypeScript,
 
@@ -150,6 +204,14 @@ pub struct ProjectConfig {
     #[serde(skip_serializing_if = "skip_if_vec_empty")]
     pub depends_on: Vec<ProjectDependsOn>,
 
+    #[serde(
+        deserialize_with = "deserialize_extends",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(schema_with = "make_extends_schema")]
+    #[validate(custom = "validate_extends_list")]
+    pub extends: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "skip_if_hash_empty")]
     #[validate(custom = "validate_file_groups")]
     pub file_groups: FileGroups,
@@ -161,6 +223,10 @@ pub struct ProjectConfig {
     #[validate]
     pub project: Option<ProjectMetadataConfig>,
 
+    #[serde(skip_serializing_if = "skip_if_vec_empty")]
+    #[validate(custom = "validate_tags")]
+    pub tags: Vec<String>,
+
     #[serde(skip_serializing_if = "skip_if_btree_empty")]
     #[validate(custom = "validate_tasks")]
     #[validate]
@@ -179,39 +245,181 @@ pub struct ProjectConfig {
     pub schema: String,
 }
 
+const PYTHON_MANIFESTS: [&str; 3] = ["pyproject.toml", "requirements.txt", "setup.py"];
+
 impl ProjectConfig {
     pub fn detect_language<T: AsRef<Path>>(root: T) -> ProjectLanguage {
         let root = root.as_ref();
 
-        if root.join("tsconfig.json").exists() {
+        // Deno projects may also contain a `tsconfig.json`, however, projects
+        // with a `package.json` are Node.js projects that also configure Deno
+        if deno::find_config_file(root).is_some() && !root.join("package.json").exists() {
+            ProjectLanguage::Deno
+        } else if root.join("tsconfig.json").exists() {
             ProjectLanguage::TypeScript
         } else if root.join("package.json").exists() {
             ProjectLanguage::JavaScript
+        } else if PYTHON_MANIFESTS.iter().any(|name| root.join(name).exists()) {
+            ProjectLanguage::Python
         } else {
             ProjectLanguage::Unknown
         }
     }
 
     #[track_caller]
-    pub fn load<T: AsRef<Path>>(path: T) -> Result<ProjectConfig, Vec<FigmentError>> {
+    pub fn load<T: AsRef<Path>>(
+        path: T,
+        extends_cache_ttl: u64,
+    ) -> Result<ProjectConfig, Vec<FigmentError>> {
         let path = path.as_ref();
-        let profile_name = "project";
-        let figment =
-            Figment::from(Serialized::defaults(ProjectConfig::default()).profile(&profile_name))
-                .merge(Yaml::file(path).profile(&profile_name))
-                .select(&profile_name);
+        let source = path
+            .canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf())
+            .to_string_lossy()
+            .to_string();
+
+        let cache_dir = find_cache_dir(path.parent().unwrap_or(path));
+        let mut config =
+            ProjectConfig::load_with_extends(&source, &mut vec![], &cache_dir, extends_cache_ttl)?;
+
+        if matches!(config.language, ProjectLanguage::Unknown) {
+            config.language = ProjectConfig::detect_language(path.parent().unwrap());
+        }
+
+        Ok(config)
+    }
+
+    /// Deep merge another project config into this one. Tasks are merged
+    /// using the same strategies as global task inheritance.
+    pub fn merge(&mut self, other: ProjectConfig) {
+        for dep in other.depends_on {
+            if !self.depends_on.contains(&dep) {
+                self.depends_on.push(dep);
+            }
+        }
+
+        self.extends = other.extends;
+        self.file_groups.extend(other.file_groups);
+
+        if !matches!(other.language, ProjectLanguage::Unknown) {
+            self.language = other.language;
+        }
+
+        if other.project.is_some() {
+            self.project = other.project;
+        }
+
+        for tag in other.tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+
+        for (id, task) in other.tasks {
+            match self.tasks.get_mut(&id) {
+                Some(base_task) => base_task.merge(&task),
+                None => {
+                    self.tasks.insert(id, task);
+                }
+            };
+        }
+
+        if !matches!(other.type_of, ProjectType::Unknown) {
+            self.type_of = other.type_of;
+        }
+
+        let inherited_tasks = other.workspace.inherited_tasks;
+
+        if inherited_tasks.exclude.is_some() {
+            self.workspace.inherited_tasks.exclude = inherited_tasks.exclude;
+        }
+
+        if inherited_tasks.include.is_some() {
+            self.workspace.inherited_tasks.include = inherited_tasks.include;
+        }
+
+        if inherited_tasks.rename.is_some() {
+            self.workspace.inherited_tasks.rename = inherited_tasks.rename;
+        }
+    }
 
-        let mut config: ProjectConfig = figment.extract().map_err(|e| vec![e])?;
+    fn load_config(figment: Figment) -> Result<ProjectConfig, Vec<FigmentError>> {
+        let config: ProjectConfig = figment.extract().map_err(|e| vec![e])?;
 
         if let Err(errors) = config.validate() {
             return Err(map_validation_errors_to_figment_errors(&figment, &errors));
         }
 
-        if matches!(config.language, ProjectLanguage::Unknown) {
-            config.language = ProjectConfig::detect_language(path.parent().unwrap());
+        Ok(config)
+    }
+
+    // Figment does not support an extends mechanism, so we load each extended
+    // config in order, merge them together, and then merge the current config last.
+    fn load_with_extends(
+        source: &str,
+        chain: &mut Vec<String>,
+        cache_dir: &Option<PathBuf>,
+        extends_cache_ttl: u64,
+    ) -> Result<ProjectConfig, Vec<FigmentError>> {
+        let profile_name = "project";
+        let mut figment =
+            Figment::from(Serialized::defaults(ProjectConfig::default()).profile(&profile_name));
+
+        if is_url(source) {
+            figment = figment.merge(
+                Url::from(source.to_owned())
+                    .cache_dir(cache_dir.clone())
+                    .profile(&profile_name)
+                    .ttl(extends_cache_ttl),
+            );
+        } else {
+            figment = merge_config_file(figment, source, profile_name);
         }
 
-        Ok(config)
+        let config = ProjectConfig::load_config(figment.select(&profile_name))?;
+
+        let extends_list = match &config.extends {
+            Some(list) if !list.is_empty() => list.clone(),
+            _ => return Ok(config),
+        };
+
+        chain.push(source.to_owned());
+
+        let mut extended_config = ProjectConfig::default();
+
+        for extends in extends_list {
+            let extended_source = resolve_extends(source, &extends);
+
+            if chain.contains(&extended_source) {
+                let mut cycle = chain.clone();
+                cycle.push(extended_source);
+
+                return Err(vec![FigmentError::from(format!(
+                    "Project config extends cycle detected: {}",
+                    cycle.join(" -> ")
+                ))]);
+            }
+
+            if !is_url(&extended_source) && !PathBuf::from(&extended_source).exists() {
+                return Err(vec![FigmentError::from(format!(
+                    "Extended project config <path>{}</path> does not exist.",
+                    extended_source
+                ))]);
+            }
+
+            extended_config.merge(ProjectConfig::load_with_extends(
+                &extended_source,
+                chain,
+                cache_dir,
+                extends_cache_ttl,
+            )?);
+        }
+
+        chain.pop();
+
+        extended_config.merge(config);
+
+        Ok(extended_config)
     }
 
     pub fn new<T: AsRef<Path>>(root: T) -> Self {
@@ -222,16 +430,76 @@ impl ProjectConfig {
     }
 }
 
+// SERDE
+
+struct DeserializeExtends;
+
+impl<'de> de::Visitor<'de> for DeserializeExtends {
+    type Value = Vec<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of strings or a string")
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut vec = Vec::new();
+
+        while let Some(elem) = visitor.next_element()? {
+            vec.push(elem);
+        }
+
+        Ok(vec)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(vec![value.to_owned()])
+    }
+}
+
+fn deserialize_extends<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Some(deserializer.deserialize_any(DeserializeExtends)?))
+}
+
+// JSON SCHEMA
+
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum ExtendsField {
+    #[allow(dead_code)]
+    String(String),
+    #[allow(dead_code)]
+    Sequence(Vec<String>),
+}
+
+fn make_extends_schema(_gen: &mut SchemaGenerator) -> Schema {
+    let root = schema_for!(ExtendsField);
+
+    Schema::Object(root.schema)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::EXTENDS_CACHE_TTL;
     use crate::project::dep::DependencyScope;
     use moon_constants as constants;
     use moon_utils::string_vec;
     use std::path::PathBuf;
 
     fn load_jailed_config() -> Result<ProjectConfig, figment::Error> {
-        match ProjectConfig::load(&PathBuf::from(constants::CONFIG_PROJECT_FILENAME)) {
+        match ProjectConfig::load(
+            &PathBuf::from(constants::CONFIG_PROJECT_FILENAME),
+            EXTENDS_CACHE_TTL,
+        ) {
             Ok(cfg) => Ok(cfg),
             Err(errors) => Err(errors.first().unwrap().clone()),
         }
@@ -277,6 +545,130 @@ fileGroups:
         });
     }
 
+    mod formats {
+        use super::*;
+
+        #[test]
+        fn loads_json() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "moon.json",
+                    r#"{ "fileGroups": { "sources": ["src/**/*"] }, "tasks": { "build": { "command": "webpack" } } }"#,
+                )?;
+
+                let config = ProjectConfig::load(&PathBuf::from("moon.json"), EXTENDS_CACHE_TTL)
+                    .map_err(|e| e.first().unwrap().clone())?;
+
+                assert_eq!(
+                    config.file_groups,
+                    HashMap::from([(String::from("sources"), string_vec!["src/**/*"])])
+                );
+                assert_eq!(
+                    config.tasks.get("build").unwrap().command,
+                    Some("webpack".to_owned())
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn loads_toml() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "moon.toml",
+                    r#"
+[fileGroups]
+sources = ["src/**/*"]
+
+[tasks.build]
+command = "webpack"
+"#,
+                )?;
+
+                let config = ProjectConfig::load(&PathBuf::from("moon.toml"), EXTENDS_CACHE_TTL)
+                    .map_err(|e| e.first().unwrap().clone())?;
+
+                assert_eq!(
+                    config.file_groups,
+                    HashMap::from([(String::from("sources"), string_vec!["src/**/*"])])
+                );
+                assert_eq!(
+                    config.tasks.get("build").unwrap().command,
+                    Some("webpack".to_owned())
+                );
+
+                Ok(())
+            });
+        }
+    }
+
+    mod detect_language {
+        use super::*;
+
+        #[test]
+        fn detects_from_files() {
+            figment::Jail::expect_with(|jail| {
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::Unknown
+                );
+
+                jail.create_file("requirements.txt", "")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::Python
+                );
+
+                jail.create_file("package.json", "{}")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::JavaScript
+                );
+
+                jail.create_file("tsconfig.json", "{}")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::TypeScript
+                );
+
+                jail.create_file("deno.jsonc", "{}")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::TypeScript
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn detects_deno_without_package_json() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file("tsconfig.json", "{}")?;
+                jail.create_file("deno.json", "{}")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::Deno
+                );
+
+                jail.create_file("package.json", "{}")?;
+
+                assert_eq!(
+                    ProjectConfig::detect_language(jail.directory()),
+                    ProjectLanguage::TypeScript
+                );
+
+                Ok(())
+            });
+        }
+    }
+
     mod depends_on {
         use super::*;
 
@@ -397,6 +789,236 @@ fileGroups:
         }
     }
 
+    mod extends {
+        use super::*;
+        use crate::project::task::{TaskConfig, TaskMergeStrategy, TaskOptionsConfig};
+
+        #[test]
+        fn merges_a_single_file() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "shared.yml",
+                    r#"
+type: library
+fileGroups:
+    sources:
+        - src/**/*
+tasks:
+    lint:
+        command: eslint
+        args: --cache
+    test:
+        command: jest"#,
+                )?;
+
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    r#"
+extends: ./shared.yml
+tasks:
+    lint:
+        args: --fix"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(config.extends, Some(string_vec!["./shared.yml"]));
+                assert_eq!(config.type_of, ProjectType::Library);
+                assert_eq!(
+                    config.file_groups,
+                    HashMap::from([("sources".to_owned(), string_vec!["src/**/*"])])
+                );
+                assert_eq!(
+                    config.tasks.get("lint").unwrap(),
+                    &TaskConfig {
+                        command: Some("eslint".to_owned()),
+                        args: Some(string_vec!["--cache", "--fix"]),
+                        ..TaskConfig::default()
+                    }
+                );
+                assert!(config.tasks.contains_key("test"));
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn merges_a_list_in_order() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "one.yml",
+                    r#"
+type: library
+tasks:
+    build:
+        command: tsc
+        env:
+            NODE_ENV: development"#,
+                )?;
+
+                jail.create_file(
+                    "two.yml",
+                    r#"
+type: application
+tasks:
+    build:
+        env:
+            NODE_ENV: production"#,
+                )?;
+
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ['./one.yml', './two.yml']",
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(config.type_of, ProjectType::Application);
+                assert_eq!(
+                    config.tasks.get("build").unwrap().env,
+                    Some(HashMap::from([(
+                        "NODE_ENV".to_owned(),
+                        "production".to_owned()
+                    )]))
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn respects_merge_strategies() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "shared.yml",
+                    r#"
+tasks:
+    test:
+        command: jest
+        args: --coverage
+        inputs:
+            - src/**/*"#,
+                )?;
+
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    r#"
+extends: ./shared.yml
+tasks:
+    test:
+        args: --ci
+        inputs:
+            - tests/**/*
+        options:
+            mergeArgs: replace
+            mergeInputs: prepend"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.tasks.get("test").unwrap(),
+                    &TaskConfig {
+                        command: Some("jest".to_owned()),
+                        args: Some(string_vec!["--ci"]),
+                        inputs: Some(string_vec!["tests/**/*", "src/**/*"]),
+                        options: TaskOptionsConfig {
+                            merge_args: Some(TaskMergeStrategy::Replace),
+                            merge_inputs: Some(TaskMergeStrategy::Prepend),
+                            ..TaskOptionsConfig::default()
+                        },
+                        ..TaskConfig::default()
+                    }
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn supports_nested_extends() {
+            figment::Jail::expect_with(|jail| {
+                std::fs::create_dir_all(jail.directory().join("presets")).unwrap();
+                jail.create_file("presets/base.yml", "type: tool")?;
+                jail.create_file("presets/lib.yml", "extends: ./base.yml")?;
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ./presets/lib.yml",
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(config.type_of, ProjectType::Tool);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Project config extends cycle detected")]
+        fn detects_cycles() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file("one.yml", "extends: ./two.yml")?;
+                jail.create_file("two.yml", "extends: ./one.yml")?;
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ./one.yml",
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Project config extends cycle detected")]
+        fn detects_self_references() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ./moon.yml",
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "does not exist")]
+        fn errors_for_missing_file() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ./missing.yml",
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a valid URL or relative file path (starts with ./) for key \"project.extends\""
+        )]
+        fn invalid_format() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "extends: ['shared.yml']",
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+    }
+
     mod file_groups {
         #[test]
         #[should_panic(
@@ -432,6 +1054,55 @@ fileGroups:
         }
     }
 
+    mod tags {
+        use super::*;
+
+        #[test]
+        #[should_panic(
+            expected = "invalid type: found unsigned int `123`, expected a sequence for key \"project.tags\""
+        )]
+        fn invalid_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(super::constants::CONFIG_PROJECT_FILENAME, "tags: 123")?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Must be a valid ID")]
+        fn invalid_tag_format() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "tags: ['billing', '@internal']",
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn supports_list_of_strings() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_PROJECT_FILENAME,
+                    "tags: ['billing', 'shared']",
+                )?;
+
+                let cfg: ProjectConfig = super::load_jailed_config()?;
+
+                assert_eq!(cfg.tags, string_vec!["billing", "shared"]);
+
+                Ok(())
+            });
+        }
+    }
+
     mod tasks {
         use super::*;
 
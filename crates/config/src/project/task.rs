@@ -1,24 +1,36 @@
+use crate::errors::create_validation_error;
 use crate::project::{ProjectConfig, ProjectLanguage};
 use crate::types::{FilePath, InputValue, TargetID};
 use crate::validators::{skip_if_default, validate_child_or_root_path, validate_target};
-use moon_utils::process::split_args;
-use moon_utils::regex::{ENV_VAR, NODE_COMMAND, UNIX_SYSTEM_COMMAND, WINDOWS_SYSTEM_COMMAND};
+use moon_utils::process::{get_default_shell, split_args};
+use moon_utils::regex::{
+    BUN_COMMAND, DENO_COMMAND, ENV_VAR, NODE_COMMAND, PYTHON_COMMAND, UNIX_SYSTEM_COMMAND,
+    WINDOWS_SYSTEM_COMMAND,
+};
 use schemars::gen::SchemaGenerator;
 use schemars::schema::Schema;
 use schemars::{schema_for, JsonSchema};
 use serde::de::{self, SeqAccess};
 use serde::{Deserialize, Deserializer, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::fmt;
+use std::fmt::{self, Write};
 use strum::Display;
 use validator::{Validate, ValidationError};
 
 // These structs utilize optional fields so that we can handle merging effectively,
 // as we need a way to skip "undefined" values. So don't use serde defaults here.
 
-fn validate_deps(list: &[String]) -> Result<(), ValidationError> {
+fn validate_deps(list: &[TaskDependency]) -> Result<(), ValidationError> {
     for (index, item) in list.iter().enumerate() {
-        validate_target(&format!("deps[{}]", index), item)?;
+        match item {
+            TaskDependency::String(target) => {
+                validate_target(&format!("deps[{}]", index), target)?;
+            }
+            TaskDependency::Object(config) => {
+                validate_target(&format!("deps[{}].target", index), &config.target)?;
+            }
+        };
     }
 
     Ok(())
@@ -42,12 +54,133 @@ fn validate_outputs(list: &[String]) -> Result<(), ValidationError> {
     Ok(())
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependencyConfig {
+    pub target: TargetID,
+
+    #[serde(
+        default,
+        deserialize_with = "deserialize_dep_args",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(schema_with = "make_args_schema")]
+    pub args: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+}
+
+impl TaskDependencyConfig {
+    pub fn new(target: &str) -> Self {
+        TaskDependencyConfig {
+            target: target.to_owned(),
+            ..TaskDependencyConfig::default()
+        }
+    }
+
+    /// Return true if the dependency passes its own args or env vars,
+    /// and must be ran separately from the base target.
+    pub fn has_overrides(&self) -> bool {
+        !self.args.is_empty() || !self.env.is_empty()
+    }
+
+    /// Return a hash of the args and env vars that uniquely identifies this
+    /// variant of the target, or none if there are no overrides.
+    pub fn get_variant_hash(&self) -> Option<String> {
+        if !self.has_overrides() {
+            return None;
+        }
+
+        let mut env = self.env.iter().collect::<Vec<_>>();
+        env.sort();
+
+        let mut sha = Sha256::new();
+
+        // Prefix each section so that args and env vars can not collide
+        sha.update(b"args\0");
+
+        for arg in &self.args {
+            sha.update(arg.as_bytes());
+            sha.update(b"\0");
+        }
+
+        sha.update(b"env\0");
+
+        for (key, value) in env {
+            sha.update(key.as_bytes());
+            sha.update(b"=");
+            sha.update(value.as_bytes());
+            sha.update(b"\0");
+        }
+
+        Some(format!("{:x}", sha.finalize()))
+    }
+
+    /// Return a label that uniquely identifies the target and its overrides.
+    pub fn label(&self) -> String {
+        let mut label = self.target.clone();
+
+        if !self.args.is_empty() {
+            let _ = write!(label, " [args: {}]", self.args.join(" "));
+        }
+
+        if !self.env.is_empty() {
+            let mut env = self
+                .env
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>();
+            env.sort();
+
+            let _ = write!(label, " [env: {}]", env.join(" "));
+        }
+
+        label
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TaskDependency {
+    String(TargetID),
+    Object(TaskDependencyConfig),
+}
+
+impl TaskDependency {
+    pub fn to_config(&self) -> TaskDependencyConfig {
+        match self {
+            TaskDependency::String(target) => TaskDependencyConfig::new(target),
+            TaskDependency::Object(config) => config.clone(),
+        }
+    }
+}
+
+impl From<TaskDependencyConfig> for TaskDependency {
+    fn from(config: TaskDependencyConfig) -> Self {
+        if config.has_overrides() {
+            TaskDependency::Object(config)
+        } else {
+            TaskDependency::String(config.target)
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Display, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PlatformType {
+    #[strum(serialize = "bun")]
+    Bun,
+
+    #[strum(serialize = "deno")]
+    Deno,
+
     #[strum(serialize = "node")]
     Node,
 
+    #[strum(serialize = "python")]
+    Python,
+
     #[strum(serialize = "system")]
     System,
 
@@ -65,6 +198,38 @@ pub enum TaskMergeStrategy {
     Replace,
 }
 
+impl TaskMergeStrategy {
+    /// Merge 2 lists based on the strategy. Shared by task configs and tasks.
+    pub fn merge_vec<T: Clone>(&self, base: &[T], next: &[T]) -> Vec<T> {
+        match self {
+            TaskMergeStrategy::Append => [base, next].concat(),
+            TaskMergeStrategy::Prepend => [next, base].concat(),
+            TaskMergeStrategy::Replace => next.to_vec(),
+        }
+    }
+
+    /// Merge 2 maps based on the strategy, where the last map wins on conflicts.
+    pub fn merge_map(
+        &self,
+        base: &HashMap<String, String>,
+        next: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        match self {
+            TaskMergeStrategy::Append => {
+                let mut map = base.clone();
+                map.extend(next.clone());
+                map
+            }
+            TaskMergeStrategy::Prepend => {
+                let mut map = next.clone();
+                map.extend(base.clone());
+                map
+            }
+            TaskMergeStrategy::Replace => next.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum TaskOutputStyle {
@@ -72,12 +237,72 @@ pub enum TaskOutputStyle {
     Stream,
 }
 
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TaskOptionEnvFile {
+    Enabled(bool),
+    File(String),
+}
+
+impl TaskOptionEnvFile {
+    pub fn to_option(&self) -> Option<String> {
+        match self {
+            TaskOptionEnvFile::Enabled(true) => Some(".env".to_owned()),
+            TaskOptionEnvFile::Enabled(false) => None,
+            TaskOptionEnvFile::File(path) => Some(path.to_owned()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TaskOptionShell {
+    Enabled(bool),
+    Shell(String),
+}
+
+impl TaskOptionShell {
+    pub fn to_option(&self) -> Option<String> {
+        match self {
+            TaskOptionShell::Enabled(true) => Some(get_default_shell()),
+            TaskOptionShell::Enabled(false) => None,
+            TaskOptionShell::Shell(shell) => Some(shell.to_owned()),
+        }
+    }
+}
+
+fn validate_env_file(file: &TaskOptionEnvFile) -> Result<(), ValidationError> {
+    if let TaskOptionEnvFile::File(path) = file {
+        validate_child_or_root_path("options.envFile", path)?;
+    }
+
+    Ok(())
+}
+
+fn validate_shell(shell: &TaskOptionShell) -> Result<(), ValidationError> {
+    if let TaskOptionShell::Shell(name) = shell {
+        if name.trim().is_empty() {
+            return Err(create_validation_error(
+                "invalid_shell",
+                "options.shell",
+                String::from("A shell name or path is required"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
 #[serde(default, rename_all = "camelCase")]
 pub struct TaskOptionsConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_env_file")]
+    pub env_file: Option<TaskOptionEnvFile>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub merge_args: Option<TaskMergeStrategy>,
 
@@ -107,6 +332,69 @@ pub struct TaskOptionsConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub run_from_workspace_root: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_shell")]
+    pub shell: Option<TaskOptionShell>,
+}
+
+impl TaskOptionsConfig {
+    /// Merge another options config into this one. Only defined values are merged.
+    pub fn merge(&mut self, other: &TaskOptionsConfig) {
+        macro_rules! merge_option {
+            ($($field:ident),+) => {
+                $(
+                    if other.$field.is_some() {
+                        self.$field = other.$field.clone();
+                    }
+                )+
+            };
+        }
+
+        merge_option!(
+            cache,
+            env_file,
+            merge_args,
+            merge_deps,
+            merge_env,
+            merge_inputs,
+            merge_outputs,
+            output_style,
+            retry_count,
+            run_deps_in_parallel,
+            run_in_ci,
+            run_from_workspace_root,
+            shell
+        );
+    }
+}
+
+fn merge_list<T: Clone>(
+    base: &Option<Vec<T>>,
+    next: &Option<Vec<T>>,
+    strategy: &Option<TaskMergeStrategy>,
+) -> Option<Vec<T>> {
+    match (base, next) {
+        (Some(base), Some(next)) => {
+            Some(strategy.clone().unwrap_or_default().merge_vec(base, next))
+        }
+        (None, Some(next)) => Some(next.clone()),
+        (base, None) => base.clone(),
+    }
+}
+
+fn merge_map(
+    base: &Option<HashMap<String, String>>,
+    next: &Option<HashMap<String, String>>,
+    strategy: &Option<TaskMergeStrategy>,
+) -> Option<HashMap<String, String>> {
+    match (base, next) {
+        (Some(base), Some(next)) => {
+            Some(strategy.clone().unwrap_or_default().merge_map(base, next))
+        }
+        (None, Some(next)) => Some(next.clone()),
+        (base, None) => base.clone(),
+    }
 }
 
 // We use serde(default) here because figment *does not* apply defaults
@@ -126,7 +414,7 @@ pub struct TaskConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_deps")]
-    pub deps: Option<Vec<TargetID>>,
+    pub deps: Option<Vec<TaskDependency>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
@@ -149,21 +437,67 @@ pub struct TaskConfig {
 }
 
 impl TaskConfig {
-    pub fn detect_platform(project: &ProjectConfig, command: &str) -> PlatformType {
+    /// Detect the platform from the command. Tasks that run through
+    /// a shell are always considered system tasks.
+    pub fn detect_platform(project: &ProjectConfig, command: &str, shell: bool) -> PlatformType {
+        if shell {
+            return PlatformType::System;
+        }
+
+        if BUN_COMMAND.is_match(command) {
+            return PlatformType::Bun;
+        }
+
+        if DENO_COMMAND.is_match(command) {
+            return PlatformType::Deno;
+        }
+
         if NODE_COMMAND.is_match(command) {
             return PlatformType::Node;
         }
 
+        if PYTHON_COMMAND.is_match(command) {
+            return PlatformType::Python;
+        }
+
         if UNIX_SYSTEM_COMMAND.is_match(command) || WINDOWS_SYSTEM_COMMAND.is_match(command) {
             return PlatformType::System;
         }
 
         match &project.language {
             ProjectLanguage::JavaScript | ProjectLanguage::TypeScript => PlatformType::Node,
-            ProjectLanguage::Bash | ProjectLanguage::Batch => PlatformType::System,
+            // Only `deno` commands or tasks with an explicit `type` run through Deno,
+            // as other commands (bundlers, scripts, etc) are not Deno modules
+            ProjectLanguage::Bash | ProjectLanguage::Batch | ProjectLanguage::Deno => {
+                PlatformType::System
+            }
+            ProjectLanguage::Python => PlatformType::Python,
             _ => PlatformType::Unknown,
         }
     }
+
+    /// Deep merge another task config into this one, using the merge strategies
+    /// defined in the options. This mirrors how tasks are inherited.
+    pub fn merge(&mut self, other: &TaskConfig) {
+        // Merge options first incase the merge strategy has changed
+        self.options.merge(&other.options);
+
+        let options = &self.options;
+
+        if other.command.is_some() {
+            self.command = other.command.clone();
+        }
+
+        self.args = merge_list(&self.args, &other.args, &options.merge_args);
+        self.deps = merge_list(&self.deps, &other.deps, &options.merge_deps);
+        self.env = merge_map(&self.env, &other.env, &options.merge_env);
+        self.inputs = merge_list(&self.inputs, &other.inputs, &options.merge_inputs);
+        self.outputs = merge_list(&self.outputs, &other.outputs, &options.merge_outputs);
+
+        if !matches!(other.type_of, PlatformType::Unknown) {
+            self.type_of = other.type_of.clone();
+        }
+    }
 }
 
 // SERDE
@@ -208,6 +542,13 @@ where
     Ok(Some(deserializer.deserialize_any(DeserializeArgs)?))
 }
 
+fn deserialize_dep_args<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(DeserializeArgs)
+}
+
 // JSON SCHEMA
 
 #[derive(JsonSchema)]
@@ -400,7 +741,7 @@ deps: abc
 
         #[test]
         #[should_panic(
-            expected = "invalid type: found unsigned int `123`, expected a string for key \"default.deps.0\""
+            expected = "data did not match any variant of untagged enum TaskDependency for key \"default.deps.0\""
         )]
         fn invalid_value_type() {
             figment::Jail::expect_with(|jail| {
@@ -439,6 +780,127 @@ deps:
         //                 Ok(())
         //             });
         //         }
+
+        #[test]
+        fn supports_strings_and_objects() {
+            use super::*;
+            use moon_utils::string_vec;
+
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+deps:
+    - shared:build
+    - target: shared:build
+      args: --mode production
+      env:
+        NODE_ENV: production
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.deps,
+                    Some(vec![
+                        TaskDependency::String("shared:build".to_owned()),
+                        TaskDependency::Object(TaskDependencyConfig {
+                            target: "shared:build".to_owned(),
+                            args: string_vec!["--mode", "production"],
+                            env: HashMap::from([("NODE_ENV".to_owned(), "production".to_owned())]),
+                        }),
+                    ])
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "data did not match any variant of untagged enum TaskDependency for key \"default.deps.0\""
+        )]
+        fn requires_object_target() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+deps:
+    - args: --mode production
+"#,
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn labels_overrides() {
+            use super::*;
+            use moon_utils::string_vec;
+
+            let config = TaskDependencyConfig {
+                target: "shared:build".to_owned(),
+                args: string_vec!["--mode", "production"],
+                env: HashMap::from([
+                    ("B".to_owned(), "2".to_owned()),
+                    ("A".to_owned(), "1".to_owned()),
+                ]),
+            };
+
+            assert_eq!(
+                config.label(),
+                "shared:build [args: --mode production] [env: A=1 B=2]"
+            );
+            assert_eq!(
+                TaskDependencyConfig::new("shared:build").label(),
+                "shared:build"
+            );
+        }
+
+        #[test]
+        fn hashes_overrides() {
+            use super::*;
+            use moon_utils::string_vec;
+
+            let config = TaskDependencyConfig {
+                target: "shared:build".to_owned(),
+                args: string_vec!["--mode", "production"],
+                env: HashMap::new(),
+            };
+            let joined_config = TaskDependencyConfig {
+                target: "shared:build".to_owned(),
+                args: string_vec!["--mode production"],
+                env: HashMap::new(),
+            };
+
+            let args_config = TaskDependencyConfig {
+                target: "shared:build".to_owned(),
+                args: string_vec!["A=1"],
+                env: HashMap::new(),
+            };
+            let env_config = TaskDependencyConfig {
+                target: "shared:build".to_owned(),
+                args: vec![],
+                env: HashMap::from([("A".to_owned(), "1".to_owned())]),
+            };
+
+            assert!(config.get_variant_hash().is_some());
+            assert_ne!(config.get_variant_hash(), joined_config.get_variant_hash());
+            assert_ne!(
+                args_config.get_variant_hash(),
+                env_config.get_variant_hash()
+            );
+            assert_eq!(
+                TaskDependencyConfig::new("shared:build").get_variant_hash(),
+                None
+            );
+        }
     }
 
     mod env {
@@ -593,7 +1055,7 @@ outputs:
     mod type_of {
         #[test]
         #[should_panic(
-            expected = "unknown variant: found `whatisthis`, expected `one of `node`, `system`, `unknown`` for key \"default.type\""
+            expected = "unknown variant: found `whatisthis`, expected `one of `bun`, `deno`, `node`, `python`, `system`, `unknown`` for key \"default.type\""
         )]
         fn invalid_type() {
             figment::Jail::expect_with(|jail| {
@@ -674,5 +1136,201 @@ options:
                 Ok(())
             });
         }
+
+        #[test]
+        #[should_panic(
+            expected = "data did not match any variant of untagged enum TaskOptionEnvFile"
+        )]
+        fn invalid_env_file_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    envFile: 123
+"#,
+                )?;
+
+                super::load_jailed_config()?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn supports_env_file_bool_and_path() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    envFile: true
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.options.env_file.unwrap().to_option(),
+                    Some(".env".to_owned())
+                );
+
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    envFile: /.env.shared
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.options.env_file.unwrap().to_option(),
+                    Some("/.env.shared".to_owned())
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn supports_shell_bool_and_name() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    shell: true
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.options.shell.unwrap().to_option(),
+                    Some(super::get_default_shell())
+                );
+
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    shell: bash
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                assert_eq!(
+                    config.options.shell.unwrap().to_option(),
+                    Some("bash".to_owned())
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "A shell name or path is required")]
+        fn invalid_empty_shell() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::CONFIG_FILENAME,
+                    r#"
+command: foo
+options:
+    shell: ''
+"#,
+                )?;
+
+                let config = super::load_jailed_config()?;
+
+                validator::Validate::validate(&config).unwrap();
+
+                Ok(())
+            });
+        }
+    }
+
+    mod detect_platform {
+        use super::*;
+
+        #[test]
+        fn detects_from_command() {
+            let project = ProjectConfig::default();
+
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "node", false),
+                PlatformType::Node
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "bun", false),
+                PlatformType::Bun
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "bunx", false),
+                PlatformType::Bun
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "deno", false),
+                PlatformType::Deno
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "python3", false),
+                PlatformType::Python
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "pip", false),
+                PlatformType::Python
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "git", false),
+                PlatformType::System
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "webpack", false),
+                PlatformType::Unknown
+            );
+        }
+
+        #[test]
+        fn detects_from_language() {
+            let project = ProjectConfig {
+                language: ProjectLanguage::Deno,
+                ..ProjectConfig::default()
+            };
+
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "webpack", false),
+                PlatformType::System
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "deno", false),
+                PlatformType::Deno
+            );
+        }
+
+        #[test]
+        fn shell_tasks_are_system() {
+            let project = ProjectConfig {
+                language: ProjectLanguage::JavaScript,
+                ..ProjectConfig::default()
+            };
+
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "node", true),
+                PlatformType::System
+            );
+            assert_eq!(
+                TaskConfig::detect_platform(&project, "webpack", true),
+                PlatformType::System
+            );
+        }
     }
 }
@@ -1,25 +1,259 @@
 // Based on https://docs.rs/figment/latest/figment/trait.Provider.html
 
+use crate::constants::EXTENDS_CACHE_TTL;
+use crate::format::ConfigFormat;
 use figment::{
-    providers::{Format, Yaml},
     value::{Dict, Map},
     Error, Metadata, Profile, Provider,
 };
+use moon_constants::CONFIG_DIRNAME;
+use moon_logger::{color, debug, warn};
+use moon_utils::fs::find_upwards;
+use reqwest::{blocking::Client, header, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const LOG_TARGET: &str = "moon:config:url";
+
+const REQUEST_TIMEOUT: u64 = 10;
+
+/// Return true if the value is a URL that can be loaded with the `Url` provider.
+pub fn is_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://") || is_file_url(value)
+}
+
+pub fn is_file_url(value: &str) -> bool {
+    value.starts_with("file://")
+}
+
+/// Locate the directory that fetched documents are cached in, by finding
+/// the `.moon` folder upwards from the provided path.
+pub fn find_cache_dir(path: &Path) -> Option<PathBuf> {
+    find_upwards(CONFIG_DIRNAME, path).map(|dir| dir.join("cache/extends"))
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheMeta {
+    etag: Option<String>,
+    fetched_at: u128,
+    last_modified: Option<String>,
+    url: String,
+}
+
+struct CachedDocument {
+    content: String,
+    meta: CacheMeta,
+}
 
 pub struct Url {
     url: String,
+    cache_dir: Option<PathBuf>,
     pub profile: Option<Profile>,
+    ttl: u64,
 }
 
 impl Url {
     pub fn from(url: String) -> Self {
-        Url { url, profile: None }
+        Url {
+            url,
+            cache_dir: None,
+            profile: None,
+            ttl: EXTENDS_CACHE_TTL,
+        }
+    }
+
+    pub fn cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
     }
 
     pub fn profile<P: Into<Profile>>(mut self, profile: P) -> Self {
         self.profile = Some(profile.into());
         self
     }
+
+    /// Amount of seconds a cached document is used before revalidating.
+    pub fn ttl(mut self, secs: u64) -> Self {
+        self.ttl = secs;
+        self
+    }
+
+    fn get_cache_paths(&self) -> Option<(PathBuf, PathBuf)> {
+        self.cache_dir.as_ref().map(|dir| {
+            let mut sha = Sha256::new();
+            sha.update(self.url.as_bytes());
+            let key = format!("{:x}", sha.finalize());
+
+            (
+                dir.join(format!(
+                    "{}.{}",
+                    key,
+                    ConfigFormat::from_path(&self.url).extension()
+                )),
+                dir.join(format!("{}.meta.json", key)),
+            )
+        })
+    }
+
+    fn read_cache(&self) -> Option<CachedDocument> {
+        let (content_path, meta_path) = self.get_cache_paths()?;
+        let content = std::fs::read_to_string(content_path).ok()?;
+        let meta = serde_json::from_str(&std::fs::read_to_string(meta_path).ok()?).ok()?;
+
+        Some(CachedDocument { content, meta })
+    }
+
+    fn write_cache(&self, content: Option<&str>, meta: &CacheMeta) {
+        let (content_path, meta_path) = match self.get_cache_paths() {
+            Some(paths) => paths,
+            None => return,
+        };
+
+        // Caching is an optimization, so never fail the load because of it
+        let result = std::fs::create_dir_all(content_path.parent().unwrap())
+            .and_then(|_| match content {
+                Some(content) => std::fs::write(&content_path, content),
+                None => Ok(()),
+            })
+            .and_then(|_| {
+                std::fs::write(&meta_path, serde_json::to_string(meta).unwrap_or_default())
+            });
+
+        if let Err(e) = result {
+            debug!(
+                target: LOG_TARGET,
+                "Failed to cache extended config {}: {}",
+                color::url(&self.url),
+                e
+            );
+        }
+    }
+
+    fn fetch(&self, cached: Option<&CacheMeta>) -> Result<Option<(String, CacheMeta)>, String> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let mut request = client.get(&self.url);
+
+        if let Some(meta) = cached {
+            if let Some(etag) = &meta.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+
+            if let Some(last_modified) = &meta.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let resp = request.send().map_err(|e| e.to_string())?;
+
+        // Cached document is still valid
+        if resp.status() == StatusCode::NOT_MODIFIED && cached.is_some() {
+            return Ok(None);
+        }
+
+        if !resp.status().is_success() {
+            return Err(format!("received status {}", resp.status()));
+        }
+
+        let get_header = |name: header::HeaderName| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned())
+        };
+
+        let meta = CacheMeta {
+            etag: get_header(header::ETAG),
+            fetched_at: now_millis(),
+            last_modified: get_header(header::LAST_MODIFIED),
+            url: self.url.clone(),
+        };
+
+        let content = resp.text().map_err(|e| e.to_string())?;
+
+        Ok(Some((content, meta)))
+    }
+
+    fn load_content(&self) -> Result<String, Error> {
+        if is_file_url(&self.url) {
+            let mut path = self.url.trim_start_matches("file://");
+
+            // file:///C:/path -> C:/path
+            if cfg!(windows) {
+                path = path.trim_start_matches('/');
+            }
+
+            return std::fs::read_to_string(path).map_err(|e| {
+                Error::from(format!(
+                    "Failed to load extended config <url>{}</url>: {}",
+                    self.url, e
+                ))
+            });
+        }
+
+        let cached = self.read_cache();
+
+        // Avoid the request entirely while the cache is fresh
+        if let Some(cached) = &cached {
+            if now_millis().saturating_sub(cached.meta.fetched_at) < (self.ttl as u128) * 1000 {
+                debug!(
+                    target: LOG_TARGET,
+                    "Using cached extended config {}",
+                    color::url(&self.url)
+                );
+
+                return Ok(cached.content.clone());
+            }
+        }
+
+        // Unfortunate we must use blocking here,
+        // but figment doesn't support async/await
+        match self.fetch(cached.as_ref().map(|c| &c.meta)) {
+            Ok(Some((content, meta))) => {
+                self.write_cache(Some(&content), &meta);
+
+                Ok(content)
+            }
+            // Not modified, so bump the fetched time and reuse the cache
+            Ok(None) => {
+                let mut cached = cached.unwrap();
+                cached.meta.fetched_at = now_millis();
+
+                self.write_cache(None, &cached.meta);
+
+                Ok(cached.content)
+            }
+            Err(error) => match cached {
+                Some(cached) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to fetch extended config {} ({}), falling back to the cached copy",
+                        color::url(&self.url),
+                        error
+                    );
+
+                    Ok(cached.content)
+                }
+                None => Err(Error::from(format!(
+                    "Failed to load extended config <url>{}</url>: {}",
+                    self.url, error
+                ))),
+            },
+        }
+    }
 }
 
 impl Provider for Url {
@@ -28,27 +262,141 @@ impl Provider for Url {
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
-        // Unfortunate we must use blocking here,
-        // but figment doesn't support async/await
-        let resp = reqwest::blocking::get(&self.url)
-            .map_err(|e| {
-                Error::from(format!(
-                    "Failed to load extended config <url>{}</url>: {}",
-                    self.url, e
-                ))
-            })?
-            .text()
-            .map_err(|e| {
-                Error::from(format!(
-                    "Failed to parse extended config <url>{}</url>: {}",
-                    self.url, e
-                ))
-            })?;
+        let content = self.load_content()?;
+
+        // Piggyback off the default providers, based on the URL's extension
+        ConfigFormat::from_path(&self.url)
+            .parse_string(&content, self.profile.as_ref().unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    const DOCUMENT: &str = "fileGroups:\n  sources:\n    - 'src/**/*'\n";
+
+    // A tiny HTTP server that responds to each request in order, and records
+    // the request headers that were received.
+    fn start_server(responses: Vec<String>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let requests_clone = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buffer = [0; 4096];
+                let size = stream.read(&mut buffer).unwrap();
+
+                requests_clone
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&buffer[0..size]).to_lowercase());
+
+                stream.write_all(response.as_bytes()).unwrap();
+                stream.flush().unwrap();
+            }
+        });
+
+        (format!("http://{}/config.yml", addr), requests)
+    }
+
+    fn ok_response(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+
+    fn not_modified_response() -> String {
+        "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_owned()
+    }
+
+    fn create_provider(url: &str, cache_dir: &Path) -> Url {
+        Url::from(url.to_owned())
+            .cache_dir(Some(cache_dir.to_path_buf()))
+            .profile("default")
+    }
+
+    #[test]
+    fn detects_urls() {
+        assert!(is_url("https://domain.com/config.yml"));
+        assert!(is_url("http://localhost/config.yml"));
+        assert!(is_url("file:///config.yml"));
+        assert!(!is_url("./config.yml"));
+        assert!(!is_url("httpdir/config.yml"));
+    }
+
+    #[test]
+    fn caches_fetched_documents() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let (url, _) = start_server(vec![ok_response(DOCUMENT)]);
+        let provider = create_provider(&url, dir.path());
+
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
+
+        // Server has shutdown, but the cache is fresh
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
+    }
+
+    #[test]
+    fn revalidates_with_etag_when_stale() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let (url, requests) = start_server(vec![ok_response(DOCUMENT), not_modified_response()]);
+        let provider = create_provider(&url, dir.path());
+
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
+
+        let provider = provider.ttl(0);
+
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
+        assert!(requests.lock().unwrap()[1].contains("if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn falls_back_to_cache_when_fetch_fails() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let (url, _) = start_server(vec![ok_response(DOCUMENT)]);
+        let provider = create_provider(&url, dir.path());
+
+        provider.load_content().unwrap();
+
+        // Server only responds once, so this request will fail
+        let provider = provider.ttl(0);
+
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
+    }
+
+    #[test]
+    fn errors_when_fetch_fails_without_cache() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let provider = create_provider("http://127.0.0.1:1/config.yml", dir.path());
+
+        assert!(provider.load_content().is_err());
+    }
+
+    #[test]
+    fn loads_file_urls() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("config.yml").write_str(DOCUMENT).unwrap();
+
+        let url = format!(
+            "file://{}",
+            dir.path()
+                .join("config.yml")
+                .to_string_lossy()
+                .replace('\\', "/")
+        );
+        let provider = create_provider(&url, dir.path());
 
-        // We expect the URLs to point to YAML files,
-        // so piggyback off the default YAML provider
-        Yaml::string(&resp)
-            .profile(self.profile.as_ref().unwrap().clone())
-            .data()
+        assert_eq!(provider.load_content().unwrap(), DOCUMENT);
     }
 }
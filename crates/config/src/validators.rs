@@ -1,6 +1,7 @@
 use crate::errors::create_validation_error;
+use crate::format::ConfigFormat;
 use moon_utils::regex::{matches_id, matches_target};
-use moon_utils::semver::Version;
+use moon_utils::semver::{parse_npm_range, Version};
 use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use validator::{validate_url as validate_base_url, ValidationError};
@@ -34,6 +35,19 @@ pub fn validate_semver_version(key: &str, value: &str) -> Result<(), ValidationE
     Ok(())
 }
 
+// Validate the value is a valid semver version, or a requirement range.
+pub fn validate_semver_requirement(key: &str, value: &str) -> Result<(), ValidationError> {
+    if Version::parse(value).is_err() && parse_npm_range(value).is_none() {
+        return Err(create_validation_error(
+            "invalid_semver_requirement",
+            key,
+            String::from("Must be a valid semantic version or range"),
+        ));
+    }
+
+    Ok(())
+}
+
 // Validate the value is a valid child relative file system path.
 // Will fail on absolute paths ("/"), and parent relative paths ("../").
 pub fn validate_child_relative_path(key: &str, value: &str) -> Result<(), ValidationError> {
@@ -125,10 +139,30 @@ pub fn validate_url(key: &str, value: &str, https_only: bool) -> Result<(), Vali
     Ok(())
 }
 
-// Validate the value is an acceptable URL or file path for an "extends" YAML field.
+// Validate the value is a URL template for downloading distribution files.
+// Local mirrors are supported through file URLs.
+pub fn validate_dist_url(key: &str, value: &str) -> Result<(), ValidationError> {
+    if !value.starts_with("file://") {
+        validate_url(key, value, false)?;
+    }
+
+    if !value.contains("{file}") {
+        return Err(create_validation_error(
+            "missing_file_placeholder",
+            key,
+            String::from("Must contain a {file} placeholder"),
+        ));
+    }
+
+    Ok(())
+}
+
+// Validate the value is an acceptable URL or file path for an "extends" field.
 pub fn validate_extends(value: &str) -> Result<(), ValidationError> {
     if value.starts_with("http") {
         validate_url("extends", value, true)?;
+    } else if value.starts_with("file://") {
+        // Absolute file URLs are allowed
 
         // Is there a better way to check that a value is a file system path?
         // We can't use existence checks because it's not absolute, and
@@ -141,11 +175,11 @@ pub fn validate_extends(value: &str) -> Result<(), ValidationError> {
         ));
     }
 
-    if !value.ends_with(".yml") && !value.ends_with(".yaml") {
+    if !ConfigFormat::is_supported(value) {
         return Err(create_validation_error(
-            "invalid_yaml",
+            "invalid_format",
             "extends",
-            String::from("Must be a YAML document"),
+            String::from("Must be a YAML, JSON, or TOML document"),
         ));
     }
 
@@ -170,6 +204,52 @@ mod tests {
         }
     }
 
+    mod validate_dist_url {
+        use super::*;
+
+        #[test]
+        fn passes_for_urls() {
+            assert!(validate_dist_url("key", "https://domain.com/v{version}/{file}").is_ok());
+            assert!(validate_dist_url("key", "http://mirror.internal/{file}").is_ok());
+        }
+
+        #[test]
+        fn passes_for_file_urls() {
+            assert!(validate_dist_url("key", "file:///mirror/v{version}/{file}").is_ok());
+        }
+
+        #[test]
+        fn fails_for_invalid_url() {
+            assert!(validate_dist_url("key", "domain.com/{file}").is_err());
+        }
+
+        #[test]
+        fn fails_without_file_placeholder() {
+            assert!(validate_dist_url("key", "https://domain.com/v{version}").is_err());
+        }
+    }
+
+    mod validate_semver_requirement {
+        use super::*;
+
+        #[test]
+        fn passes_for_version() {
+            assert!(validate_semver_requirement("key", "1.2.3").is_ok());
+        }
+
+        #[test]
+        fn passes_for_range() {
+            assert!(validate_semver_requirement("key", "^1.2").is_ok());
+            assert!(validate_semver_requirement("key", ">=16.0.0 <19").is_ok());
+            assert!(validate_semver_requirement("key", "18").is_ok());
+        }
+
+        #[test]
+        fn fails_for_invalid() {
+            assert!(validate_semver_requirement("key", "foo bar").is_err());
+        }
+    }
+
     mod validate_child_relative_path {
         use super::*;
 
@@ -0,0 +1,30 @@
+use crate::validators::validate_semver_version;
+use moon_lang_bun::BUN;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::env;
+use validator::{Validate, ValidationError};
+
+pub fn default_bun_version() -> String {
+    env::var("MOON_BUN_VERSION").unwrap_or_else(|_| BUN.default_version.to_string())
+}
+
+fn validate_bun_version(value: &str) -> Result<(), ValidationError> {
+    validate_semver_version("bun.version", value)
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BunConfig {
+    #[validate(custom = "validate_bun_version")]
+    pub version: String,
+}
+
+impl Default for BunConfig {
+    fn default() -> Self {
+        BunConfig {
+            version: default_bun_version(),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::validators::validate_id;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use validator::{Validate, ValidationError};
+
+type PathsMap = BTreeMap<String, Vec<String>>;
+
+fn validate_paths(paths: &PathsMap) -> Result<(), ValidationError> {
+    for key in paths.keys() {
+        validate_id(&format!("codeowners.paths.{}", key), key)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeownersProvider {
+    Bitbucket,
+    #[default]
+    GitHub,
+    GitLab,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CodeownersConfig {
+    #[validate(custom = "validate_paths")]
+    pub paths: PathsMap,
+
+    pub provider: CodeownersProvider,
+}
@@ -0,0 +1,59 @@
+use crate::project::ProjectType;
+use crate::validators::validate_id;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use validator::{Validate, ValidationError};
+
+type TagConstraintsMap = BTreeMap<String, TagConstraintConfig>;
+
+fn validate_tags(tags: &TagConstraintsMap) -> Result<(), ValidationError> {
+    for (tag, config) in tags {
+        validate_id(&format!("constraints.tags.{}", tag), tag)?;
+
+        if let Some(allow) = &config.allow {
+            for (index, allowed) in allow.iter().enumerate() {
+                validate_id(
+                    &format!("constraints.tags.{}.allow[{}]", tag, index),
+                    allowed,
+                )?;
+            }
+        }
+
+        for (index, denied) in config.deny.iter().enumerate() {
+            validate_id(&format!("constraints.tags.{}.deny[{}]", tag, index), denied)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TypeConstraintConfig {
+    #[serde(rename = "type")]
+    pub type_of: ProjectType,
+
+    pub deny: Vec<ProjectType>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TagConstraintConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+
+    pub deny: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConstraintsConfig {
+    #[validate(custom = "validate_tags")]
+    pub tags: TagConstraintsMap,
+
+    pub types: Vec<TypeConstraintConfig>,
+}
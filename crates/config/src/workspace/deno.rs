@@ -0,0 +1,30 @@
+use crate::validators::validate_semver_version;
+use moon_lang_deno::DENO;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::env;
+use validator::{Validate, ValidationError};
+
+pub fn default_deno_version() -> String {
+    env::var("MOON_DENO_VERSION").unwrap_or_else(|_| DENO.default_version.to_string())
+}
+
+fn validate_deno_version(value: &str) -> Result<(), ValidationError> {
+    validate_semver_version("deno.version", value)
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DenoConfig {
+    #[validate(custom = "validate_deno_version")]
+    pub version: String,
+}
+
+impl Default for DenoConfig {
+    fn default() -> Self {
+        DenoConfig {
+            version: default_deno_version(),
+        }
+    }
+}
@@ -1,20 +1,28 @@
 // .moon/workspace.yml
 
+pub mod bun;
+mod codeowners;
+mod constraints;
+pub mod deno;
 pub mod node;
+pub mod python;
 mod typescript;
 mod vcs;
 
-use crate::constants::FLAG_PROJECTS_USING_GLOB;
-use crate::errors::map_validation_errors_to_figment_errors;
-use crate::providers::url::Url;
+use crate::constants::{EXTENDS_CACHE_TTL, FLAG_PROJECTS_USING_GLOB};
+use crate::errors::{create_validation_error, map_validation_errors_to_figment_errors};
+use crate::format::merge_config_file;
+use crate::providers::url::{find_cache_dir, is_url, Url};
 use crate::types::{FileGlob, FilePath};
 use crate::validators::{validate_child_relative_path, validate_extends, validate_id};
-use figment::{
-    providers::{Format, Serialized, Yaml},
-    Error as FigmentError, Figment,
-};
+use bun::BunConfig;
+pub use codeowners::{CodeownersConfig, CodeownersProvider};
+pub use constraints::{ConstraintsConfig, TagConstraintConfig, TypeConstraintConfig};
+use deno::DenoConfig;
+use figment::{providers::Serialized, Error as FigmentError, Figment};
 use moon_utils::string_vec;
 use node::NodeConfig;
+use python::PythonConfig;
 use schemars::gen::SchemaGenerator;
 use schemars::schema::Schema;
 use schemars::{schema_for, JsonSchema};
@@ -50,10 +58,38 @@ fn validate_projects(projects: &ProjectsMap) -> Result<(), ValidationError> {
     Ok(())
 }
 
+// Validate the zstd compression level is within the supported range.
+fn validate_archive_compression_level(level: i32) -> Result<(), ValidationError> {
+    if !(1..=22).contains(&level) {
+        return Err(create_validation_error(
+            "invalid_compression_level",
+            "actionRunner.archiveCompressionLevel",
+            String::from("Must be a compression level between 1 and 22"),
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    #[default]
+    Gzip,
+    Zstd,
+}
+
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
 #[schemars(default)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionRunnerConfig {
+    #[validate(custom = "validate_archive_compression_level")]
+    pub archive_compression_level: i32,
+
+    pub archive_format: ArchiveFormat,
+
+    pub archive_preserve_mtimes: bool,
+
     pub implicit_inputs: Vec<String>,
 
     pub inherit_colors_for_piped_tasks: bool,
@@ -64,6 +100,9 @@ pub struct ActionRunnerConfig {
 impl Default for ActionRunnerConfig {
     fn default() -> Self {
         ActionRunnerConfig {
+            archive_compression_level: 3,
+            archive_format: ArchiveFormat::default(),
+            archive_preserve_mtimes: true,
             implicit_inputs: string_vec![
                 // When a project changes
                 "package.json",
@@ -85,9 +124,23 @@ pub struct WorkspaceConfig {
     #[validate]
     pub action_runner: ActionRunnerConfig,
 
+    #[validate]
+    pub bun: Option<BunConfig>,
+
+    #[validate]
+    pub codeowners: Option<CodeownersConfig>,
+
+    #[validate]
+    pub constraints: Option<ConstraintsConfig>,
+
+    #[validate]
+    pub deno: Option<DenoConfig>,
+
     #[validate(custom = "validate_extends")]
     pub extends: Option<String>,
 
+    pub extends_cache_ttl: Option<u64>,
+
     #[validate]
     pub node: NodeConfig,
 
@@ -96,6 +149,9 @@ pub struct WorkspaceConfig {
     #[validate(custom = "validate_projects")]
     pub projects: ProjectsMap,
 
+    #[validate]
+    pub python: Option<PythonConfig>,
+
     #[validate]
     pub typescript: TypeScriptConfig,
 
@@ -110,35 +166,57 @@ pub struct WorkspaceConfig {
 impl WorkspaceConfig {
     pub fn load(path: PathBuf) -> Result<WorkspaceConfig, Vec<FigmentError>> {
         let profile_name = "workspace";
-        let mut config = WorkspaceConfig::load_config(
-            Figment::from(Serialized::defaults(WorkspaceConfig::default()).profile(&profile_name))
-                .merge(Yaml::file(&path).profile(&profile_name))
-                .select(&profile_name),
-        )?;
+        let mut figment = merge_config_file(Figment::new(), &path, profile_name);
+        let mut config = WorkspaceConfig::load_config(&figment, profile_name)?;
 
         // This is janky, but figment does not support any kind of extends mechanism,
         // and figment providers do not have access to the current config dataset,
         // so we need to double-load this config and extract in the correct order!
-        if let Some(extends) = config.extends {
-            let mut figment = Figment::from(
-                Serialized::defaults(WorkspaceConfig::default()).profile(&profile_name),
-            );
-
-            if extends.starts_with("http") {
-                figment = figment.merge(Url::from(extends).profile(&profile_name));
+        if let Some(extends) = config.extends.clone() {
+            let mut extended_figment = Figment::new();
+
+            if is_url(&extends) {
+                extended_figment = extended_figment.merge(
+                    Url::from(extends)
+                        .cache_dir(find_cache_dir(path.parent().unwrap()))
+                        .profile(&profile_name)
+                        .ttl(config.get_extends_cache_ttl()),
+                );
             } else {
-                figment = figment
-                    .merge(Yaml::file(path.parent().unwrap().join(extends)).profile(&profile_name));
+                extended_figment = merge_config_file(
+                    extended_figment,
+                    path.parent().unwrap().join(extends),
+                    profile_name,
+                );
             };
 
-            figment = figment.merge(Yaml::file(&path).profile(&profile_name));
-
-            config = WorkspaceConfig::load_config(figment.select(&profile_name))?;
+            figment = merge_config_file(extended_figment, &path, profile_name);
+            config = WorkspaceConfig::load_config(&figment, profile_name)?;
         }
 
+        // Track whether the Node.js version was defined in a config file, as the
+        // default version will be resolved from the workspace when not defined.
+        config.node.version_configured = figment
+            .select(&profile_name)
+            .find_value("node.version")
+            .is_ok();
+
         // Versions from env vars should take precedence
+        if let Ok(bun_version) = env::var("MOON_BUN_VERSION") {
+            if let Some(bun_config) = &mut config.bun {
+                bun_config.version = bun_version;
+            }
+        }
+
+        if let Ok(deno_version) = env::var("MOON_DENO_VERSION") {
+            if let Some(deno_config) = &mut config.deno {
+                deno_config.version = deno_version;
+            }
+        }
+
         if let Ok(node_version) = env::var("MOON_NODE_VERSION") {
             config.node.version = node_version;
+            config.node.version_configured = true;
         }
 
         if let Ok(npm_version) = env::var("MOON_NPM_VERSION") {
@@ -151,6 +229,12 @@ impl WorkspaceConfig {
             }
         }
 
+        if let Ok(python_version) = env::var("MOON_PYTHON_VERSION") {
+            if let Some(python_config) = &mut config.python {
+                python_config.version = python_version;
+            }
+        }
+
         if let Ok(yarn_version) = env::var("MOON_YARN_VERSION") {
             if let Some(yarn_config) = &mut config.node.yarn {
                 yarn_config.version = yarn_version;
@@ -160,8 +244,31 @@ impl WorkspaceConfig {
         Ok(config)
     }
 
-    fn load_config(figment: Figment) -> Result<WorkspaceConfig, Vec<FigmentError>> {
-        let config: WorkspaceConfig = figment.extract().map_err(|e| vec![e])?;
+    /// Amount of seconds that documents fetched for `extends` are cached.
+    pub fn get_extends_cache_ttl(&self) -> u64 {
+        self.extends_cache_ttl.unwrap_or(EXTENDS_CACHE_TTL)
+    }
+
+    fn load_config(
+        figment: &Figment,
+        profile_name: &str,
+    ) -> Result<WorkspaceConfig, Vec<FigmentError>> {
+        let figment =
+            Figment::from(Serialized::defaults(WorkspaceConfig::default()).profile(&profile_name))
+                .merge(figment.clone())
+                .select(&profile_name);
+        let mut config: WorkspaceConfig = figment.extract().map_err(|e| vec![e])?;
+
+        // Loaded before extends are fetched, so it must be applied here,
+        // and unlike other env vars, invalid values should fail
+        if let Ok(ttl) = env::var("MOON_EXTENDS_CACHE_TTL") {
+            config.extends_cache_ttl = Some(ttl.parse::<u64>().map_err(|_| {
+                vec![FigmentError::from(format!(
+                    "Invalid MOON_EXTENDS_CACHE_TTL environment variable \"{}\", must be a number of seconds",
+                    ttl
+                ))]
+            })?);
+        }
 
         if let Err(errors) = config.validate() {
             return Err(map_validation_errors_to_figment_errors(&figment, &errors));
@@ -246,6 +353,7 @@ fn make_projects_schema(_gen: &mut SchemaGenerator) -> Schema {
 mod tests {
     use super::*;
     use moon_constants as constants;
+    use std::collections::BTreeMap;
     use std::path::Path;
 
     fn load_jailed_config(root: &Path) -> Result<WorkspaceConfig, figment::Error> {
@@ -260,28 +368,515 @@ mod tests {
         figment::Jail::expect_with(|jail| {
             jail.create_file(constants::CONFIG_WORKSPACE_FILENAME, "projects: {}")?;
 
-            let config = load_jailed_config(jail.directory())?;
+            let config = load_jailed_config(jail.directory())?;
+
+            assert_eq!(
+                config,
+                WorkspaceConfig {
+                    action_runner: ActionRunnerConfig::default(),
+                    bun: None,
+                    codeowners: None,
+                    constraints: None,
+                    deno: None,
+                    extends: None,
+                    extends_cache_ttl: None,
+                    node: NodeConfig::default(),
+                    projects: HashMap::new(),
+                    python: None,
+                    typescript: TypeScriptConfig::default(),
+                    vcs: VcsConfig::default(),
+                    schema: String::new(),
+                }
+            );
+
+            Ok(())
+        });
+    }
+
+    mod formats {
+        use super::*;
+        use std::fs;
+
+        #[test]
+        fn loads_json() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "workspace.json",
+                    r#"{ "projects": { "app": "apps/app" }, "node": { "version": "18.0.0" } }"#,
+                )?;
+
+                let config = WorkspaceConfig::load(jail.directory().join("workspace.json"))
+                    .map_err(|e| e.first().unwrap().clone())?;
+
+                assert_eq!(
+                    config.projects,
+                    HashMap::from([("app".to_owned(), "apps/app".to_owned())])
+                );
+                assert_eq!(config.node.version, "18.0.0");
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn loads_toml() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "workspace.toml",
+                    r#"
+[projects]
+app = "apps/app"
+
+[node]
+version = "18.0.0"
+"#,
+                )?;
+
+                let config = WorkspaceConfig::load(jail.directory().join("workspace.toml"))
+                    .map_err(|e| e.first().unwrap().clone())?;
+
+                assert_eq!(
+                    config.projects,
+                    HashMap::from([("app".to_owned(), "apps/app".to_owned())])
+                );
+                assert_eq!(config.node.version, "18.0.0");
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn extends_other_formats() {
+            figment::Jail::expect_with(|jail| {
+                fs::create_dir_all(jail.directory().join("shared")).unwrap();
+
+                jail.create_file(
+                    "shared/base.toml",
+                    r#"
+[node]
+version = "16.0.0"
+"#,
+                )?;
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    "extends: ./shared/base.toml\nprojects: {}",
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.node.version, "16.0.0");
+
+                Ok(())
+            });
+        }
+    }
+
+    mod bun {
+
+        #[test]
+        #[should_panic(
+            expected = "invalid type: found string \"foo\", expected struct BunConfig for key \"workspace.bun\""
+        )]
+        fn invalid_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(super::constants::CONFIG_WORKSPACE_FILENAME, "bun: foo")?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a valid semantic version for key \"workspace.bun.version\""
+        )]
+        fn invalid_version() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+bun:
+    version: 'foo bar'
+projects: {}"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn loads_defaults() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+bun: {}
+projects: {}"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.bun, Some(super::BunConfig::default()));
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn inherits_from_env_var() {
+            figment::Jail::expect_with(|jail| {
+                jail.set_env("MOON_BUN_VERSION", "4.5.6");
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+bun:
+    version: '1.2.3'
+projects: {}
+"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.bun.unwrap().version, String::from("4.5.6"));
+
+                Ok(())
+            });
+        }
+    }
+
+    mod codeowners {
+        use super::*;
+
+        #[test]
+        fn loads_settings() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+codeowners:
+    provider: gitlab
+    paths:
+        app:
+            - '/.github/workflows/app.yml'"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(
+                    config.codeowners,
+                    Some(CodeownersConfig {
+                        paths: BTreeMap::from([(
+                            "app".to_owned(),
+                            vec!["/.github/workflows/app.yml".to_owned()]
+                        )]),
+                        provider: CodeownersProvider::GitLab,
+                    })
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "unknown variant: found `svn`, expected `one of `bitbucket`, `github`, `gitlab`` for key \"workspace.codeowners.provider\""
+        )]
+        fn invalid_provider() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+codeowners:
+    provider: svn"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Must be a valid ID")]
+        fn invalid_project_id() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+codeowners:
+    paths:
+        '@app':
+            - '*.md'"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+    }
+
+    mod constraints {
+        use super::*;
+        use crate::ProjectType;
+
+        #[test]
+        fn loads_settings() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+constraints:
+    types:
+        - type: library
+          deny: [application, tool]
+    tags:
+        billing:
+            allow: [billing, shared]
+            deny: [auth]"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(
+                    config.constraints,
+                    Some(ConstraintsConfig {
+                        tags: BTreeMap::from([(
+                            "billing".to_owned(),
+                            TagConstraintConfig {
+                                allow: Some(string_vec!["billing", "shared"]),
+                                deny: string_vec!["auth"],
+                            }
+                        )]),
+                        types: vec![TypeConstraintConfig {
+                            type_of: ProjectType::Library,
+                            deny: vec![ProjectType::Application, ProjectType::Tool],
+                        }],
+                    })
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "unknown variant: found `service`, expected `one of `application`, `library`, `tool`, `unknown`` for key \"workspace.constraints.types.0.deny.0\""
+        )]
+        fn invalid_project_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+constraints:
+    types:
+        - type: library
+          deny: [service]"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Must be a valid ID")]
+        fn invalid_tag() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+constraints:
+    tags:
+        billing:
+            deny: ['@auth']"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+    }
+
+    mod deno {
+
+        #[test]
+        #[should_panic(
+            expected = "invalid type: found string \"foo\", expected struct DenoConfig for key \"workspace.deno\""
+        )]
+        fn invalid_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(super::constants::CONFIG_WORKSPACE_FILENAME, "deno: foo")?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a valid semantic version for key \"workspace.deno.version\""
+        )]
+        fn invalid_version() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+deno:
+    version: 'foo bar'
+projects: {}"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn loads_defaults() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+deno: {}
+projects: {}"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.deno, Some(super::DenoConfig::default()));
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn inherits_from_env_var() {
+            figment::Jail::expect_with(|jail| {
+                jail.set_env("MOON_DENO_VERSION", "4.5.6");
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+deno:
+    version: '1.2.3'
+projects: {}
+"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.deno.unwrap().version, String::from("4.5.6"));
+
+                Ok(())
+            });
+        }
+    }
+
+    mod extends {
+        use super::*;
+        use std::fs;
+
+        #[test]
+        fn loads_cache_ttl() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+extendsCacheTtl: 60"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.get_extends_cache_ttl(), 60);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn defaults_cache_ttl() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(super::constants::CONFIG_WORKSPACE_FILENAME, "projects: {}")?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.get_extends_cache_ttl(), 3600);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn inherits_cache_ttl_from_env_var() {
+            figment::Jail::expect_with(|jail| {
+                jail.set_env("MOON_EXTENDS_CACHE_TTL", "120");
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+extendsCacheTtl: 60"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.get_extends_cache_ttl(), 120);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Invalid MOON_EXTENDS_CACHE_TTL environment variable \"1h\", must be a number of seconds"
+        )]
+        fn invalid_cache_ttl_env_var() {
+            figment::Jail::expect_with(|jail| {
+                jail.set_env("MOON_EXTENDS_CACHE_TTL", "1h");
+
+                jail.create_file(super::constants::CONFIG_WORKSPACE_FILENAME, "projects: {}")?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
 
-            assert_eq!(
-                config,
-                WorkspaceConfig {
-                    action_runner: ActionRunnerConfig::default(),
-                    extends: None,
-                    node: NodeConfig::default(),
-                    projects: HashMap::new(),
-                    typescript: TypeScriptConfig::default(),
-                    vcs: VcsConfig::default(),
-                    schema: String::new(),
-                }
-            );
+        #[test]
+        #[should_panic(
+            expected = "invalid type: found string \"abc\", expected u64 for key \"workspace.extendsCacheTtl\""
+        )]
+        fn invalid_cache_ttl() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+extendsCacheTtl: abc"#,
+                )?;
 
-            Ok(())
-        });
-    }
+                super::load_jailed_config(jail.directory())?;
 
-    mod extends {
-        use super::*;
-        use std::fs;
+                Ok(())
+            });
+        }
 
         #[test]
         #[should_panic(
@@ -330,7 +925,9 @@ mod tests {
         }
 
         #[test]
-        #[should_panic(expected = "Must be a YAML document for key \"workspace.extends\"")]
+        #[should_panic(
+            expected = "Must be a YAML, JSON, or TOML document for key \"workspace.extends\""
+        )]
         fn not_a_yaml_url() {
             figment::Jail::expect_with(|jail| {
                 jail.create_file(
@@ -345,7 +942,9 @@ mod tests {
         }
 
         #[test]
-        #[should_panic(expected = "Must be a YAML document for key \"workspace.extends\"")]
+        #[should_panic(
+            expected = "Must be a YAML, JSON, or TOML document for key \"workspace.extends\""
+        )]
         fn not_a_yaml_file() {
             figment::Jail::expect_with(|jail| {
                 fs::create_dir_all(jail.directory().join("shared")).unwrap();
@@ -474,12 +1073,18 @@ node:
                     config,
                     WorkspaceConfig {
                         action_runner: ActionRunnerConfig::default(),
+                        bun: None,
+                        codeowners: None,
+                        constraints: None,
+                        deno: None,
                         extends: None,
+                        extends_cache_ttl: None,
                         node: NodeConfig {
                             package_manager: NodePackageManager::Yarn,
                             ..NodeConfig::default()
                         },
                         projects: HashMap::new(),
+                        python: None,
                         typescript: TypeScriptConfig::default(),
                         vcs: VcsConfig::default(),
                         schema: String::new(),
@@ -506,7 +1111,7 @@ node:
 
         #[test]
         #[should_panic(
-            expected = "Must be a valid semantic version for key \"workspace.node.version\""
+            expected = "Must be a valid semantic version or range for key \"workspace.node.version\""
         )]
         fn invalid_version() {
             figment::Jail::expect_with(|jail| {
@@ -526,10 +1131,7 @@ projects:
         }
 
         #[test]
-        #[should_panic(
-            expected = "Must be a valid semantic version for key \"workspace.node.version\""
-        )]
-        fn no_patch_version() {
+        fn supports_partial_versions() {
             figment::Jail::expect_with(|jail| {
                 jail.create_file(
                     super::constants::CONFIG_WORKSPACE_FILENAME,
@@ -540,28 +1142,79 @@ projects:
   foo: packages/foo"#,
                 )?;
 
-                super::load_jailed_config(jail.directory())?;
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.node.version, "16.13");
+                assert!(config.node.version_configured);
 
                 Ok(())
             });
         }
 
         #[test]
-        #[should_panic(
-            expected = "Must be a valid semantic version for key \"workspace.node.version\""
-        )]
-        fn no_minor_version() {
+        fn supports_version_ranges() {
             figment::Jail::expect_with(|jail| {
                 jail.create_file(
                     super::constants::CONFIG_WORKSPACE_FILENAME,
                     r#"
 node:
-  version: '16'
+  version: '>=16 <19'
 projects:
   foo: packages/foo"#,
                 )?;
 
-                super::load_jailed_config(jail.directory())?;
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.node.version, ">=16 <19");
+                assert!(config.node.version_configured);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn tracks_when_version_not_configured() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+node:
+  packageManager: yarn
+projects:
+  foo: packages/foo"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.node.version, NodeConfig::default().version);
+                assert!(!config.node.version_configured);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn tracks_version_configured_through_extends() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    "shared.yml",
+                    r#"
+node:
+  version: '18.0.0'"#,
+                )?;
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+extends: './shared.yml'
+projects:
+  foo: packages/foo"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.node.version, "18.0.0");
+                assert!(config.node.version_configured);
 
                 Ok(())
             });
@@ -956,6 +1609,181 @@ projects:
         }
     }
 
+    mod python {
+
+        #[test]
+        #[should_panic(
+            expected = "invalid type: found string \"foo\", expected struct PythonConfig for key \"workspace.python\""
+        )]
+        fn invalid_type() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(super::constants::CONFIG_WORKSPACE_FILENAME, "python: foo")?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a valid semantic version or range for key \"workspace.python.version\""
+        )]
+        fn invalid_version() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+python:
+    version: 'foo bar'
+projects: {}"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn loads_defaults() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+python: {}
+projects: {}"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.python, Some(super::PythonConfig::default()));
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn inherits_from_env_var() {
+            figment::Jail::expect_with(|jail| {
+                jail.set_env("MOON_PYTHON_VERSION", "4.5.6");
+
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+python:
+    version: '1.2.3'
+projects: {}
+"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.python.unwrap().version, String::from("4.5.6"));
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        fn supports_venv_settings() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+python:
+    version: '3.9'
+    interpreter: '/usr/bin/python3.9'
+    venvName: 'venv'
+    venvScope: 'project'
+projects: {}
+"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(
+                    config.python.unwrap(),
+                    super::PythonConfig {
+                        interpreter: Some(String::from("/usr/bin/python3.9")),
+                        venv_name: String::from("venv"),
+                        venv_scope: crate::PythonVenvScope::Project,
+                        version: String::from("3.9"),
+                    }
+                );
+
+                Ok(())
+            });
+        }
+    }
+
+    mod action_runner {
+        use super::*;
+
+        #[test]
+        fn loads_archive_settings() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+actionRunner:
+    archiveFormat: zstd
+    archiveCompressionLevel: 19
+    archivePreserveMtimes: false"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(config.action_runner.archive_format, ArchiveFormat::Zstd);
+                assert_eq!(config.action_runner.archive_compression_level, 19);
+                assert!(!config.action_runner.archive_preserve_mtimes);
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "unknown variant: found `brotli`, expected ``gzip` or `zstd`` for key \"workspace.actionRunner.archiveFormat\""
+        )]
+        fn invalid_archive_format() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+actionRunner:
+    archiveFormat: brotli"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a compression level between 1 and 22 for key \"workspace.actionRunner.archiveCompressionLevel\""
+        )]
+        fn invalid_compression_level() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+actionRunner:
+    archiveCompressionLevel: 30"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+    }
+
     mod vcs {
         use super::*;
 
@@ -976,9 +1804,15 @@ vcs:
                     config,
                     WorkspaceConfig {
                         action_runner: ActionRunnerConfig::default(),
+                        bun: None,
+                        codeowners: None,
+                        constraints: None,
+                        deno: None,
                         extends: None,
+                        extends_cache_ttl: None,
                         node: NodeConfig::default(),
                         projects: HashMap::new(),
+                        python: None,
                         typescript: TypeScriptConfig::default(),
                         vcs: VcsConfig {
                             manager: VcsManager::Svn,
@@ -1013,7 +1847,7 @@ vcs: 123"#,
 
         #[test]
         #[should_panic(
-            expected = "unknown variant: found `unknown`, expected ``git` or `svn`` for key \"workspace.vcs.manager\""
+            expected = "unknown variant: found `unknown`, expected `one of `git`, `hg`, `svn`` for key \"workspace.vcs.manager\""
         )]
         fn invalid_manager_option() {
             figment::Jail::expect_with(|jail| {
@@ -1031,6 +1865,56 @@ vcs:
             });
         }
 
+        #[test]
+        fn loads_hooks() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+vcs:
+    hooks:
+        pre-commit:
+            - ':lint'
+            - 'cargo fmt --check'"#,
+                )?;
+
+                let config = super::load_jailed_config(jail.directory())?;
+
+                assert_eq!(
+                    config.vcs.hooks,
+                    BTreeMap::from([(
+                        "pre-commit".to_owned(),
+                        vec![":lint".to_owned(), "cargo fmt --check".to_owned()]
+                    )])
+                );
+
+                Ok(())
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Must be a supported git hook name for key \"workspace.vcs.hooks.pre-commti\""
+        )]
+        fn invalid_hook_name() {
+            figment::Jail::expect_with(|jail| {
+                jail.create_file(
+                    super::constants::CONFIG_WORKSPACE_FILENAME,
+                    r#"
+projects: {}
+vcs:
+    hooks:
+        pre-commti:
+            - ':lint'"#,
+                )?;
+
+                super::load_jailed_config(jail.directory())?;
+
+                Ok(())
+            });
+        }
+
         #[test]
         #[should_panic(
             expected = "invalid type: found unsigned int `123`, expected a string for key \"workspace.vcs.defaultBranch\""
@@ -1,4 +1,4 @@
-use crate::validators::validate_semver_version;
+use crate::validators::{validate_dist_url, validate_semver_requirement, validate_semver_version};
 use moon_lang_node::{NODE, NODENV, NVMRC, PNPM, YARN};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -22,8 +22,24 @@ pub fn default_yarn_version() -> String {
     env::var("MOON_YARN_VERSION").unwrap_or_else(|_| YARN.default_version.to_string())
 }
 
+fn validate_node_dist_url(value: &str) -> Result<(), ValidationError> {
+    validate_dist_url("node.distUrl", value)
+}
+
+fn validate_npm_dist_url(value: &str) -> Result<(), ValidationError> {
+    validate_dist_url("node.npm.distUrl", value)
+}
+
+fn validate_pnpm_dist_url(value: &str) -> Result<(), ValidationError> {
+    validate_dist_url("node.pnpm.distUrl", value)
+}
+
+fn validate_yarn_dist_url(value: &str) -> Result<(), ValidationError> {
+    validate_dist_url("node.yarn.distUrl", value)
+}
+
 fn validate_node_version(value: &str) -> Result<(), ValidationError> {
-    validate_semver_version("node.version", value)
+    validate_semver_requirement("node.version", value)
 }
 
 fn validate_npm_version(value: &str) -> Result<(), ValidationError> {
@@ -107,7 +123,11 @@ impl NodeVersionManager {
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
 #[schemars(default)]
+#[serde(rename_all = "camelCase")]
 pub struct NpmConfig {
+    #[validate(custom = "validate_npm_dist_url")]
+    pub dist_url: Option<String>,
+
     #[validate(custom = "validate_npm_version")]
     pub version: String,
 }
@@ -115,13 +135,18 @@ pub struct NpmConfig {
 impl Default for NpmConfig {
     fn default() -> Self {
         NpmConfig {
+            dist_url: None,
             version: default_npm_version(),
         }
     }
 }
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct PnpmConfig {
+    #[validate(custom = "validate_pnpm_dist_url")]
+    pub dist_url: Option<String>,
+
     #[validate(custom = "validate_pnpm_version")]
     pub version: String,
 }
@@ -129,13 +154,18 @@ pub struct PnpmConfig {
 impl Default for PnpmConfig {
     fn default() -> Self {
         PnpmConfig {
+            dist_url: None,
             version: default_pnpm_version(),
         }
     }
 }
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct YarnConfig {
+    #[validate(custom = "validate_yarn_dist_url")]
+    pub dist_url: Option<String>,
+
     #[validate(custom = "validate_yarn_version")]
     pub version: String,
 }
@@ -143,6 +173,7 @@ pub struct YarnConfig {
 impl Default for YarnConfig {
     fn default() -> Self {
         YarnConfig {
+            dist_url: None,
             version: default_yarn_version(),
         }
     }
@@ -160,6 +191,9 @@ pub struct NodeConfig {
 
     pub dependency_version_format: NodeVersionFormat,
 
+    #[validate(custom = "validate_node_dist_url")]
+    pub dist_url: Option<String>,
+
     pub infer_tasks_from_scripts: bool,
 
     #[validate]
@@ -177,6 +211,11 @@ pub struct NodeConfig {
     #[validate(custom = "validate_node_version")]
     pub version: String,
 
+    // Whether `version` was explicitly configured, or is the default
+    // and should be resolved from the workspace instead.
+    #[serde(skip)]
+    pub version_configured: bool,
+
     #[validate]
     pub yarn: Option<YarnConfig>,
 }
@@ -188,6 +227,7 @@ impl Default for NodeConfig {
             alias_package_names: None,
             dedupe_on_lockfile_change: true,
             dependency_version_format: NodeVersionFormat::WorkspaceCaret,
+            dist_url: None,
             infer_tasks_from_scripts: false,
             npm: NpmConfig::default(),
             package_manager: NodePackageManager::default(),
@@ -195,6 +235,7 @@ impl Default for NodeConfig {
             sync_project_workspace_dependencies: true,
             sync_version_manager_config: None,
             version: default_node_version(),
+            version_configured: false,
             yarn: None,
         }
     }
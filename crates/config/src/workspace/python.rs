@@ -0,0 +1,47 @@
+use crate::validators::validate_semver_requirement;
+use moon_lang_python::PYTHON;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::env;
+use validator::{Validate, ValidationError};
+
+pub fn default_python_version() -> String {
+    env::var("MOON_PYTHON_VERSION").unwrap_or_else(|_| PYTHON.default_version.to_string())
+}
+
+fn validate_python_version(value: &str) -> Result<(), ValidationError> {
+    validate_semver_requirement("python.version", value)
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonVenvScope {
+    Project,
+    #[default]
+    Workspace,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize, Validate)]
+#[schemars(default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PythonConfig {
+    pub interpreter: Option<String>,
+
+    pub venv_name: String,
+
+    pub venv_scope: PythonVenvScope,
+
+    #[validate(custom = "validate_python_version")]
+    pub version: String,
+}
+
+impl Default for PythonConfig {
+    fn default() -> Self {
+        PythonConfig {
+            interpreter: None,
+            venv_name: String::from(".venv"),
+            venv_scope: PythonVenvScope::default(),
+            version: default_python_version(),
+        }
+    }
+}
@@ -1,12 +1,47 @@
+use crate::errors::create_validation_error;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use std::collections::BTreeMap;
+use validator::{Validate, ValidationError};
+
+// https://git-scm.com/docs/githooks
+const GIT_HOOKS: [&str; 14] = [
+    "applypatch-msg",
+    "commit-msg",
+    "post-applypatch",
+    "post-checkout",
+    "post-commit",
+    "post-merge",
+    "post-rewrite",
+    "pre-applypatch",
+    "pre-auto-gc",
+    "pre-commit",
+    "pre-merge-commit",
+    "pre-push",
+    "pre-rebase",
+    "prepare-commit-msg",
+];
+
+fn validate_hooks(hooks: &BTreeMap<String, Vec<String>>) -> Result<(), ValidationError> {
+    for name in hooks.keys() {
+        if !GIT_HOOKS.contains(&name.as_str()) {
+            return Err(create_validation_error(
+                "invalid_hook",
+                &format!("vcs.hooks.{}", name),
+                String::from("Must be a supported git hook name"),
+            ));
+        }
+    }
+
+    Ok(())
+}
 
 #[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum VcsManager {
     #[default]
     Git,
+    Hg,
     Svn,
 }
 
@@ -17,6 +52,9 @@ pub struct VcsConfig {
     pub manager: VcsManager,
 
     pub default_branch: String,
+
+    #[validate(custom = "validate_hooks")]
+    pub hooks: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for VcsConfig {
@@ -24,6 +62,7 @@ impl Default for VcsConfig {
         VcsConfig {
             manager: VcsManager::default(),
             default_branch: String::from("master"),
+            hooks: BTreeMap::new(),
         }
     }
 }
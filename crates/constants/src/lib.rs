@@ -4,4 +4,6 @@ pub const CONFIG_WORKSPACE_FILENAME: &str = "workspace.yml";
 
 pub const CONFIG_GLOBAL_PROJECT_FILENAME: &str = "project.yml";
 
+pub const CONFIG_GLOBAL_TASKS_DIRNAME: &str = "tasks";
+
 pub const CONFIG_PROJECT_FILENAME: &str = "moon.yml";
@@ -22,6 +22,9 @@ pub enum MoonError {
     #[error("Failed to parse <path>{0}</path>: {1}")]
     Json(PathBuf, #[source] JsonError),
 
+    #[error("Found multiple configuration files in <path>{0}</path> ({1}). Only one format may be used.")]
+    MultipleConfigFormats(PathBuf, String),
+
     #[error("Network failure: {0}")]
     Network(#[source] IoError),
 
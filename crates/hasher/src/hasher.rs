@@ -1,4 +1,4 @@
-use crate::{hash_btree, hash_vec, Digest, Hasher, Sha256};
+use crate::{hash_btree, hash_value, hash_vec, Digest, Hasher, Sha256};
 use moon_task::Task;
 use moon_utils::path;
 use serde::{Deserialize, Serialize};
@@ -8,6 +8,9 @@ use std::env;
 #[derive(Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TargetHasher {
+    // `actionRunner.archivePreserveMtimes`
+    archive_preserve_mtimes: bool,
+
     // Task `command`
     command: String,
 
@@ -40,11 +43,17 @@ pub struct TargetHasher {
 impl TargetHasher {
     pub fn new() -> Self {
         TargetHasher {
+            archive_preserve_mtimes: true,
             version: String::from("1"),
             ..TargetHasher::default()
         }
     }
 
+    /// Hash settings that change the contents of output archives.
+    pub fn hash_archive_settings(&mut self, preserve_mtimes: bool) {
+        self.archive_preserve_mtimes = preserve_mtimes;
+    }
+
     /// Hash additional args outside of the provided task.
     pub fn hash_args(&mut self, passthrough_args: &[String]) {
         if !passthrough_args.is_empty() {
@@ -73,11 +82,25 @@ impl TargetHasher {
     }
 
     /// Hash `args`, `inputs`, `deps`, and `env` vars from a task.
+    /// Interpolated values may contain secrets, so they are hashed before
+    /// being added, as the hasher is written to the hash manifest.
     pub fn hash_task(&mut self, task: &Task) {
+        let hash_interpolated = |value: &String| {
+            if task.interpolated_values.contains(value) {
+                hash_value(value)
+            } else {
+                value.to_owned()
+            }
+        };
+
         self.command = task.command.clone();
-        self.args = task.args.clone();
-        self.env_vars.extend(task.env.clone());
-        self.deps = task.deps.clone();
+        self.args = task.args.iter().map(hash_interpolated).collect();
+        self.env_vars.extend(
+            task.env
+                .iter()
+                .map(|(key, value)| (key.to_owned(), hash_interpolated(value))),
+        );
+        self.deps = task.deps.iter().map(|d| d.label()).collect();
         self.outputs = task.outputs.clone();
         self.target = task.target.clone();
 
@@ -86,7 +109,13 @@ impl TargetHasher {
         self.deps.sort();
         self.outputs.sort();
 
-        // Inherits vars from inputs
+        // Inherits vars from interpolation and inputs
+        for var_name in &task.interpolated_vars {
+            self.env_vars
+                .entry(var_name.to_owned())
+                .or_insert_with(|| hash_value(&env::var(var_name).unwrap_or_default()));
+        }
+
         for var_name in &task.input_vars {
             self.env_vars
                 .entry(var_name.to_owned())
@@ -108,5 +137,10 @@ impl Hasher for TargetHasher {
         hash_btree(&self.inputs, sha);
         hash_vec(&self.outputs, sha);
         hash_vec(&self.project_deps, sha);
+
+        // Only when disabled, so that existing hashes remain the same
+        if !self.archive_preserve_mtimes {
+            sha.update("archivePreserveMtimes:false".as_bytes());
+        }
     }
 }
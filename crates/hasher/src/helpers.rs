@@ -25,6 +25,11 @@ pub fn to_hash_only(hasher: &impl Hasher) -> String {
     format!("{:x}", sha.finalize())
 }
 
+/// Hash a single value, for values that should not appear as plain text.
+pub fn hash_value(value: &str) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
+
 pub fn hash_btree(tree: &BTreeMap<String, String>, sha: &mut Sha256) {
     for (k, v) in tree {
         sha.update(k.as_bytes());
@@ -0,0 +1,63 @@
+use moon_lang::LangError;
+use std::env::consts;
+
+pub fn get_download_file_name() -> Result<String, LangError> {
+    let platform = match consts::OS {
+        "linux" => "linux",
+        "macos" => "darwin",
+        os => {
+            return Err(LangError::UnsupportedPlatform(
+                os.to_string(),
+                String::from("Bun"),
+            ));
+        }
+    };
+
+    let arch = match consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "aarch64",
+        arch => {
+            return Err(LangError::UnsupportedArchitecture(
+                arch.to_string(),
+                String::from("Bun"),
+            ));
+        }
+    };
+
+    Ok(format!(
+        "bun-{platform}-{arch}",
+        platform = platform,
+        arch = arch
+    ))
+}
+
+pub fn get_download_file() -> Result<String, LangError> {
+    Ok(format!("{}.zip", get_download_file_name()?))
+}
+
+pub fn get_bun_url<A, B, C>(version: A, host: B, path: C) -> String
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+    C: AsRef<str>,
+{
+    format!(
+        "{host}/oven-sh/bun/releases/download/bun-v{version}/{path}",
+        host = host.as_ref(),
+        version = version.as_ref(),
+        path = path.as_ref(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_release_urls() {
+        assert_eq!(
+            get_bun_url("0.1.11", "https://github.com", "SHASUMS256.txt"),
+            "https://github.com/oven-sh/bun/releases/download/bun-v0.1.11/SHASUMS256.txt"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+pub mod bun;
+
+use moon_lang::{Language, PackageManager};
+
+pub const BUN: Language = Language {
+    binary: "bun",
+    default_version: "0.1.11",
+    vendor_bins_dir: "node_modules/.bin",
+    vendor_dir: "node_modules",
+};
+
+// Package managers
+
+pub const BUN_INSTALL: PackageManager = PackageManager {
+    binary: "bun",
+    config_filenames: &["bunfig.toml"],
+    default_version: "0.1.11",
+    lock_filenames: &["bun.lockb"],
+    manifest_filename: "package.json",
+};
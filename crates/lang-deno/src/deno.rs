@@ -0,0 +1,73 @@
+use moon_lang::LangError;
+use std::env::consts;
+use std::path::{Path, PathBuf};
+
+pub fn get_download_file_name() -> Result<String, LangError> {
+    let target = match (consts::OS, consts::ARCH) {
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        ("linux" | "macos" | "windows", arch) => {
+            return Err(LangError::UnsupportedArchitecture(
+                arch.to_string(),
+                String::from("Deno"),
+            ));
+        }
+        (os, _) => {
+            return Err(LangError::UnsupportedPlatform(
+                os.to_string(),
+                String::from("Deno"),
+            ));
+        }
+    };
+
+    Ok(format!("deno-{target}", target = target))
+}
+
+pub fn get_download_file() -> Result<String, LangError> {
+    Ok(format!("{}.zip", get_download_file_name()?))
+}
+
+pub fn get_deno_url<A, B, C>(version: A, host: B, path: C) -> String
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+    C: AsRef<str>,
+{
+    format!(
+        "{host}/denoland/deno/releases/download/v{version}/{path}",
+        host = host.as_ref(),
+        version = version.as_ref(),
+        path = path.as_ref(),
+    )
+}
+
+pub fn get_bin_name<T: AsRef<str>>(name: T) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", name.as_ref())
+    } else {
+        name.as_ref().to_owned()
+    }
+}
+
+/// Find the `deno.json` or `deno.jsonc` config file within the directory.
+pub fn find_config_file(dir: &Path) -> Option<PathBuf> {
+    crate::DENO_CONFIG_FILENAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_release_urls() {
+        assert_eq!(
+            get_deno_url("1.25.2", "https://github.com", "deno-x86_64-apple-darwin.zip"),
+            "https://github.com/denoland/deno/releases/download/v1.25.2/deno-x86_64-apple-darwin.zip"
+        );
+    }
+}
@@ -0,0 +1,14 @@
+pub mod deno;
+
+use moon_lang::Language;
+
+pub const DENO: Language = Language {
+    binary: "deno",
+    default_version: "1.25.2",
+    vendor_bins_dir: "vendor",
+    vendor_dir: "vendor",
+};
+
+pub const DENO_CONFIG_FILENAMES: &[&str] = &["deno.json", "deno.jsonc"];
+
+pub const DENO_LOCK_FILENAME: &str = "deno.lock";
@@ -0,0 +1,25 @@
+pub mod python;
+
+use moon_lang::{Language, PackageManager};
+
+pub const PYTHON: Language = Language {
+    binary: "python3",
+    default_version: "3.10",
+    vendor_bins_dir: ".venv/bin",
+    vendor_dir: ".venv",
+};
+
+// Package managers
+
+pub const PIP: PackageManager = PackageManager {
+    binary: "pip",
+    config_filenames: &["pip.conf"],
+    default_version: "22.2.2",
+    lock_filenames: &[
+        "requirements.txt",
+        "poetry.lock",
+        "Pipfile.lock",
+        "pdm.lock",
+    ],
+    manifest_filename: "pyproject.toml",
+};
@@ -0,0 +1,65 @@
+use crate::PIP;
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub fn get_bin_name<T: AsRef<str>>(name: T) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", name.as_ref())
+    } else {
+        name.as_ref().to_owned()
+    }
+}
+
+/// Return the directory within a virtual environment that contains its binaries.
+pub fn get_venv_bin_dir(venv_dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv_dir.join("Scripts")
+    } else {
+        venv_dir.join("bin")
+    }
+}
+
+/// Find a binary by name within the directories of the `PATH` environment variable.
+pub fn find_bin_on_path(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    let bin_name = get_bin_name(name);
+
+    env::split_paths(&paths)
+        .map(|dir| dir.join(&bin_name))
+        .find(|path| path.is_file())
+}
+
+/// Find the file that dependencies should be installed from, preferring
+/// `requirements.txt` over `pyproject.toml`.
+pub fn find_dependencies_file(dir: &Path) -> Option<PathBuf> {
+    [PIP.lock_filenames[0], PIP.manifest_filename]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// Extract the version from the output of `python --version`,
+/// which is formatted as "Python 3.10.4".
+pub fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .last()
+        .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|version| version.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_output() {
+        assert_eq!(
+            parse_version_output("Python 3.10.4"),
+            Some("3.10.4".to_owned())
+        );
+        assert_eq!(parse_version_output("3.9.1"), Some("3.9.1".to_owned()));
+        assert_eq!(parse_version_output("Python"), None);
+        assert_eq!(parse_version_output(""), None);
+    }
+}
@@ -89,14 +89,18 @@ pub fn is_using_version_manager<T: AsRef<Path>>(base_dir: T, vm: &VersionManager
 
 #[derive(Clone, Eq, PartialEq)]
 pub enum SupportedLanguage {
+    Bun,
     Node,
+    Python,
     System,
 }
 
 impl SupportedLanguage {
     pub fn label(&self) -> String {
         match self {
+            SupportedLanguage::Bun => "Bun".into(),
             SupportedLanguage::Node => "Node.js".into(),
+            SupportedLanguage::Python => "Python".into(),
             SupportedLanguage::System => "system".into(),
         }
     }
@@ -105,7 +109,9 @@ impl SupportedLanguage {
 impl fmt::Display for SupportedLanguage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            SupportedLanguage::Bun => write!(f, "Bun"),
             SupportedLanguage::Node => write!(f, "Node"),
+            SupportedLanguage::Python => write!(f, "Python"),
             SupportedLanguage::System => write!(f, "System"),
         }
     }
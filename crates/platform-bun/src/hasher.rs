@@ -0,0 +1,132 @@
+use moon_hasher::{hash_btree, Digest, Hasher, Sha256};
+use moon_lang_node::package::PackageJson;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BunTargetHasher {
+    // Bun version
+    bun_version: String,
+
+    // `package.json` `dependencies`
+    package_dependencies: BTreeMap<String, String>,
+
+    // `package.json` `devDependencies`
+    package_dev_dependencies: BTreeMap<String, String>,
+
+    // `package.json` `peerDependencies`
+    package_peer_dependencies: BTreeMap<String, String>,
+
+    // Version of our hasher
+    #[allow(dead_code)]
+    version: String,
+}
+
+impl BunTargetHasher {
+    pub fn new(bun_version: String) -> Self {
+        BunTargetHasher {
+            bun_version,
+            version: String::from("1"),
+            ..BunTargetHasher::default()
+        }
+    }
+
+    /// Hash `package.json` dependencies as version changes should bust the cache.
+    pub fn hash_package_json(&mut self, package: &PackageJson) {
+        if let Some(deps) = &package.dependencies {
+            self.package_dependencies.extend(deps.clone());
+        }
+
+        if let Some(dev_deps) = &package.dev_dependencies {
+            self.package_dev_dependencies.extend(dev_deps.clone());
+        }
+
+        if let Some(peer_deps) = &package.peer_dependencies {
+            self.package_peer_dependencies.extend(peer_deps.clone());
+        }
+    }
+}
+
+impl Hasher for BunTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.version.as_bytes());
+        sha.update(self.bun_version.as_bytes());
+
+        hash_btree(&self.package_dependencies, sha);
+        hash_btree(&self.package_dev_dependencies, sha);
+        hash_btree(&self.package_peer_dependencies, sha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moon_hasher::to_hash_only;
+
+    #[test]
+    fn returns_same_hash_if_called_again() {
+        let hasher = BunTargetHasher::new(String::from("0.0.0"));
+
+        assert_eq!(to_hash_only(&hasher), to_hash_only(&hasher));
+    }
+
+    #[test]
+    fn returns_different_hash_for_diff_contents() {
+        let hasher1 = BunTargetHasher::new(String::from("0.0.0"));
+        let hasher2 = BunTargetHasher::new(String::from("1.0.0"));
+
+        assert_ne!(to_hash_only(&hasher1), to_hash_only(&hasher2));
+    }
+
+    mod package_json {
+        use super::*;
+
+        #[test]
+        fn returns_same_hash_for_diff_order_insertion() {
+            let mut package1 = PackageJson::default();
+            package1.add_dependency("react", "17.0.0", true);
+
+            let mut package2 = PackageJson::default();
+            package2.add_dependency("react-dom", "17.0.0", true);
+
+            let mut hasher1 = BunTargetHasher::new(String::from("0.0.0"));
+            hasher1.hash_package_json(&package2);
+            hasher1.hash_package_json(&package1);
+
+            let mut hasher2 = BunTargetHasher::new(String::from("0.0.0"));
+            hasher2.hash_package_json(&package1);
+            hasher2.hash_package_json(&package2);
+
+            assert_eq!(to_hash_only(&hasher1), to_hash_only(&hasher2));
+        }
+
+        #[test]
+        fn supports_all_dep_types() {
+            let mut package = PackageJson::default();
+            package.add_dependency("moment", "10.0.0", true);
+
+            let mut hasher1 = BunTargetHasher::new(String::from("0.0.0"));
+            hasher1.hash_package_json(&package);
+            let hash1 = to_hash_only(&hasher1);
+
+            package.dev_dependencies =
+                Some(BTreeMap::from([("eslint".to_owned(), "8.0.0".to_owned())]));
+
+            let mut hasher2 = BunTargetHasher::new(String::from("0.0.0"));
+            hasher2.hash_package_json(&package);
+            let hash2 = to_hash_only(&hasher2);
+
+            package.peer_dependencies =
+                Some(BTreeMap::from([("react".to_owned(), "18.0.0".to_owned())]));
+
+            let mut hasher3 = BunTargetHasher::new(String::from("0.0.0"));
+            hasher3.hash_package_json(&package);
+            let hash3 = to_hash_only(&hasher3);
+
+            assert_ne!(hash1, hash2);
+            assert_ne!(hash1, hash3);
+            assert_ne!(hash2, hash3);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use moon_hasher::{hash_vec, Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenoTargetHasher {
+    // Deno version
+    deno_version: String,
+
+    // `deno.json` contents
+    deno_json: Vec<String>,
+
+    // `deno.lock` contents
+    deno_lock: Vec<String>,
+
+    // Version of our hasher
+    #[allow(dead_code)]
+    version: String,
+}
+
+impl DenoTargetHasher {
+    pub fn new(deno_version: String) -> Self {
+        DenoTargetHasher {
+            deno_version,
+            version: String::from("1"),
+            ..DenoTargetHasher::default()
+        }
+    }
+
+    /// Hash `deno.json` contents, as import maps, compiler options,
+    /// and tasks may all alter the output.
+    pub fn hash_deno_json(&mut self, contents: String) {
+        self.deno_json.push(contents);
+    }
+
+    /// Hash `deno.lock` contents as dependency changes should bust the cache.
+    pub fn hash_deno_lock(&mut self, contents: String) {
+        self.deno_lock.push(contents);
+    }
+}
+
+impl Hasher for DenoTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.version.as_bytes());
+        sha.update(self.deno_version.as_bytes());
+
+        hash_vec(&self.deno_json, sha);
+        hash_vec(&self.deno_lock, sha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moon_hasher::to_hash_only;
+
+    #[test]
+    fn returns_same_hash_if_called_again() {
+        let hasher = DenoTargetHasher::new(String::from("0.0.0"));
+
+        assert_eq!(to_hash_only(&hasher), to_hash_only(&hasher));
+    }
+
+    #[test]
+    fn returns_different_hash_for_diff_contents() {
+        let hasher1 = DenoTargetHasher::new(String::from("0.0.0"));
+        let hasher2 = DenoTargetHasher::new(String::from("1.0.0"));
+
+        assert_ne!(to_hash_only(&hasher1), to_hash_only(&hasher2));
+    }
+
+    #[test]
+    fn returns_different_hash_for_config_and_lockfile() {
+        let mut hasher1 = DenoTargetHasher::new(String::from("0.0.0"));
+        hasher1.hash_deno_json(String::from(r#"{"importMap":"./import_map.json"}"#));
+        let hash1 = to_hash_only(&hasher1);
+
+        hasher1.hash_deno_lock(String::from(r#"{"https://deno.land/std/fmt.ts":"abc"}"#));
+        let hash2 = to_hash_only(&hasher1);
+
+        let mut hasher2 = DenoTargetHasher::new(String::from("0.0.0"));
+        hasher2.hash_deno_json(String::from(r#"{"importMap":"./other_map.json"}"#));
+        let hash3 = to_hash_only(&hasher2);
+
+        assert_ne!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+        assert_ne!(hash2, hash3);
+    }
+}
@@ -2,7 +2,7 @@ use crate::LOG_TARGET;
 use lazy_static::lazy_static;
 use moon_lang_node::package::{PackageJson, ScriptsSet};
 use moon_logger::{color, debug, warn};
-use moon_task::{PlatformType, Target, Task, TaskError, TaskID};
+use moon_task::{PlatformType, Target, Task, TaskDependencyConfig, TaskError, TaskID};
 use moon_utils::regex::{UNIX_SYSTEM_COMMAND, WINDOWS_SYSTEM_COMMAND};
 use moon_utils::{process, regex, string_vec};
 use std::collections::{BTreeMap, HashMap};
@@ -476,7 +476,10 @@ impl<'a> ScriptParser<'a> {
             )? {
                 if !previous_task_id.is_empty() {
                     if let Some(task) = self.tasks.get_mut(&task_id) {
-                        task.deps.push(format!("~:{}", previous_task_id));
+                        task.deps.push(TaskDependencyConfig::new(&format!(
+                            "~:{}",
+                            previous_task_id
+                        )));
                     }
                 }
 
@@ -522,7 +525,8 @@ impl<'a> ScriptParser<'a> {
 
             if let Some(pre_task_id) = self.parse_script(format!("pre{}", script_name), pre)? {
                 if let Some(task) = self.tasks.get_mut(task_id) {
-                    task.deps.push(format!("~:{}", pre_task_id));
+                    task.deps
+                        .push(TaskDependencyConfig::new(&format!("~:{}", pre_task_id)));
                 }
             }
         }
@@ -533,7 +537,8 @@ impl<'a> ScriptParser<'a> {
 
             if let Some(post_task_id) = self.parse_script(format!("post{}", script_name), post)? {
                 if let Some(task) = self.tasks.get_mut(&post_task_id) {
-                    task.deps.push(format!("~:{}", task_id));
+                    task.deps
+                        .push(TaskDependencyConfig::new(&format!("~:{}", task_id)));
                 }
             }
         }
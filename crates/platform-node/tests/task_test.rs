@@ -1,7 +1,7 @@
 use moon_lang_node::package::PackageJson;
 use moon_platform_node::task::{create_task, should_run_in_ci, TaskContext};
 use moon_platform_node::{create_tasks_from_scripts, infer_tasks_from_scripts};
-use moon_task::{PlatformType, Task, TaskOptions};
+use moon_task::{PlatformType, Task, TaskDependencyConfig, TaskOptions};
 use moon_utils::string_vec;
 use std::collections::{BTreeMap, HashMap};
 
@@ -637,7 +637,7 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "do".to_owned(),
                             args: string_vec!["another"],
-                            deps: string_vec!["~:test"],
+                            deps: vec![TaskDependencyConfig::new("~:test")],
                             platform: PlatformType::Node,
                             ..Task::new("project:posttest")
                         }
@@ -647,7 +647,7 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "jest".to_owned(),
                             args: string_vec!["."],
-                            deps: string_vec!["~:pretest"],
+                            deps: vec![TaskDependencyConfig::new("~:pretest")],
                             platform: PlatformType::Node,
                             ..Task::new("project:test")
                         }
@@ -687,7 +687,7 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "do".to_owned(),
                             args: string_vec!["another"],
-                            deps: string_vec!["~:pretest-dep1"],
+                            deps: vec![TaskDependencyConfig::new("~:pretest-dep1")],
                             platform: PlatformType::Node,
                             ..Task::new("project:pretest")
                         }
@@ -697,7 +697,7 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "jest".to_owned(),
                             args: string_vec!["."],
-                            deps: string_vec!["~:pretest"],
+                            deps: vec![TaskDependencyConfig::new("~:pretest")],
                             platform: PlatformType::Node,
                             ..Task::new("project:test")
                         }
@@ -737,7 +737,10 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "do".to_owned(),
                             args: string_vec!["another"],
-                            deps: string_vec!["~:posttest-dep1", "~:test"],
+                            deps: vec![
+                                TaskDependencyConfig::new("~:posttest-dep1"),
+                                TaskDependencyConfig::new("~:test")
+                            ],
                             platform: PlatformType::Node,
                             ..Task::new("project:posttest")
                         }
@@ -786,7 +789,7 @@ mod create_tasks_from_scripts {
                         Task {
                             command: "npm".to_owned(),
                             args: string_vec!["publish"],
-                            deps: string_vec!["~:prerelease"],
+                            deps: vec![TaskDependencyConfig::new("~:prerelease")],
                             platform: PlatformType::Node,
                             ..Task::new("project:release")
                         }
@@ -1276,7 +1279,7 @@ mod complex_examples {
                     Task {
                         command: "moon".to_owned(),
                         args: string_vec!["run", "project:test"],
-                        deps: string_vec!["~:check-dep1"],
+                        deps: vec![TaskDependencyConfig::new("~:check-dep1")],
                         platform: PlatformType::Node,
                         ..Task::new("project:check-dep2")
                     }
@@ -1286,7 +1289,7 @@ mod complex_examples {
                     Task {
                         command: "moon".to_owned(),
                         args: string_vec!["run", "project:lint"],
-                        deps: string_vec!["~:check-dep2"],
+                        deps: vec![TaskDependencyConfig::new("~:check-dep2")],
                         platform: PlatformType::Node,
                         ..Task::new("project:check")
                     }
@@ -1314,7 +1317,7 @@ mod complex_examples {
                     Task {
                         command: "git".to_owned(),
                         args: string_vec!["add", "yarn.lock"],
-                        deps: string_vec!["~:commit-dep1"],
+                        deps: vec![TaskDependencyConfig::new("~:commit-dep1")],
                         platform: PlatformType::System,
                         ..Task::new("project:commit")
                     }
@@ -1396,7 +1399,7 @@ mod complex_examples {
                     Task {
                         command: "moon".to_owned(),
                         args: string_vec!["run", "project:setup"],
-                        deps: string_vec!["~:prerelease-dep1"],
+                        deps: vec![TaskDependencyConfig::new("~:prerelease-dep1")],
                         platform: PlatformType::Node,
                         ..Task::new("project:prerelease-dep2")
                     }
@@ -1406,7 +1409,7 @@ mod complex_examples {
                     Task {
                         command: "moon".to_owned(),
                         args: string_vec!["run", "project:packup"],
-                        deps: string_vec!["~:prerelease-dep2"],
+                        deps: vec![TaskDependencyConfig::new("~:prerelease-dep2")],
                         platform: PlatformType::Node,
                         ..Task::new("project:prerelease-dep3")
                     }
@@ -1416,7 +1419,7 @@ mod complex_examples {
                     Task {
                         command: "moon".to_owned(),
                         args: string_vec!["run", "project:check"],
-                        deps: string_vec!["~:prerelease-dep3"],
+                        deps: vec![TaskDependencyConfig::new("~:prerelease-dep3")],
                         platform: PlatformType::Node,
                         ..Task::new("project:prerelease")
                     }
@@ -1426,7 +1429,7 @@ mod complex_examples {
                     Task {
                         command: "beemo".to_owned(),
                         args: string_vec!["run-script", "lerna-release"],
-                        deps: string_vec!["~:prerelease"],
+                        deps: vec![TaskDependencyConfig::new("~:prerelease")],
                         platform: PlatformType::Node,
                         ..Task::new("project:release")
                     }
@@ -1637,7 +1640,7 @@ mod complex_examples {
                     Task {
                         command: "cross-env".to_owned(),
                         args: string_vec!["node", "./dist/bin-prettier.js"],
-                        deps: string_vec!["~:perf-dep1"],
+                        deps: vec![TaskDependencyConfig::new("~:perf-dep1")],
                         env: HashMap::from([("NODE_ENV".to_owned(), "production".to_owned())]),
                         platform: PlatformType::Node,
                         ..Task::new("project:perf")
@@ -1657,7 +1660,7 @@ mod complex_examples {
                     Task {
                         command: "cross-env".to_owned(),
                         args: string_vec!["node", "--inspect-brk", "./dist/bin-prettier.js"],
-                        deps: string_vec!["~:perf-inspect-dep1"],
+                        deps: vec![TaskDependencyConfig::new("~:perf-inspect-dep1")],
                         env: HashMap::from([("NODE_ENV".to_owned(), "production".to_owned())]),
                         platform: PlatformType::Node,
                         ..Task::new("project:perf-inspect")
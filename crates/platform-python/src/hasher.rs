@@ -0,0 +1,90 @@
+use moon_hasher::{hash_btree, Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonTargetHasher {
+    // Lockfile and requirements contents, keyed by their workspace relative path
+    lockfiles: BTreeMap<String, String>,
+
+    // Python version
+    python_version: String,
+
+    // Version of our hasher
+    #[allow(dead_code)]
+    version: String,
+}
+
+impl PythonTargetHasher {
+    pub fn new(python_version: String) -> Self {
+        PythonTargetHasher {
+            python_version,
+            version: String::from("1"),
+            ..PythonTargetHasher::default()
+        }
+    }
+
+    /// Hash the contents of a lockfile (`requirements.txt`, `poetry.lock`, etc),
+    /// as dependency changes should bust the cache.
+    pub fn hash_lockfile(&mut self, path: String, contents: String) {
+        self.lockfiles.insert(path, contents);
+    }
+}
+
+impl Hasher for PythonTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.version.as_bytes());
+        sha.update(self.python_version.as_bytes());
+
+        hash_btree(&self.lockfiles, sha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moon_hasher::to_hash_only;
+
+    #[test]
+    fn returns_same_hash_if_called_again() {
+        let hasher = PythonTargetHasher::new(String::from("3.10"));
+
+        assert_eq!(to_hash_only(&hasher), to_hash_only(&hasher));
+    }
+
+    #[test]
+    fn returns_different_hash_for_diff_contents() {
+        let hasher1 = PythonTargetHasher::new(String::from("3.10"));
+        let hasher2 = PythonTargetHasher::new(String::from("3.11"));
+
+        assert_ne!(to_hash_only(&hasher1), to_hash_only(&hasher2));
+    }
+
+    #[test]
+    fn returns_different_hash_for_lockfiles() {
+        let mut hasher1 = PythonTargetHasher::new(String::from("3.10"));
+        hasher1.hash_lockfile(
+            String::from("requirements.txt"),
+            String::from("requests==2.28.1"),
+        );
+        let hash1 = to_hash_only(&hasher1);
+
+        hasher1.hash_lockfile(
+            String::from("app/requirements.txt"),
+            String::from("flask==2.2.2"),
+        );
+        let hash2 = to_hash_only(&hasher1);
+
+        let mut hasher2 = PythonTargetHasher::new(String::from("3.10"));
+        hasher2.hash_lockfile(
+            String::from("requirements.txt"),
+            String::from("requests==2.28.0"),
+        );
+        let hash3 = to_hash_only(&hasher2);
+
+        assert_ne!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+        assert_ne!(hash2, hash3);
+    }
+}
@@ -3,11 +3,14 @@ use moon_config::constants::FLAG_PROJECTS_USING_GLOB;
 use moon_config::{GlobalProjectConfig, ProjectAlias, ProjectID, ProjectLanguage, WorkspaceConfig};
 use moon_logger::{color, debug, map_list, trace};
 use moon_platform_node::{infer_tasks_from_package, load_project_aliases_from_packages};
-use moon_project::{detect_projects_with_globs, Project, ProjectError, ProjectsSourceMap};
+use moon_project::{
+    detect_projects_with_globs, enforce_constraints, Project, ProjectError, ProjectsSourceMap,
+};
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
+use std::collections::btree_map::Entry;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
@@ -240,6 +243,11 @@ impl ProjectGraph {
         Ok(deps)
     }
 
+    /// Return the file system location of a project, relative from the workspace root.
+    pub fn get_source(&self, alias_or_id: &str) -> Option<&String> {
+        self.projects_map.get(&self.resolve_id(alias_or_id))
+    }
+
     /// Resolve a project ID from the provided value, which can be an ID or alias.
     pub fn resolve_id(&self, alias_or_id: &str) -> String {
         match self.aliases.get(alias_or_id) {
@@ -286,13 +294,16 @@ impl ProjectGraph {
         format!("{:?}", dot)
     }
 
-    fn create_project(&self, id: &str, source: &str) -> Result<Project, ProjectError> {
+    /// Create a project from the provided ID and source, without inserting
+    /// it (or its dependencies) into the project graph.
+    pub fn create_project(&self, id: &str, source: &str) -> Result<Project, ProjectError> {
         let mut project = Project::new(
             id,
             source,
             &self.workspace_root,
             &self.global_config,
             &self.workspace_config.action_runner.implicit_inputs,
+            self.workspace_config.get_extends_cache_ttl(),
         )?;
 
         project.alias = self.find_alias_for_id(id);
@@ -312,7 +323,12 @@ impl ProjectGraph {
             if let Some(tasks) = infer_tasks_from_package(id, &project.root)? {
                 for (task_id, task) in tasks {
                     // Scripts should not override global tasks
-                    project.tasks.entry(task_id).or_insert(task);
+                    if let Entry::Vacant(entry) = project.tasks.entry(task_id.clone()) {
+                        entry.insert(task);
+                        project
+                            .task_sources
+                            .insert(task_id, vec!["package.json".to_owned()]);
+                    }
                 }
             }
         }
@@ -383,6 +399,15 @@ impl ProjectGraph {
 
             for dep_id in depends_on {
                 let dep_index = self.internal_load(dep_id.as_str(), indices, graph)?;
+
+                if let Some(constraints) = &self.workspace_config.constraints {
+                    enforce_constraints(
+                        constraints,
+                        graph.node_weight(node_index).unwrap(),
+                        graph.node_weight(dep_index).unwrap(),
+                    )?;
+                }
+
                 graph.add_edge(node_index, dep_index, ());
             }
         }
@@ -1,4 +1,4 @@
 mod graph;
 
 pub use graph::ProjectGraph;
-pub use moon_project::ProjectError;
+pub use moon_project::{enforce_constraints, ProjectError};
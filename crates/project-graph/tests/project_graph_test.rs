@@ -72,6 +72,21 @@ async fn get_aliases_graph(node_config: NodeConfig) -> ProjectGraph {
     .unwrap()
 }
 
+async fn get_constraints_graph() -> ProjectGraph {
+    let workspace_root = get_fixtures_dir("project-graph/constraints");
+    let workspace_config =
+        WorkspaceConfig::load(workspace_root.join(".moon/workspace.yml")).unwrap();
+
+    ProjectGraph::create(
+        &workspace_root,
+        &workspace_config,
+        GlobalProjectConfig::default(),
+        &CacheEngine::create(&workspace_root).await.unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
 mod get_dependencies_of {
     use super::*;
 
@@ -197,3 +212,56 @@ mod aliases {
         assert_snapshot!(graph.to_dot());
     }
 }
+
+mod constraints {
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_valid_edges() {
+        let graph = get_constraints_graph().await;
+
+        let billing = graph.load("billing").unwrap();
+
+        assert_eq!(
+            graph.get_dependencies_of(&billing).unwrap(),
+            string_vec!["shared"]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_projects_without_constrained_tags() {
+        let graph = get_constraints_graph().await;
+
+        assert!(graph.load("app").is_ok());
+    }
+
+    #[tokio::test]
+    async fn errors_for_denied_project_type() {
+        let graph = get_constraints_graph().await;
+
+        assert_eq!(
+            graph.load("typeViolation").unwrap_err().to_string(),
+            "Invalid <symbol>dependsOn</symbol> edge <id>typeViolation</id> → <id>app</id>: <symbol>library</symbol> projects may not depend on <symbol>application</symbol> projects."
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_for_denied_tag() {
+        let graph = get_constraints_graph().await;
+
+        assert_eq!(
+            graph.load("tagDenied").unwrap_err().to_string(),
+            "Invalid <symbol>dependsOn</symbol> edge <id>tagDenied</id> → <id>auth</id>: projects tagged <symbol>billing</symbol> may not depend on a project that has the denied tag <symbol>auth</symbol>."
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_when_missing_allowed_tag() {
+        let graph = get_constraints_graph().await;
+
+        assert_eq!(
+            graph.load("tagNotAllowed").unwrap_err().to_string(),
+            "Invalid <symbol>dependsOn</symbol> edge <id>tagNotAllowed</id> → <id>untagged</id>: projects tagged <symbol>billing</symbol> may not depend on a project that is missing one of the allowed tags <symbol>billing</symbol>, <symbol>shared</symbol>."
+        );
+    }
+}
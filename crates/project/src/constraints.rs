@@ -0,0 +1,70 @@
+use crate::errors::ProjectError;
+use crate::project::Project;
+use moon_config::ConstraintsConfig;
+
+/// Verify that the `dependsOn` edge between a project and one of its dependencies
+/// satisfies the project type and tag constraints configured in the workspace.
+pub fn enforce_constraints(
+    constraints: &ConstraintsConfig,
+    project: &Project,
+    dependency: &Project,
+) -> Result<(), ProjectError> {
+    let project_type = &project.config.type_of;
+    let dep_type = &dependency.config.type_of;
+
+    for rule in &constraints.types {
+        if rule.type_of == *project_type && rule.deny.contains(dep_type) {
+            return Err(ProjectError::InvalidTypeRelationship(
+                project.id.clone(),
+                project_type.to_string(),
+                dependency.id.clone(),
+                dep_type.to_string(),
+            ));
+        }
+    }
+
+    for tag in &project.config.tags {
+        let rule = match constraints.tags.get(tag) {
+            Some(rule) => rule,
+            None => continue,
+        };
+
+        if let Some(denied) = dependency
+            .config
+            .tags
+            .iter()
+            .find(|t| rule.deny.contains(t))
+        {
+            return Err(ProjectError::InvalidTagRelationship(
+                project.id.clone(),
+                tag.clone(),
+                dependency.id.clone(),
+                format!("has the denied tag <symbol>{}</symbol>", denied),
+            ));
+        }
+
+        if let Some(allow) = &rule.allow {
+            if !dependency.config.tags.iter().any(|t| allow.contains(t)) {
+                return Err(ProjectError::InvalidTagRelationship(
+                    project.id.clone(),
+                    tag.clone(),
+                    dependency.id.clone(),
+                    if allow.is_empty() {
+                        "is not explicitly allowed".to_owned()
+                    } else {
+                        format!(
+                            "is missing one of the allowed tags {}",
+                            allow
+                                .iter()
+                                .map(|t| format!("<symbol>{}</symbol>", t))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
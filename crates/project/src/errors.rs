@@ -1,4 +1,3 @@
-use moon_constants::CONFIG_PROJECT_FILENAME;
 use moon_error::MoonError;
 use moon_task::{TargetError, TaskError};
 use moon_utils::glob::GlobError;
@@ -6,11 +5,18 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ProjectError {
+    #[error("Failed to validate <file>{0}</file> configuration file.\n\n{1}")]
+    InvalidConfigFile(String, String),
+
     #[error(
-        "Failed to validate <file>{0}/{}</file> configuration file.\n\n{1}",
-        CONFIG_PROJECT_FILENAME
+        "Invalid <symbol>dependsOn</symbol> edge <id>{0}</id> → <id>{2}</id>: projects tagged <symbol>{1}</symbol> may not depend on a project that {3}."
     )]
-    InvalidConfigFile(String, String),
+    InvalidTagRelationship(String, String, String, String),
+
+    #[error(
+        "Invalid <symbol>dependsOn</symbol> edge <id>{0}</id> → <id>{2}</id>: <symbol>{1}</symbol> projects may not depend on <symbol>{3}</symbol> projects."
+    )]
+    InvalidTypeRelationship(String, String, String, String),
 
     #[error("No project exists at path <file>{0}</file>.")]
     MissingProject(String),
@@ -1,9 +1,11 @@
+mod constraints;
 mod errors;
 mod helpers;
 mod project;
 
+pub use constraints::enforce_constraints;
 pub use errors::ProjectError;
 pub use helpers::*;
 
 pub use moon_config::{ProjectID, ProjectType};
-pub use project::{Project, ProjectsMap};
+pub use project::{Project, ProjectsMap, TaskSourcesMap};
@@ -1,7 +1,7 @@
 use crate::errors::ProjectError;
 use moon_config::{
-    format_figment_errors, DependencyConfig, FilePath, GlobalProjectConfig, PlatformType,
-    ProjectConfig, ProjectDependsOn, ProjectID, TaskConfig, TaskID,
+    find_config_file, format_figment_errors, DependencyConfig, FilePath, GlobalProjectConfig,
+    PlatformType, ProjectConfig, ProjectDependsOn, ProjectID, TaskConfig, TaskID,
 };
 use moon_constants::CONFIG_PROJECT_FILENAME;
 use moon_logger::{color, debug, trace, Logable};
@@ -17,14 +17,17 @@ pub type ProjectsMap = HashMap<ProjectID, Project>;
 
 pub type TasksMap = BTreeMap<TaskID, Task>;
 
-// moon.yml
+pub type TaskSourcesMap = BTreeMap<TaskID, Vec<String>>;
+
+type InheritedConfigs<'a> = [(String, &'a GlobalProjectConfig)];
+
+// moon.yml, moon.json, or moon.toml
 fn load_project_config(
     log_target: &str,
     project_root: &Path,
     project_source: &str,
-) -> Result<ProjectConfig, ProjectError> {
-    let config_path = project_root.join(CONFIG_PROJECT_FILENAME);
-
+    extends_cache_ttl: u64,
+) -> Result<(String, ProjectConfig), ProjectError> {
     trace!(
         target: log_target,
         "Attempting to find {} in {}",
@@ -32,30 +35,45 @@ fn load_project_config(
         color::path(project_root),
     );
 
-    if config_path.exists() {
-        return ProjectConfig::load(config_path).map_err(|e| {
-            ProjectError::InvalidConfigFile(String::from(project_source), format_figment_errors(e))
-        });
+    if let Some(config_path) = find_config_file(project_root, CONFIG_PROJECT_FILENAME)? {
+        let config_file = config_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+
+        return match ProjectConfig::load(config_path, extends_cache_ttl) {
+            Ok(config) => Ok((config_file, config)),
+            Err(errors) => Err(ProjectError::InvalidConfigFile(
+                format!("{}/{}", project_source, config_file),
+                format_figment_errors(errors),
+            )),
+        };
     }
 
-    Ok(ProjectConfig::new(project_root))
+    Ok((
+        CONFIG_PROJECT_FILENAME.to_owned(),
+        ProjectConfig::new(project_root),
+    ))
 }
 
 fn create_file_groups_from_config(
     log_target: &str,
     config: &ProjectConfig,
-    global_config: &GlobalProjectConfig,
+    global_configs: &InheritedConfigs,
 ) -> FileGroupsMap {
     let mut file_groups = HashMap::<String, FileGroup>::new();
 
     debug!(target: log_target, "Creating file groups");
 
-    // Add global file groups first
-    for (group_id, files) in &global_config.file_groups {
-        file_groups.insert(
-            group_id.to_owned(),
-            FileGroup::new(group_id, files.to_owned()),
-        );
+    // Add global file groups first, with scoped configs overriding
+    for (_, global_config) in global_configs {
+        for (group_id, files) in &global_config.file_groups {
+            file_groups.insert(
+                group_id.to_owned(),
+                FileGroup::new(group_id, files.to_owned()),
+            );
+        }
     }
 
     // Override global configs with local
@@ -106,13 +124,14 @@ fn create_dependencies_from_config(
 fn create_tasks_from_config(
     log_target: &str,
     project_id: &str,
-    project_config: &ProjectConfig,
-    global_config: &GlobalProjectConfig,
+    (config_file, project_config): (&str, &ProjectConfig),
+    global_configs: &InheritedConfigs,
     dependencies: &[DependencyConfig],
     token_data: &TokenSharedData,
     implicit_inputs: &[String],
-) -> Result<TasksMap, ProjectError> {
+) -> Result<(TasksMap, TaskSourcesMap), ProjectError> {
     let mut tasks = BTreeMap::<String, Task>::new();
+    let mut task_sources = TaskSourcesMap::new();
 
     debug!(target: log_target, "Creating tasks");
 
@@ -135,61 +154,80 @@ fn create_tasks_from_config(
         exclude.extend(exclude_config.clone());
     }
 
-    // Add global tasks first while taking inheritance config into account
-    for (task_id, task_config) in &global_config.tasks {
-        // None = Include all
-        // [] = Include none
-        // ["a"] = Include "a"
-        if !include_all {
-            if include.is_empty() {
-                trace!(
-                    target: log_target,
-                    "Not inheriting global tasks, empty `include` set"
-                );
+    // Add global tasks first while taking inheritance config into account.
+    // Scoped configs are merged in order on top of the base global config.
+    for (config_source, global_config) in global_configs {
+        for (task_id, task_config) in &global_config.tasks {
+            // None = Include all
+            // [] = Include none
+            // ["a"] = Include "a"
+            if !include_all {
+                if include.is_empty() {
+                    trace!(
+                        target: log_target,
+                        "Not inheriting global tasks, empty `include` set"
+                    );
+
+                    break;
+                } else if !include.contains(task_id) {
+                    trace!(
+                        target: log_target,
+                        "Not inheriting global task {}, not explicitly included",
+                        color::id(task_id)
+                    );
+
+                    continue;
+                }
+            }
 
-                break;
-            } else if !include.contains(task_id) {
+            // None, [] = Exclude none
+            // ["a"] = Exclude "a"
+            if !exclude.is_empty() && exclude.contains(task_id) {
                 trace!(
                     target: log_target,
-                    "Not inheriting global task {}, not explicitly included",
+                    "Not inheriting global task {}, explicitly excluded",
                     color::id(task_id)
                 );
 
                 continue;
             }
-        }
 
-        // None, [] = Exclude none
-        // ["a"] = Exclude "a"
-        if !exclude.is_empty() && exclude.contains(task_id) {
-            trace!(
-                target: log_target,
-                "Not inheriting global task {}, explicitly excluded",
-                color::id(task_id)
-            );
+            let task_name = if rename.contains_key(task_id) {
+                let renamed_task_id = rename.get(task_id).unwrap();
 
-            continue;
-        }
+                trace!(
+                    target: log_target,
+                    "Renaming global task {} to {}",
+                    color::id(task_id),
+                    color::id(renamed_task_id)
+                );
 
-        let task_name = if rename.contains_key(task_id) {
-            let renamed_task_id = rename.get(task_id).unwrap();
+                renamed_task_id
+            } else {
+                task_id
+            };
 
-            trace!(
-                target: log_target,
-                "Renaming global task {} to {}",
-                color::id(task_id),
-                color::id(renamed_task_id)
-            );
+            if let Some(task) = tasks.get_mut(task_name) {
+                debug!(
+                    target: log_target,
+                    "Merging global task {} with {}",
+                    color::id(task_name),
+                    color::file(config_source)
+                );
 
-            renamed_task_id
-        } else {
-            task_id
-        };
+                task.merge(task_config);
+            } else {
+                tasks.insert(
+                    task_name.to_owned(),
+                    Task::from_config(Target::format(project_id, task_name)?, task_config),
+                );
+            }
 
-        tasks.insert(
-            task_name.to_owned(),
-            Task::from_config(Target::format(project_id, task_name)?, task_config),
-        );
+            task_sources
+                .entry(task_name.to_owned())
+                .or_default()
+                .push(config_source.to_owned());
+        }
     }
 
     // Add local tasks second
@@ -210,18 +248,35 @@ fn create_tasks_from_config(
                 Task::from_config(Target::format(project_id, task_id)?, task_config),
             );
         }
+
+        task_sources
+            .entry(task_id.to_owned())
+            .or_default()
+            .push(config_file.to_owned());
     }
 
     // Expand deps, args, inputs, and outputs after all tasks have been created
     for task in tasks.values_mut() {
         if matches!(task.platform, PlatformType::Unknown) {
-            task.platform = TaskConfig::detect_platform(project_config, &task.command);
+            task.platform = TaskConfig::detect_platform(
+                project_config,
+                &task.command,
+                task.options.shell.is_some(),
+            );
         }
 
         // Inherit implicit inputs before resolving
         task.inputs.extend(implicit_inputs.iter().cloned());
 
+        // The env file affects the task's environment, so changes should mark it
+        // as affected. Its contents are also hashed directly by the runner,
+        // as env files are commonly gitignored.
+        if let Some(env_file) = &task.options.env_file {
+            task.inputs.push(env_file.to_owned());
+        }
+
         // Resolve in order!
+        task.expand_env();
         task.expand_deps(project_id, dependencies)?;
         task.expand_inputs(TokenResolver::for_inputs(token_data))?;
         task.expand_outputs(TokenResolver::for_outputs(token_data))?;
@@ -230,7 +285,7 @@ fn create_tasks_from_config(
         task.expand_args(TokenResolver::for_args(token_data))?;
     }
 
-    Ok(tasks)
+    Ok((tasks, task_sources))
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -264,6 +319,9 @@ pub struct Project {
 
     /// Tasks specific to the project. Inherits all tasks from the global config.
     pub tasks: TasksMap,
+
+    /// Config files that each task was created from or merged with, in order.
+    pub task_sources: TaskSourcesMap,
 }
 
 impl PartialEq for Project {
@@ -290,6 +348,7 @@ impl Project {
         workspace_root: &Path,
         global_config: &GlobalProjectConfig,
         implicit_inputs: &[String],
+        extends_cache_ttl: u64,
     ) -> Result<Project, ProjectError> {
         let root = workspace_root.join(path::normalize_separators(source));
         let log_target = format!("moon:project:{}", id);
@@ -306,15 +365,17 @@ impl Project {
             return Err(ProjectError::MissingProject(String::from(source)));
         }
 
-        let config = load_project_config(&log_target, &root, source)?;
-        let file_groups = create_file_groups_from_config(&log_target, &config, global_config);
+        let (config_file, config) =
+            load_project_config(&log_target, &root, source, extends_cache_ttl)?;
+        let global_configs = global_config.get_inherited_configs(&config.language, &config.type_of);
+        let file_groups = create_file_groups_from_config(&log_target, &config, &global_configs);
         let dependencies = create_dependencies_from_config(&log_target, &config);
         let token_data = TokenSharedData::new(&file_groups, workspace_root, &root, &config);
-        let tasks = create_tasks_from_config(
+        let (tasks, task_sources) = create_tasks_from_config(
             &log_target,
             id,
-            &config,
-            global_config,
+            (&config_file, &config),
+            &global_configs,
             &dependencies,
             &token_data,
             implicit_inputs,
@@ -330,6 +391,7 @@ impl Project {
             root,
             source: String::from(source),
             tasks,
+            task_sources,
         })
     }
 
@@ -1,6 +1,8 @@
+use moon_config::constants::EXTENDS_CACHE_TTL;
 use moon_config::{
     GlobalProjectConfig, PlatformType, ProjectConfig, ProjectDependsOn, ProjectLanguage,
-    ProjectMetadataConfig, ProjectType, TargetID, TaskConfig, TaskMergeStrategy, TaskOptionsConfig,
+    ProjectMetadataConfig, ProjectType, TargetID, TaskConfig, TaskDependency, TaskDependencyConfig,
+    TaskMergeStrategy, TaskOptionsConfig,
 };
 use moon_project::{Project, ProjectError};
 use moon_task::{EnvVars, FileGroup, Target, Task};
@@ -22,6 +24,8 @@ fn mock_global_project_config() -> GlobalProjectConfig {
         file_groups: HashMap::from([(String::from("sources"), string_vec!["src/**/*"])]),
         tasks: BTreeMap::new(),
         schema: String::new(),
+        file_name: None,
+        scoped_configs: BTreeMap::new(),
     }
 }
 
@@ -34,6 +38,7 @@ fn doesnt_exist() {
         &get_fixtures_root(),
         &mock_global_project_config(),
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
 }
@@ -47,6 +52,7 @@ fn no_config() {
         &workspace_root,
         &mock_global_project_config(),
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
 
@@ -72,6 +78,7 @@ fn empty_config() {
         &workspace_root,
         &mock_global_project_config(),
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
 
@@ -98,6 +105,7 @@ fn basic_config() {
         &workspace_root,
         &mock_global_project_config(),
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
     let project_root = workspace_root.join("projects/basic");
@@ -137,6 +145,7 @@ fn advanced_config() {
         &workspace_root,
         &mock_global_project_config(),
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
 
@@ -177,6 +186,7 @@ fn overrides_global_file_groups() {
             ..GlobalProjectConfig::default()
         },
         &[],
+        EXTENDS_CACHE_TTL,
     )
     .unwrap();
 
@@ -221,6 +231,7 @@ mod tasks {
     fn mock_merged_task_options_config(strategy: TaskMergeStrategy) -> TaskOptionsConfig {
         TaskOptionsConfig {
             cache: None,
+            env_file: None,
             merge_args: Some(strategy.clone()),
             merge_deps: Some(strategy.clone()),
             merge_env: Some(strategy.clone()),
@@ -231,12 +242,14 @@ mod tasks {
             run_deps_in_parallel: Some(true),
             run_in_ci: Some(true),
             run_from_workspace_root: None,
+            shell: None,
         }
     }
 
     fn mock_local_task_options_config(strategy: TaskMergeStrategy) -> TaskOptionsConfig {
         TaskOptionsConfig {
             cache: None,
+            env_file: None,
             merge_args: Some(strategy.clone()),
             merge_deps: Some(strategy.clone()),
             merge_env: Some(strategy.clone()),
@@ -247,12 +260,14 @@ mod tasks {
             run_deps_in_parallel: None,
             run_in_ci: None,
             run_from_workspace_root: None,
+            shell: None,
         }
     }
 
     fn stub_global_task_options_config() -> TaskOptionsConfig {
         TaskOptionsConfig {
             cache: Some(true),
+            env_file: None,
             merge_args: None,
             merge_deps: None,
             merge_env: None,
@@ -263,9 +278,19 @@ mod tasks {
             run_deps_in_parallel: Some(true),
             run_in_ci: Some(true),
             run_from_workspace_root: None,
+            shell: None,
         }
     }
 
+    fn stub_deps(targets: &[&str]) -> Option<Vec<TaskDependency>> {
+        Some(
+            targets
+                .iter()
+                .map(|t| TaskDependency::String(t.to_string()))
+                .collect(),
+        )
+    }
+
     fn stub_global_env_vars() -> EnvVars {
         HashMap::from([
             ("GLOBAL".to_owned(), "1".to_owned()),
@@ -301,6 +326,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -343,6 +369,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -422,6 +449,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &implicit_inputs,
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -477,7 +505,7 @@ mod tasks {
                     TaskConfig {
                         args: Some(string_vec!["--a"]),
                         command: Some(String::from("standard")),
-                        deps: Some(string_vec!["a:standard"]),
+                        deps: stub_deps(&["a:standard"]),
                         env: Some(stub_global_env_vars()),
                         inputs: Some(string_vec!["a.*"]),
                         outputs: Some(string_vec!["a.ts"]),
@@ -488,6 +516,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -501,7 +530,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b"]),
                             command: Some(String::from("newcmd")),
-                            deps: Some(string_vec!["b:standard"]),
+                            deps: stub_deps(&["b:standard"]),
                             env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                             inputs: Some(string_vec!["b.*"]),
                             outputs: Some(string_vec!["b.ts"]),
@@ -521,7 +550,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b"]),
                             command: Some(String::from("newcmd")),
-                            deps: Some(string_vec!["b:standard"]),
+                            deps: stub_deps(&["b:standard"]),
                             env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                             inputs: Some(string_vec!["b.*"]),
                             outputs: Some(string_vec!["b.ts"]),
@@ -552,7 +581,7 @@ mod tasks {
                     TaskConfig {
                         args: Some(string_vec!["--a"]),
                         command: Some(String::from("standard")),
-                        deps: Some(string_vec!["a:standard"]),
+                        deps: stub_deps(&["a:standard"]),
                         env: Some(stub_global_env_vars()),
                         inputs: Some(string_vec!["a.*"]),
                         outputs: Some(string_vec!["a.ts"]),
@@ -563,6 +592,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -576,7 +606,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b"]),
                             command: None,
-                            deps: Some(string_vec!["b:standard"]),
+                            deps: stub_deps(&["b:standard"]),
                             env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                             inputs: Some(string_vec!["b.*"]),
                             outputs: Some(string_vec!["b.ts"]),
@@ -596,7 +626,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--a", "--b"]),
                             command: Some(String::from("standard")),
-                            deps: Some(string_vec!["a:standard", "b:standard"]),
+                            deps: stub_deps(&["a:standard", "b:standard"]),
                             env: Some(HashMap::from([
                                 ("GLOBAL".to_owned(), "1".to_owned()),
                                 ("KEY".to_owned(), "b".to_owned())
@@ -630,7 +660,7 @@ mod tasks {
                     TaskConfig {
                         args: Some(string_vec!["--a"]),
                         command: Some(String::from("standard")),
-                        deps: Some(string_vec!["a:standard"]),
+                        deps: stub_deps(&["a:standard"]),
                         env: Some(stub_global_env_vars()),
                         inputs: Some(string_vec!["a.*"]),
                         outputs: Some(string_vec!["a.ts"]),
@@ -641,6 +671,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -654,7 +685,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b"]),
                             command: Some(String::from("newcmd")),
-                            deps: Some(string_vec!["b:standard"]),
+                            deps: stub_deps(&["b:standard"]),
                             env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                             inputs: Some(string_vec!["b.*"]),
                             outputs: Some(string_vec!["b.ts"]),
@@ -674,7 +705,7 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b", "--a"]),
                             command: Some(String::from("newcmd")),
-                            deps: Some(string_vec!["b:standard", "a:standard"]),
+                            deps: stub_deps(&["b:standard", "a:standard"]),
                             env: Some(HashMap::from([
                                 ("GLOBAL".to_owned(), "1".to_owned()),
                                 ("KEY".to_owned(), "a".to_owned())
@@ -708,7 +739,7 @@ mod tasks {
                     TaskConfig {
                         args: Some(string_vec!["--a"]),
                         command: Some(String::from("standard")),
-                        deps: Some(string_vec!["a:standard"]),
+                        deps: stub_deps(&["a:standard"]),
                         env: Some(stub_global_env_vars()),
                         inputs: Some(string_vec!["a.*"]),
                         outputs: Some(string_vec!["a.ts"]),
@@ -719,6 +750,7 @@ mod tasks {
                 ..GlobalProjectConfig::default()
             },
             &[],
+            EXTENDS_CACHE_TTL,
         )
         .unwrap();
 
@@ -727,12 +759,13 @@ mod tasks {
             TaskConfig {
                 args: Some(string_vec!["--a", "--b"]),
                 command: Some(String::from("standard")),
-                deps: Some(string_vec!["b:standard", "a:standard"]),
+                deps: stub_deps(&["b:standard", "a:standard"]),
                 env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                 inputs: Some(string_vec!["b.*"]),
                 outputs: Some(string_vec!["a.ts", "b.ts"]),
                 options: TaskOptionsConfig {
                     cache: Some(true),
+                    env_file: None,
                     merge_args: Some(TaskMergeStrategy::Append),
                     merge_deps: Some(TaskMergeStrategy::Prepend),
                     merge_env: Some(TaskMergeStrategy::Replace),
@@ -743,6 +776,7 @@ mod tasks {
                     run_deps_in_parallel: Some(true),
                     run_in_ci: Some(true),
                     run_from_workspace_root: None,
+                    shell: None,
                 },
                 type_of: PlatformType::Unknown,
             },
@@ -762,12 +796,13 @@ mod tasks {
                         TaskConfig {
                             args: Some(string_vec!["--b"]),
                             command: None,
-                            deps: Some(string_vec!["b:standard"]),
+                            deps: stub_deps(&["b:standard"]),
                             env: Some(HashMap::from([("KEY".to_owned(), "b".to_owned())])),
                             inputs: Some(string_vec!["b.*"]),
                             outputs: Some(string_vec!["b.ts"]),
                             options: TaskOptionsConfig {
                                 cache: None,
+                                env_file: None,
                                 merge_args: Some(TaskMergeStrategy::Append),
                                 merge_deps: Some(TaskMergeStrategy::Prepend),
                                 merge_env: Some(TaskMergeStrategy::Replace),
@@ -778,6 +813,7 @@ mod tasks {
                                 run_deps_in_parallel: None,
                                 run_in_ci: None,
                                 run_from_workspace_root: None,
+                                shell: None,
                             },
                             type_of: PlatformType::Unknown,
                         }
@@ -805,12 +841,16 @@ mod tasks {
                 &get_fixtures_dir("task-deps"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
             assert_eq!(
                 project.tasks.get("lint").unwrap().deps,
-                string_vec!["id:clean", "id:build"]
+                vec![
+                    TaskDependencyConfig::new("id:clean"),
+                    TaskDependencyConfig::new("id:build")
+                ]
             );
         }
 
@@ -822,12 +862,13 @@ mod tasks {
                 &get_fixtures_dir("task-deps"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
             assert_eq!(
                 project.tasks.get("lint").unwrap().deps,
-                string_vec!["id:build"]
+                vec![TaskDependencyConfig::new("id:build")]
             );
         }
 
@@ -839,12 +880,17 @@ mod tasks {
                 &get_fixtures_dir("task-deps"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
             assert_eq!(
                 project.tasks.get("build").unwrap().deps,
-                string_vec!["foo:build", "bar:build", "baz:build"]
+                vec![
+                    TaskDependencyConfig::new("foo:build"),
+                    TaskDependencyConfig::new("bar:build"),
+                    TaskDependencyConfig::new("baz:build")
+                ]
             );
         }
 
@@ -856,12 +902,48 @@ mod tasks {
                 &get_fixtures_dir("task-deps"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
             assert_eq!(
                 project.tasks.get("build").unwrap().deps,
-                string_vec!["foo:build", "bar:build", "baz:build"]
+                vec![
+                    TaskDependencyConfig::new("foo:build"),
+                    TaskDependencyConfig::new("bar:build"),
+                    TaskDependencyConfig::new("baz:build")
+                ]
+            );
+        }
+
+        #[test]
+        fn resolves_object_deps_with_overrides() {
+            let project = Project::new(
+                "id",
+                "overrides",
+                &get_fixtures_dir("task-deps"),
+                &mock_global_project_config(),
+                &[],
+                EXTENDS_CACHE_TTL,
+            )
+            .unwrap();
+
+            let with_args = |target: &str| TaskDependencyConfig {
+                args: string_vec!["--mode", "production"],
+                ..TaskDependencyConfig::new(target)
+            };
+
+            assert_eq!(
+                project.tasks.get("build").unwrap().deps,
+                vec![
+                    with_args("foo:build"),
+                    with_args("bar:build"),
+                    TaskDependencyConfig {
+                        env: HashMap::from([("NODE_ENV".to_owned(), "production".to_owned())]),
+                        ..TaskDependencyConfig::new("id:clean")
+                    },
+                    TaskDependencyConfig::new("id:clean"),
+                ]
             );
         }
 
@@ -874,6 +956,7 @@ mod tasks {
                 &get_fixtures_dir("task-deps"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
         }
@@ -913,6 +996,7 @@ mod tasks {
                     ..GlobalProjectConfig::default()
                 },
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -986,6 +1070,7 @@ mod tasks {
                     ..GlobalProjectConfig::default()
                 },
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1051,6 +1136,7 @@ mod tasks {
                     ..GlobalProjectConfig::default()
                 },
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1106,6 +1192,7 @@ mod tasks {
                     ..GlobalProjectConfig::default()
                 },
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1163,6 +1250,7 @@ mod tasks {
                     "/.moon/$taskType-$projectType.yml".to_owned(),
                     "*.yml".to_owned(),
                 ],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1240,6 +1328,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1254,6 +1343,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1268,6 +1358,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1282,6 +1373,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1296,6 +1388,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1310,6 +1403,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1328,6 +1422,7 @@ mod workspace {
                 &workspace_root,
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1352,6 +1447,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1366,6 +1462,7 @@ mod workspace {
                 &get_fixtures_dir("task-inheritance"),
                 &mock_global_project_config(),
                 &[],
+                EXTENDS_CACHE_TTL,
             )
             .unwrap();
 
@@ -1373,3 +1470,142 @@ mod workspace {
         }
     }
 }
+
+mod scoped_global_configs {
+    use super::*;
+
+    fn mock_task_config(command: &str, args: &[&str]) -> TaskConfig {
+        TaskConfig {
+            command: Some(command.to_owned()),
+            args: Some(args.iter().map(|arg| arg.to_string()).collect()),
+            ..TaskConfig::default()
+        }
+    }
+
+    fn mock_scoped_global_project_config() -> GlobalProjectConfig {
+        GlobalProjectConfig {
+            tasks: BTreeMap::from([
+                ("build".to_owned(), mock_task_config("tsc", &["--build"])),
+                ("lint".to_owned(), mock_task_config("eslint", &["."])),
+            ]),
+            scoped_configs: BTreeMap::from([
+                (
+                    "bash".to_owned(),
+                    GlobalProjectConfig {
+                        tasks: BTreeMap::from([(
+                            "shellcheck".to_owned(),
+                            mock_task_config("shellcheck", &[]),
+                        )]),
+                        ..GlobalProjectConfig::default()
+                    },
+                ),
+                (
+                    "typescript".to_owned(),
+                    GlobalProjectConfig {
+                        tasks: BTreeMap::from([
+                            (
+                                "lint".to_owned(),
+                                TaskConfig {
+                                    args: Some(string_vec!["--ext", ".ts"]),
+                                    ..TaskConfig::default()
+                                },
+                            ),
+                            (
+                                "typecheck".to_owned(),
+                                mock_task_config("tsc", &["--noEmit"]),
+                            ),
+                        ]),
+                        ..GlobalProjectConfig::default()
+                    },
+                ),
+                (
+                    "typescript-application".to_owned(),
+                    GlobalProjectConfig {
+                        tasks: BTreeMap::from([(
+                            "build".to_owned(),
+                            TaskConfig {
+                                args: Some(string_vec!["--verbose"]),
+                                ..TaskConfig::default()
+                            },
+                        )]),
+                        ..GlobalProjectConfig::default()
+                    },
+                ),
+            ]),
+            ..GlobalProjectConfig::default()
+        }
+    }
+
+    #[test]
+    fn inherits_language_and_type_configs_in_order() {
+        let project = Project::new(
+            "advanced",
+            "advanced",
+            &get_fixtures_dir("projects"),
+            &mock_scoped_global_project_config(),
+            &[],
+            EXTENDS_CACHE_TTL,
+        )
+        .unwrap();
+
+        assert_eq!(
+            project.tasks.keys().cloned().collect::<Vec<_>>(),
+            string_vec!["build", "lint", "typecheck"]
+        );
+        assert_eq!(
+            project.get_task("build").unwrap().args,
+            string_vec!["--build", "--verbose"]
+        );
+        assert_eq!(
+            project.get_task("lint").unwrap().args,
+            string_vec![".", "--ext", ".ts"]
+        );
+        assert_eq!(
+            project.task_sources,
+            BTreeMap::from([
+                (
+                    "build".to_owned(),
+                    string_vec![
+                        ".moon/project.yml",
+                        ".moon/tasks/typescript-application.yml"
+                    ]
+                ),
+                (
+                    "lint".to_owned(),
+                    string_vec![".moon/project.yml", ".moon/tasks/typescript.yml"]
+                ),
+                (
+                    "typecheck".to_owned(),
+                    string_vec![".moon/tasks/typescript.yml"]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn skips_configs_for_other_types() {
+        let project = Project::new(
+            "tasks",
+            "tasks",
+            &get_fixtures_dir("projects"),
+            &mock_scoped_global_project_config(),
+            &[],
+            EXTENDS_CACHE_TTL,
+        )
+        .unwrap();
+
+        assert_eq!(
+            project.get_task("build").unwrap().args,
+            string_vec!["--build"]
+        );
+        assert_eq!(
+            project.task_sources.get("lint").unwrap(),
+            &string_vec![
+                ".moon/project.yml",
+                ".moon/tasks/typescript.yml",
+                "moon.yml"
+            ]
+        );
+        assert!(!project.tasks.contains_key("shellcheck"));
+    }
+}
@@ -6,7 +6,10 @@ pub mod test;
 mod token;
 mod types;
 
-pub use moon_config::{PlatformType, TargetID, TaskConfig, TaskID, TaskOptionsConfig};
+pub use moon_config::{
+    PlatformType, TargetID, TaskConfig, TaskDependency, TaskDependencyConfig, TaskID,
+    TaskOptionsConfig,
+};
 
 pub use errors::*;
 pub use file_group::FileGroup;
@@ -4,11 +4,13 @@ use crate::token::TokenResolver;
 use crate::types::{EnvVars, TouchedFilePaths};
 use moon_config::{
     DependencyConfig, FileGlob, FilePath, InputValue, PlatformType, TargetID, TaskConfig,
-    TaskMergeStrategy, TaskOptionsConfig, TaskOutputStyle,
+    TaskDependency, TaskDependencyConfig, TaskMergeStrategy, TaskOptionEnvFile, TaskOptionShell,
+    TaskOptionsConfig, TaskOutputStyle,
 };
 use moon_logger::{color, debug, trace, Logable};
-use moon_utils::{glob, path, regex::ENV_VAR, string_vec};
-use serde::{Deserialize, Serialize};
+use moon_utils::regex::{Captures, ENV_VAR, ENV_VAR_SUBSTITUTE};
+use moon_utils::{glob, path, string_vec};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashSet;
 use std::env;
 use std::path::PathBuf;
@@ -18,6 +20,8 @@ use std::path::PathBuf;
 pub struct TaskOptions {
     pub cache: bool,
 
+    pub env_file: Option<String>,
+
     pub merge_args: TaskMergeStrategy,
 
     pub merge_deps: TaskMergeStrategy,
@@ -37,12 +41,15 @@ pub struct TaskOptions {
     pub run_in_ci: bool,
 
     pub run_from_workspace_root: bool,
+
+    pub shell: Option<String>,
 }
 
 impl Default for TaskOptions {
     fn default() -> Self {
         TaskOptions {
             cache: true,
+            env_file: None,
             merge_args: TaskMergeStrategy::Append,
             merge_deps: TaskMergeStrategy::Append,
             merge_env: TaskMergeStrategy::Append,
@@ -53,12 +60,17 @@ impl Default for TaskOptions {
             run_deps_in_parallel: true,
             run_in_ci: true,
             run_from_workspace_root: false,
+            shell: None,
         }
     }
 }
 
 impl TaskOptions {
     pub fn merge(&mut self, config: &TaskOptionsConfig) {
+        if let Some(env_file) = &config.env_file {
+            self.env_file = env_file.to_option();
+        }
+
         if let Some(merge_args) = &config.merge_args {
             self.merge_args = merge_args.clone();
         }
@@ -98,6 +110,10 @@ impl TaskOptions {
         if let Some(run_from_workspace_root) = &config.run_from_workspace_root {
             self.run_from_workspace_root = *run_from_workspace_root;
         }
+
+        if let Some(shell) = &config.shell {
+            self.shell = shell.to_option();
+        }
     }
 
     pub fn to_config(&self) -> TaskOptionsConfig {
@@ -106,6 +122,10 @@ impl TaskOptions {
 
         // Skip merge options until we need them
 
+        if let Some(env_file) = &self.env_file {
+            config.env_file = Some(TaskOptionEnvFile::File(env_file.clone()));
+        }
+
         if let Some(output_style) = &self.output_style {
             config.output_style = Some(output_style.clone());
         }
@@ -126,10 +146,74 @@ impl TaskOptions {
             config.run_from_workspace_root = Some(self.run_from_workspace_root);
         }
 
+        if let Some(shell) = &self.shell {
+            config.shell = Some(TaskOptionShell::Shell(shell.clone()));
+        }
+
         config
     }
 }
 
+// Serialize deps without args or env vars as plain targets, so that the JSON
+// shape of existing deps is preserved for consumers of `moon project --json`
+fn serialize_deps<S>(deps: &[TaskDependencyConfig], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(deps.iter().map(|dep| TaskDependency::from(dep.clone())))
+}
+
+fn deserialize_deps<'de, D>(deserializer: D) -> Result<Vec<TaskDependencyConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Vec::<TaskDependency>::deserialize(deserializer)?
+        .iter()
+        .map(|dep| dep.to_config())
+        .collect())
+}
+
+/// Interpolate `${VAR}` and `${VAR:-default}` occurrences within a value. Variables are
+/// first looked up in the local env map (excluding those currently being resolved, to
+/// avoid cycles), and then the process environment. Names of variables pulled from the
+/// process environment are tracked, so that they can be hashed.
+fn interpolate_env_vars(
+    value: &str,
+    local_env: &EnvVars,
+    system_vars: &mut HashSet<String>,
+    resolving: &[&str],
+) -> String {
+    if !value.contains("${") {
+        return value.to_owned();
+    }
+
+    ENV_VAR_SUBSTITUTE
+        .replace_all(value, |caps: &Captures| {
+            let name = caps.get(1).unwrap().as_str();
+            let fallback = caps.get(2).map(|m| m.as_str());
+
+            let resolved = match local_env.get(name) {
+                Some(local_value) if !resolving.contains(&name) => {
+                    let mut next_resolving = resolving.to_vec();
+                    next_resolving.push(name);
+
+                    interpolate_env_vars(local_value, local_env, system_vars, &next_resolving)
+                }
+                _ => {
+                    system_vars.insert(name.to_owned());
+
+                    env::var(name).unwrap_or_default()
+                }
+            };
+
+            match fallback {
+                Some(fallback) if resolved.is_empty() => fallback.to_owned(),
+                _ => resolved,
+            }
+        })
+        .to_string()
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
@@ -137,7 +221,11 @@ pub struct Task {
 
     pub command: String,
 
-    pub deps: Vec<TargetID>,
+    #[serde(
+        serialize_with = "serialize_deps",
+        deserialize_with = "deserialize_deps"
+    )]
+    pub deps: Vec<TaskDependencyConfig>,
 
     pub env: EnvVars,
 
@@ -149,6 +237,13 @@ pub struct Task {
 
     pub input_vars: HashSet<String>,
 
+    pub interpolated_vars: HashSet<String>,
+
+    // Resolved env values and args that contain interpolated values,
+    // which may be secrets, so they must not be hashed as plain text
+    #[serde(skip)]
+    pub interpolated_values: HashSet<String>,
+
     #[serde(skip)]
     pub log_target: String,
 
@@ -192,15 +287,23 @@ impl Task {
         let task = Task {
             args: cloned_config.args.unwrap_or_default(),
             command,
-            deps: cloned_config.deps.unwrap_or_default(),
+            deps: cloned_config
+                .deps
+                .unwrap_or_default()
+                .iter()
+                .map(|d| d.to_config())
+                .collect(),
             env: cloned_config.env.unwrap_or_default(),
             inputs: cloned_config.inputs.unwrap_or_else(|| string_vec!["**/*"]),
             input_vars: HashSet::new(),
             input_globs: HashSet::new(),
             input_paths: HashSet::new(),
+            interpolated_vars: HashSet::new(),
+            interpolated_values: HashSet::new(),
             log_target,
             options: TaskOptions {
                 cache: cloned_options.cache.unwrap_or(!is_long_running),
+                env_file: cloned_options.env_file.and_then(|f| f.to_option()),
                 merge_args: cloned_options.merge_args.unwrap_or_default(),
                 merge_deps: cloned_options.merge_deps.unwrap_or_default(),
                 merge_env: cloned_options.merge_env.unwrap_or_default(),
@@ -211,6 +314,7 @@ impl Task {
                 run_deps_in_parallel: cloned_options.run_deps_in_parallel.unwrap_or(true),
                 run_in_ci: cloned_options.run_in_ci.unwrap_or(!is_long_running),
                 run_from_workspace_root: cloned_options.run_from_workspace_root.unwrap_or_default(),
+                shell: cloned_options.shell.and_then(|s| s.to_option()),
             },
             outputs: cloned_config.outputs.unwrap_or_default(),
             output_paths: HashSet::new(),
@@ -240,7 +344,12 @@ impl Task {
         }
 
         if !self.deps.is_empty() {
-            config.deps = Some(self.deps.clone());
+            config.deps = Some(
+                self.deps
+                    .iter()
+                    .map(|d| TaskDependency::from(d.clone()))
+                    .collect(),
+            );
         }
 
         if !self.env.is_empty() {
@@ -319,31 +428,78 @@ impl Task {
             Ok(arg)
         };
 
+        // Interpolate env vars first, as they may resolve to tokens
+        let mut interpolated_vars = HashSet::new();
+        let raw_args = self
+            .args
+            .iter()
+            .map(|arg| interpolate_env_vars(arg, &self.env, &mut interpolated_vars, &[]))
+            .collect::<Vec<String>>();
+
+        let mut interpolated_values = HashSet::new();
+
         // We cant use `TokenResolver.resolve` as args are a mix of strings,
         // strings with tokens, and file paths when tokens are resolved.
-        for arg in &self.args {
+        for (original_arg, arg) in self.args.iter().zip(&raw_args) {
+            let mut resolved_args = vec![];
+
             if token_resolver.has_token_func(arg) {
                 let (paths, globs) = token_resolver.resolve_func(arg, self)?;
 
                 for path in paths {
-                    args.push(handle_path(path, false)?);
+                    resolved_args.push(handle_path(path, false)?);
                 }
 
                 for glob in globs {
-                    args.push(handle_path(PathBuf::from(glob), true)?);
+                    resolved_args.push(handle_path(PathBuf::from(glob), true)?);
                 }
             } else if token_resolver.has_token_var(arg) {
-                args.push(token_resolver.resolve_vars(arg, self)?);
+                resolved_args.push(token_resolver.resolve_vars(arg, self)?);
             } else {
-                args.push(arg.clone());
+                resolved_args.push(arg.clone());
             }
+
+            if original_arg != arg {
+                interpolated_values.extend(resolved_args.iter().cloned());
+            }
+
+            args.extend(resolved_args);
         }
 
         self.args = args;
+        self.interpolated_vars.extend(interpolated_vars);
+        self.interpolated_values.extend(interpolated_values);
 
         Ok(())
     }
 
+    /// Expand the env map by interpolating `${VAR}` and `${VAR:-default}` values,
+    /// from other keys in the same map, or from the process environment.
+    pub fn expand_env(&mut self) {
+        if self.env.is_empty() {
+            return;
+        }
+
+        let mut env = EnvVars::new();
+
+        for (key, value) in &self.env {
+            let resolved_value = interpolate_env_vars(
+                value,
+                &self.env,
+                &mut self.interpolated_vars,
+                &[key.as_str()],
+            );
+
+            if &resolved_value != value {
+                self.interpolated_values.insert(resolved_value.clone());
+            }
+
+            env.insert(key.to_owned(), resolved_value);
+        }
+
+        self.env = env;
+    }
+
     /// Expand the deps list and resolve parent/self scopes.
     pub fn expand_deps(
         &mut self,
@@ -354,32 +510,37 @@ impl Task {
             return Ok(());
         }
 
-        let mut deps: Vec<String> = vec![];
+        let mut deps: Vec<TaskDependencyConfig> = vec![];
 
         // Dont use a `HashSet` as we want to preserve order
-        let mut push_dep = |dep: String| {
+        let mut push_dep = |dep: &TaskDependencyConfig, target: String| {
+            let dep = TaskDependencyConfig {
+                target,
+                ..dep.clone()
+            };
+
             if !deps.contains(&dep) {
                 deps.push(dep);
             }
         };
 
         for dep in &self.deps {
-            let target = Target::parse(dep)?;
+            let target = Target::parse(&dep.target)?;
 
             match &target.project {
                 // ^:task
                 TargetProjectScope::Deps => {
                     for dep_cfg in depends_on {
-                        push_dep(Target::format(&dep_cfg.id, &target.task_id)?);
+                        push_dep(dep, Target::format(&dep_cfg.id, &target.task_id)?);
                     }
                 }
                 // ~:task
                 TargetProjectScope::Own => {
-                    push_dep(Target::format(owner_id, &target.task_id)?);
+                    push_dep(dep, Target::format(owner_id, &target.task_id)?);
                 }
                 // project:task
                 TargetProjectScope::Id(_) => {
-                    push_dep(dep.clone());
+                    push_dep(dep, dep.target.clone());
                 }
                 _ => {
                     target.fail_with(TargetError::NoProjectAllInTaskDeps(target.id.clone()))?;
@@ -442,6 +603,17 @@ impl Task {
         Ok(())
     }
 
+    /// Return the shell that the command line should be ran through, if any.
+    /// Bun, Deno, and Node tasks are executed through the toolchain, and never use a shell.
+    pub fn get_shell(&self) -> Option<&str> {
+        match self.platform {
+            PlatformType::Bun | PlatformType::Deno | PlatformType::Node | PlatformType::Python => {
+                None
+            }
+            _ => self.options.shell.as_deref(),
+        }
+    }
+
     /// Return true if this task is affected, based on touched files.
     /// Will attempt to find any file that matches our list of inputs.
     pub fn is_affected(&self, touched_files: &TouchedFilePaths) -> Result<bool, TaskError> {
@@ -503,81 +675,29 @@ impl Task {
         }
 
         if let Some(args) = &config.args {
-            self.args = self.merge_string_vec(&self.args, args, &self.options.merge_args);
+            self.args = self.options.merge_args.merge_vec(&self.args, args);
         }
 
         if let Some(deps) = &config.deps {
-            self.deps = self.merge_string_vec(&self.deps, deps, &self.options.merge_deps);
+            let deps = deps.iter().map(|d| d.to_config()).collect::<Vec<_>>();
+
+            self.deps = self.options.merge_deps.merge_vec(&self.deps, &deps);
         }
 
         if let Some(env) = &config.env {
-            self.env = self.merge_env_vars(&self.env, env, &self.options.merge_env);
+            self.env = self.options.merge_env.merge_map(&self.env, env);
         }
 
         if let Some(inputs) = &config.inputs {
-            self.inputs = self.merge_string_vec(&self.inputs, inputs, &self.options.merge_inputs);
+            self.inputs = self.options.merge_inputs.merge_vec(&self.inputs, inputs);
         }
 
         if let Some(outputs) = &config.outputs {
-            self.outputs =
-                self.merge_string_vec(&self.outputs, outputs, &self.options.merge_outputs);
+            self.outputs = self.options.merge_outputs.merge_vec(&self.outputs, outputs);
         }
     }
 
     pub fn should_run_in_ci(&self) -> bool {
         !self.outputs.is_empty() || self.options.run_in_ci
     }
-
-    fn merge_env_vars(
-        &self,
-        base: &EnvVars,
-        next: &EnvVars,
-        strategy: &TaskMergeStrategy,
-    ) -> EnvVars {
-        match strategy {
-            TaskMergeStrategy::Append => {
-                let mut map = base.clone();
-                map.extend(next.clone());
-                map
-            }
-            TaskMergeStrategy::Prepend => {
-                let mut map = next.clone();
-                map.extend(base.clone());
-                map
-            }
-            TaskMergeStrategy::Replace => next.clone(),
-        }
-    }
-
-    fn merge_string_vec(
-        &self,
-        base: &[String],
-        next: &[String],
-        strategy: &TaskMergeStrategy,
-    ) -> Vec<String> {
-        let mut list: Vec<String> = vec![];
-
-        // This is easier than .extend() as we need to clone the inner string
-        let mut merge = |inner_list: &[String]| {
-            for item in inner_list {
-                list.push(item.clone());
-            }
-        };
-
-        match strategy {
-            TaskMergeStrategy::Append => {
-                merge(base);
-                merge(next);
-            }
-            TaskMergeStrategy::Prepend => {
-                merge(next);
-                merge(base);
-            }
-            TaskMergeStrategy::Replace => {
-                merge(next);
-            }
-        }
-
-        list
-    }
 }
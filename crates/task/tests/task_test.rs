@@ -1,8 +1,8 @@
-use moon_config::TaskConfig;
+use moon_config::{TaskConfig, TaskDependency, TaskDependencyConfig};
 use moon_task::test::create_expanded_task;
 use moon_utils::test::get_fixtures_dir;
 use moon_utils::{glob, string_vec};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 
 #[test]
@@ -22,6 +22,50 @@ fn errors_for_output_glob() {
     .unwrap();
 }
 
+mod serialize {
+    use super::*;
+    use moon_task::Task;
+
+    #[test]
+    fn serializes_deps_without_overrides_as_strings() {
+        let task = Task {
+            deps: vec![
+                TaskDependencyConfig::new("a:build"),
+                TaskDependencyConfig {
+                    target: "b:build".into(),
+                    env: HashMap::from([("MODE".into(), "prod".into())]),
+                    ..TaskDependencyConfig::default()
+                },
+            ],
+            ..Task::default()
+        };
+
+        let value = serde_json::to_value(&task).unwrap();
+
+        assert_eq!(
+            value["deps"],
+            serde_json::json!(["a:build", { "target": "b:build", "env": { "MODE": "prod" } }])
+        );
+
+        let task: Task = serde_json::from_value(value).unwrap();
+
+        assert_eq!(
+            task.deps
+                .into_iter()
+                .map(TaskDependency::from)
+                .collect::<Vec<_>>(),
+            vec![
+                TaskDependency::String("a:build".into()),
+                TaskDependency::Object(TaskDependencyConfig {
+                    target: "b:build".into(),
+                    env: HashMap::from([("MODE".into(), "prod".into())]),
+                    ..TaskDependencyConfig::default()
+                }),
+            ]
+        );
+    }
+}
+
 mod is_affected {
     use super::*;
 
@@ -230,3 +274,155 @@ mod expand_inputs {
         );
     }
 }
+
+mod expand_env {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn interpolates_system_vars() {
+        env::set_var("MOON_INTERP_SYSTEM", "system");
+
+        let workspace_root = get_fixtures_dir("base");
+        let project_root = workspace_root.join("files-and-dirs");
+        let task = create_expanded_task(
+            &workspace_root,
+            &project_root,
+            Some(TaskConfig {
+                env: Some(HashMap::from([(
+                    "KEY".to_owned(),
+                    "value-${MOON_INTERP_SYSTEM}".to_owned(),
+                )])),
+                ..TaskConfig::default()
+            }),
+        )
+        .unwrap();
+
+        env::remove_var("MOON_INTERP_SYSTEM");
+
+        assert_eq!(task.env.get("KEY").unwrap(), "value-system");
+        assert_eq!(
+            task.interpolated_vars,
+            HashSet::from(["MOON_INTERP_SYSTEM".to_owned()])
+        );
+        assert_eq!(
+            task.interpolated_values,
+            HashSet::from(["value-system".to_owned()])
+        );
+    }
+
+    #[test]
+    fn interpolates_local_vars_before_system() {
+        env::set_var("MOON_INTERP_LOCAL", "system");
+
+        let workspace_root = get_fixtures_dir("base");
+        let project_root = workspace_root.join("files-and-dirs");
+        let task = create_expanded_task(
+            &workspace_root,
+            &project_root,
+            Some(TaskConfig {
+                env: Some(HashMap::from([
+                    ("MOON_INTERP_LOCAL".to_owned(), "local".to_owned()),
+                    ("KEY".to_owned(), "${MOON_INTERP_LOCAL}".to_owned()),
+                ])),
+                ..TaskConfig::default()
+            }),
+        )
+        .unwrap();
+
+        env::remove_var("MOON_INTERP_LOCAL");
+
+        assert_eq!(task.env.get("KEY").unwrap(), "local");
+        assert!(task.interpolated_vars.is_empty());
+    }
+
+    #[test]
+    fn references_system_var_when_self_referencing() {
+        env::set_var("MOON_INTERP_SELF", "/usr/bin");
+
+        let workspace_root = get_fixtures_dir("base");
+        let project_root = workspace_root.join("files-and-dirs");
+        let task = create_expanded_task(
+            &workspace_root,
+            &project_root,
+            Some(TaskConfig {
+                env: Some(HashMap::from([(
+                    "MOON_INTERP_SELF".to_owned(),
+                    "./bin:${MOON_INTERP_SELF}".to_owned(),
+                )])),
+                ..TaskConfig::default()
+            }),
+        )
+        .unwrap();
+
+        env::remove_var("MOON_INTERP_SELF");
+
+        assert_eq!(task.env.get("MOON_INTERP_SELF").unwrap(), "./bin:/usr/bin");
+    }
+
+    #[test]
+    fn uses_default_when_missing() {
+        let workspace_root = get_fixtures_dir("base");
+        let project_root = workspace_root.join("files-and-dirs");
+        let task = create_expanded_task(
+            &workspace_root,
+            &project_root,
+            Some(TaskConfig {
+                env: Some(HashMap::from([(
+                    "KEY".to_owned(),
+                    "${MOON_INTERP_MISSING:-fallback}".to_owned(),
+                )])),
+                ..TaskConfig::default()
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(task.env.get("KEY").unwrap(), "fallback");
+        assert_eq!(
+            task.interpolated_vars,
+            HashSet::from(["MOON_INTERP_MISSING".to_owned()])
+        );
+    }
+
+    #[test]
+    fn interpolates_args() {
+        env::set_var("MOON_INTERP_ARGS", "prod");
+
+        let workspace_root = get_fixtures_dir("base");
+        let project_root = workspace_root.join("files-and-dirs");
+        let task = create_expanded_task(
+            &workspace_root,
+            &project_root,
+            Some(TaskConfig {
+                args: Some(string_vec![
+                    "--mode",
+                    "${MOON_INTERP_ARGS}",
+                    "--port=${MOON_INTERP_PORT:-3000}",
+                    "--name=${NAME}"
+                ]),
+                env: Some(HashMap::from([("NAME".to_owned(), "app".to_owned())])),
+                ..TaskConfig::default()
+            }),
+        )
+        .unwrap();
+
+        env::remove_var("MOON_INTERP_ARGS");
+
+        assert_eq!(
+            task.args,
+            string_vec!["--mode", "prod", "--port=3000", "--name=app"]
+        );
+        assert_eq!(
+            task.interpolated_vars,
+            HashSet::from(["MOON_INTERP_ARGS".to_owned(), "MOON_INTERP_PORT".to_owned()])
+        );
+        assert_eq!(
+            task.interpolated_values,
+            HashSet::from([
+                "prod".to_owned(),
+                "--port=3000".to_owned(),
+                "--name=app".to_owned()
+            ])
+        );
+    }
+}
@@ -14,6 +14,15 @@ pub enum ToolchainError {
     #[error("Unable to find a node module binary for <symbol>{0}</symbol>. Have you installed the corresponding package?")]
     MissingNodeModuleBin(String), // bin name
 
+    #[error("Unable to find a Python interpreter that satisfies <symbol>{0}</symbol>. Python is not installed by moon, so install it on your system, or configure <symbol>python.interpreter</symbol>.")]
+    MissingPythonInterpreter(String),
+
+    #[error("Toolchain usage <path>{0}</path> was recently missing or unreadable, so tools used by other workspaces may be unknown. Refusing to prune, try again later or with a lower <symbol>--days</symbol>.")]
+    UnreliableToolchainUsage(String),
+
+    #[error("Unable to resolve a Node.js version that satisfies <symbol>{0}</symbol>.")]
+    UnresolvedNodeVersion(String),
+
     #[error(transparent)]
     Archive(#[from] ArchiveError),
 
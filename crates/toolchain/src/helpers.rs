@@ -1,14 +1,15 @@
 use crate::errors::ToolchainError;
 use moon_archive::{untar, unzip};
 use moon_error::map_io_to_fs_error;
+use moon_lang::LangError;
 use moon_logger::{color, trace};
 use moon_utils::fs;
 use moon_utils::process::{output_to_trimmed_string, Command};
 use sha2::{Digest, Sha256};
 use std::env;
 use std::fs::File;
-use std::io;
-use std::path::Path;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
 pub const LOG_TARGET: &str = "moon:toolchain";
 
@@ -57,6 +58,33 @@ pub fn get_file_sha256_hash(path: &Path) -> Result<String, ToolchainError> {
     Ok(hash)
 }
 
+/// Verify the downloaded file against a list of SHA-256 checksums, where each line
+/// is formatted as `<hash>  <file name>`.
+// https://github.com/nodejs/node#verifying-binaries
+#[track_caller]
+pub fn verify_shasum(
+    download_url: &str,
+    download_path: &Path,
+    shasums_path: &Path,
+) -> Result<(), ToolchainError> {
+    let sha_hash = get_file_sha256_hash(download_path)?;
+    let file_name = download_path.file_name().unwrap().to_str().unwrap();
+    let file_handle =
+        File::open(shasums_path).map_err(|e| map_io_to_fs_error(e, shasums_path.to_path_buf()))?;
+
+    for line in BufReader::new(file_handle).lines().flatten() {
+        // hash1923hnsdouahsd91houn79h1beyasdpaksdm  node-vx.x.x-darwin-arm64.tar.gz
+        if line.starts_with(sha_hash.as_str()) && line.ends_with(file_name) {
+            return Ok(());
+        }
+    }
+
+    Err(ToolchainError::Lang(LangError::InvalidShasum(
+        String::from(download_path.to_string_lossy()),
+        String::from(download_url),
+    )))
+}
+
 /// We need to ensure that our toolchain binaries are executed instead of
 /// other binaries of the same name. Otherwise, tooling like nvm will
 /// intercept execution and break our processes. We can work around this
@@ -70,6 +98,38 @@ pub fn get_path_env_var(bin_dir: &Path) -> std::ffi::OsString {
     env::join_paths(paths).unwrap()
 }
 
+/// Format a distribution URL template by replacing the `{version}`
+/// and `{file}` placeholders.
+pub fn format_dist_url(template: &str, version: &str, file: &str) -> String {
+    template
+        .replace("{version}", version)
+        .replace("{file}", file)
+}
+
+/// Convert a `file://` URL into a local file system path.
+/// Returns `None` if the value is not a file URL.
+pub fn file_url_to_path(url: &str) -> Option<PathBuf> {
+    if !url.starts_with("file://") {
+        return None;
+    }
+
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.to_file_path().ok(),
+        Err(_) => None,
+    }
+}
+
+/// Return the location of a package tarball within a distribution mirror, which
+/// can be passed to `npm install`. Local mirrors are returned as file paths.
+pub fn get_dist_tarball(template: &str, package: &str, version: &str) -> String {
+    let url = format_dist_url(template, version, &format!("{}-{}.tgz", package, version));
+
+    match file_url_to_path(&url) {
+        Some(path) => path.to_string_lossy().to_string(),
+        None => url,
+    }
+}
+
 pub async fn download_file_from_url(url: &str, dest: &Path) -> Result<(), ToolchainError> {
     let handle_error = |e: io::Error| map_io_to_fs_error(e, dest.to_path_buf());
 
@@ -83,8 +143,15 @@ pub async fn download_file_from_url(url: &str, dest: &Path) -> Result<(), Toolch
     // Ensure parent directories exist
     fs::create_dir_all(dest.parent().unwrap()).await?;
 
+    // Copy the file from a local mirror
+    if let Some(source) = file_url_to_path(url) {
+        fs::copy_file(&source, dest).await?;
+
+        return Ok(());
+    }
+
     // Fetch the file from the HTTP source
-    let response = reqwest::get(url).await?;
+    let response = reqwest::get(url).await?.error_for_status()?;
 
     // Write the bytes to our local file
     let mut contents = io::Cursor::new(response.bytes().await?);
@@ -103,9 +170,9 @@ pub async fn unpack(
     fs::create_dir_all(output_dir).await?;
 
     if input_file.extension().unwrap() == "zip" {
-        unzip(input_file, output_dir, Some(prefix))?;
+        unzip(input_file, output_dir, Some(prefix), true)?;
     } else {
-        untar(input_file, output_dir, Some(prefix))?;
+        untar(input_file, output_dir, Some(prefix), true)?;
     }
 
     Ok(())
@@ -4,8 +4,10 @@ pub mod pms;
 mod toolchain;
 pub mod tools;
 mod traits;
+mod usage;
 
 pub use errors::ToolchainError;
 pub use helpers::get_path_env_var;
 pub use toolchain::Toolchain;
 pub use traits::{Downloadable, Executable, Installable, PackageManager, Tool};
+pub use usage::*;
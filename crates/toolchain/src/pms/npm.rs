@@ -1,5 +1,5 @@
 use crate::errors::ToolchainError;
-use crate::helpers::{get_bin_version, get_path_env_var};
+use crate::helpers::{get_bin_version, get_dist_tarball, get_path_env_var};
 use crate::tools::node::NodeTool;
 use crate::traits::{Executable, Installable, Lifecycle, PackageManager};
 use crate::Toolchain;
@@ -49,6 +49,23 @@ impl NpmTool {
         package: &str,
         version: &str,
     ) -> Result<(), ToolchainError> {
+        self.install_global_spec(&format!("{}@{}", package, version))
+            .await
+    }
+
+    /// Install a global dependency from a tarball within a distribution
+    /// mirror, instead of the npm registry.
+    pub async fn install_global_dep_from_dist(
+        &self,
+        package: &str,
+        version: &str,
+        dist_url: &str,
+    ) -> Result<(), ToolchainError> {
+        self.install_global_spec(&get_dist_tarball(dist_url, package, version))
+            .await
+    }
+
+    async fn install_global_spec(&self, spec: &str) -> Result<(), ToolchainError> {
         self.create_command()
             .args([
                 // We must install them to our install, and not the current environments
@@ -56,7 +73,7 @@ impl NpmTool {
                 self.install_dir.to_str().unwrap(),
                 "install",
                 "-g",
-                &format!("{}@{}", package, version),
+                spec,
             ])
             .exec_capture_output()
             .await?;
@@ -164,7 +181,16 @@ impl Installable<NodeTool> for NpmTool {
         let log_target = self.get_log_target();
         let package = format!("npm@{}", self.config.version);
 
-        if node.is_corepack_aware() {
+        if let Some(dist_url) = &self.config.dist_url {
+            debug!(
+                target: log_target,
+                "Installing package manager from {}",
+                color::url(dist_url)
+            );
+
+            self.install_global_dep_from_dist("npm", &self.config.version, dist_url)
+                .await?;
+        } else if node.is_corepack_aware() {
             debug!(
                 target: log_target,
                 "Enabling package manager with {}",
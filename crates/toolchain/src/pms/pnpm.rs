@@ -58,7 +58,7 @@ impl Installable<NodeTool> for PnpmTool {
         check_version: bool,
     ) -> Result<bool, ToolchainError> {
         if !self.is_executable()
-            || (!node.is_corepack_aware()
+            || (!node.is_corepack_enabled()
                 && !node.get_npm().is_global_dep_installed("pnpm").await?)
         {
             return Ok(false);
@@ -93,7 +93,16 @@ impl Installable<NodeTool> for PnpmTool {
         let npm = node.get_npm();
         let package = format!("pnpm@{}", self.config.version);
 
-        if node.is_corepack_aware() {
+        if let Some(dist_url) = &self.config.dist_url {
+            debug!(
+                target: log_target,
+                "Installing package manager from {}",
+                color::url(dist_url)
+            );
+
+            npm.install_global_dep_from_dist("pnpm", &self.config.version, dist_url)
+                .await?;
+        } else if node.is_corepack_aware() {
             debug!(
                 target: log_target,
                 "Enabling package manager with {}",
@@ -84,7 +84,7 @@ impl Installable<NodeTool> for YarnTool {
         check_version: bool,
     ) -> Result<bool, ToolchainError> {
         if !self.is_executable()
-            || (!node.is_corepack_aware()
+            || (!node.is_corepack_enabled()
                 && !node.get_npm().is_global_dep_installed("yarn").await?)
         {
             return Ok(false);
@@ -124,7 +124,16 @@ impl Installable<NodeTool> for YarnTool {
         let npm = node.get_npm();
         let package = format!("yarn@{}", self.config.version);
 
-        if node.is_corepack_aware() {
+        if let Some(dist_url) = &self.config.dist_url {
+            debug!(
+                target: log_target,
+                "Installing package manager from {}",
+                color::url(dist_url)
+            );
+
+            npm.install_global_dep_from_dist("yarn", &self.config.version, dist_url)
+                .await?;
+        } else if node.is_corepack_aware() {
             debug!(
                 target: log_target,
                 "Enabling package manager with {}",
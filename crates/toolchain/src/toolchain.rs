@@ -1,12 +1,17 @@
 use crate::errors::ToolchainError;
 use crate::helpers::LOG_TARGET;
-use crate::tools::node::NodeTool;
-use crate::traits::Tool;
-use moon_config::WorkspaceConfig;
+use crate::tools::bun::BunTool;
+use crate::tools::deno::DenoTool;
+use crate::tools::node::{self, NodeTool};
+use crate::tools::python::PythonTool;
+use crate::traits::{Installable, Tool};
+use crate::usage::{now_millis, InstalledTool, ToolchainUsage, ToolchainUsageLock};
+use moon_config::{NodeConfig, WorkspaceConfig};
 use moon_constants::CONFIG_DIRNAME;
-use moon_logger::{color, debug, trace};
+use moon_logger::{color, debug, trace, warn};
 use moon_utils::{fs, path};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 async fn create_dir(dir: &Path) -> Result<(), ToolchainError> {
     trace!(
@@ -43,7 +48,13 @@ pub struct Toolchain {
     pub workspace_root: PathBuf,
 
     // Tool instances are private, as we want to lazy load them.
+    bun: Option<BunTool>,
+
+    deno: Option<DenoTool>,
+
     node: Option<NodeTool>,
+
+    python: Option<PythonTool>,
 }
 
 impl Toolchain {
@@ -71,11 +82,26 @@ impl Toolchain {
             temp_dir,
             tools_dir,
             workspace_root: root_dir.to_path_buf(),
+            bun: None,
+            deno: None,
             node: None,
+            python: None,
         };
 
+        if let Some(bun_config) = &workspace_config.bun {
+            toolchain.bun = Some(BunTool::new(&toolchain, bun_config)?);
+        }
+
+        if let Some(deno_config) = &workspace_config.deno {
+            toolchain.deno = Some(DenoTool::new(&toolchain, deno_config)?);
+        }
+
         toolchain.node = Some(NodeTool::new(&toolchain, &workspace_config.node)?);
 
+        if let Some(python_config) = &workspace_config.python {
+            toolchain.python = Some(PythonTool::new(&toolchain, python_config)?);
+        }
+
         Ok(toolchain)
     }
 
@@ -91,6 +117,47 @@ impl Toolchain {
         .await
     }
 
+    /// Resolve the Node.js version to install. When not configured, the version
+    /// is detected from the workspace root, and ranges are resolved to the
+    /// highest matching version in the Node.js release index.
+    pub async fn resolve_node_version(
+        root_dir: &Path,
+        node_config: &mut NodeConfig,
+    ) -> Result<(), ToolchainError> {
+        if !node_config.version_configured {
+            if let Some(version) = node::detect_workspace_version(root_dir)? {
+                debug!(
+                    target: LOG_TARGET,
+                    "Detected Node.js version {} from workspace",
+                    color::symbol(&version)
+                );
+
+                node_config.version = version;
+            }
+        }
+
+        let cache_dir = path::get_home_dir()
+            .ok_or(ToolchainError::MissingHomeDir)?
+            .join(CONFIG_DIRNAME)
+            .join("tools")
+            .join("node");
+        let index_url = node::get_index_url(None, node_config.dist_url.as_deref());
+        let version = node::resolve_version(&node_config.version, &cache_dir, &index_url).await?;
+
+        if version != node_config.version {
+            debug!(
+                target: LOG_TARGET,
+                "Resolved Node.js version {} to {}",
+                color::symbol(&node_config.version),
+                color::symbol(&version)
+            );
+
+            node_config.version = version;
+        }
+
+        Ok(())
+    }
+
     /// Download and install all tools into the toolchain.
     /// Return a count of how many tools were installed.
     pub async fn setup(&mut self, check_versions: bool) -> Result<u8, ToolchainError> {
@@ -98,15 +165,185 @@ impl Toolchain {
 
         let mut installed = 0;
 
+        if self.bun.is_some() {
+            let mut bun = self.bun.take().unwrap();
+            installed += bun.run_setup(self, check_versions).await?;
+            self.bun = Some(bun);
+        }
+
+        if self.deno.is_some() {
+            let mut deno = self.deno.take().unwrap();
+            installed += deno.run_setup(self, check_versions).await?;
+            self.deno = Some(deno);
+        }
+
         if self.node.is_some() {
             let mut node = self.node.take().unwrap();
             installed += node.run_setup(self, check_versions).await?;
             self.node = Some(node);
         }
 
+        if self.python.is_some() {
+            let mut python = self.python.take().unwrap();
+            installed += python.run_setup(self, check_versions).await?;
+            self.python = Some(python);
+        }
+
+        // Tracking usage is only used for pruning, so it should never fail the setup
+        if let Err(error) = self.track_usage().await {
+            warn!(
+                target: LOG_TARGET,
+                "Unable to track toolchain usage: {}", error
+            );
+        }
+
         Ok(installed)
     }
 
+    /// Return identifiers ("<name>/<version>") for all configured tools
+    /// that are installed into the tools directory.
+    pub fn get_used_tools(&self) -> Result<Vec<String>, ToolchainError> {
+        let mut install_dirs = vec![];
+
+        if let Some(bun) = &self.bun {
+            install_dirs.push(bun.get_install_dir()?);
+        }
+
+        if let Some(deno) = &self.deno {
+            install_dirs.push(deno.get_install_dir()?);
+        }
+
+        if let Some(node) = &self.node {
+            install_dirs.push(node.get_install_dir()?);
+        }
+
+        // Python is provided by the system, so is never within the tools directory
+        let tools = install_dirs
+            .into_iter()
+            .filter_map(|dir| dir.strip_prefix(&self.tools_dir).ok())
+            .map(|dir| {
+                dir.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect();
+
+        Ok(tools)
+    }
+
+    /// Record the tools used by the current workspace, and when.
+    pub async fn track_usage(&self) -> Result<(), ToolchainError> {
+        let _lock = ToolchainUsageLock::acquire(&self.tools_dir).await?;
+        let mut usage = ToolchainUsage::load(&self.tools_dir).await?;
+
+        usage.track(&self.workspace_root, self.get_used_tools()?, now_millis());
+        usage.save().await?;
+
+        Ok(())
+    }
+
+    /// Return all tool versions that have been installed into the tools directory,
+    /// with their size on disk and when they were last used by a workspace.
+    pub async fn list_installed(&self) -> Result<Vec<InstalledTool>, ToolchainError> {
+        let usage = ToolchainUsage::load(&self.tools_dir).await?;
+        let mut tools = vec![];
+
+        for tool_entry in fs::read_dir(&self.tools_dir).await? {
+            let tool_dir = tool_entry.path();
+
+            if !tool_dir.is_dir() {
+                continue;
+            }
+
+            let name = tool_entry.file_name().to_string_lossy().to_string();
+
+            for version_entry in fs::read_dir(&tool_dir).await? {
+                let version_dir = version_entry.path();
+
+                if !version_dir.is_dir() {
+                    continue;
+                }
+
+                let version = version_entry.file_name().to_string_lossy().to_string();
+                let id = format!("{}/{}", name, version);
+                let mut size = 0;
+
+                for file in fs::read_dir_all(&version_dir).await? {
+                    if let Ok(meta) = file.metadata().await {
+                        size += meta.len();
+                    }
+                }
+
+                tools.push(InstalledTool {
+                    last_used_time: usage.get_last_used_time(&id),
+                    id,
+                    name: name.clone(),
+                    path: version_dir,
+                    size,
+                    version,
+                });
+            }
+        }
+
+        tools.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(tools)
+    }
+
+    /// Remove all tool versions that are not used by the current workspace,
+    /// or by any other workspace that has been used within the provided duration.
+    /// Return a list of tools that were removed.
+    pub async fn prune(&self, max_age: Duration) -> Result<Vec<InstalledTool>, ToolchainError> {
+        let lock = ToolchainUsageLock::acquire(&self.tools_dir).await?;
+        let mut usage = ToolchainUsage::load(&self.tools_dir).await?;
+        let now = now_millis();
+        let since = now.saturating_sub(max_age.as_millis());
+
+        // If the usage was reset within the duration, other workspaces may have
+        // been forgotten, and we would remove tools that are still in use
+        if usage.was_reset_since(since) {
+            return Err(ToolchainError::UnreliableToolchainUsage(
+                usage.path.to_string_lossy().to_string(),
+            ));
+        }
+
+        usage.remove_stale_workspaces(since);
+        usage.track(&self.workspace_root, self.get_used_tools()?, now);
+
+        let mut removed = vec![];
+
+        for tool in self.list_installed().await? {
+            if usage.get_last_used_time(&tool.id).is_some() {
+                continue;
+            }
+
+            // Removing tools may take a while, so keep the lock from going stale
+            lock.refresh()?;
+
+            debug!(
+                target: LOG_TARGET,
+                "Pruning unused tool {}",
+                color::path(&tool.path)
+            );
+
+            fs::remove_dir_all(&tool.path).await?;
+
+            // Also remove the downloaded archive, if it still exists
+            let temp_dir = self.temp_dir.join(&tool.name).join(&tool.version);
+
+            if temp_dir.exists() {
+                fs::remove_dir_all(&temp_dir).await?;
+            }
+
+            removed.push(tool);
+        }
+
+        usage.save().await?;
+
+        Ok(removed)
+    }
+
     /// Uninstall all tools from the toolchain, and delete any temporary files.
     pub async fn teardown(&mut self) -> Result<(), ToolchainError> {
         debug!(
@@ -114,16 +351,46 @@ impl Toolchain {
             "Tearing down toolchain, uninstalling tools",
         );
 
+        if self.bun.is_some() {
+            let mut bun = self.bun.take().unwrap();
+            bun.run_teardown(self).await?;
+        }
+
+        if self.deno.is_some() {
+            let mut deno = self.deno.take().unwrap();
+            deno.run_teardown(self).await?;
+        }
+
         if self.node.is_some() {
             let mut node = self.node.take().unwrap();
             node.run_teardown(self).await?;
         }
 
+        if self.python.is_some() {
+            let mut python = self.python.take().unwrap();
+            python.run_teardown(self).await?;
+        }
+
         Ok(())
     }
 
+    /// Return the Bun tool, if configured.
+    pub fn get_bun(&self) -> Option<&BunTool> {
+        self.bun.as_ref()
+    }
+
+    /// Return the Deno tool, if configured.
+    pub fn get_deno(&self) -> Option<&DenoTool> {
+        self.deno.as_ref()
+    }
+
     /// Return the Node.js tool.
     pub fn get_node(&self) -> &NodeTool {
         self.node.as_ref().unwrap()
     }
+
+    /// Return the Python tool, if configured.
+    pub fn get_python(&self) -> Option<&PythonTool> {
+        self.python.as_ref()
+    }
 }
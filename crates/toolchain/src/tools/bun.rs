@@ -0,0 +1,203 @@
+use crate::errors::ToolchainError;
+use crate::helpers::{
+    download_file_from_url, get_bin_version, get_path_env_var, unpack, verify_shasum,
+};
+use crate::traits::{Downloadable, Executable, Installable, Lifecycle, Tool};
+use crate::Toolchain;
+use async_trait::async_trait;
+use moon_config::BunConfig;
+use moon_lang_bun::{bun, BUN_INSTALL};
+use moon_logger::{color, debug, error, Logable};
+use moon_utils::process::Command;
+use moon_utils::{fs, is_ci};
+use std::env;
+use std::path::PathBuf;
+
+pub struct BunTool {
+    bin_path: PathBuf,
+
+    pub config: BunConfig,
+
+    download_path: Option<PathBuf>,
+
+    install_dir: PathBuf,
+
+    log_target: String,
+}
+
+impl BunTool {
+    pub fn new(toolchain: &Toolchain, config: &BunConfig) -> Result<BunTool, ToolchainError> {
+        let install_dir = toolchain.tools_dir.join("bun").join(&config.version);
+
+        Ok(BunTool {
+            bin_path: install_dir.join("bun"),
+            config: config.to_owned(),
+            // Bun isn't available on every platform, so avoid failing
+            // until it's actually downloaded or installed
+            download_path: bun::get_download_file().ok().map(|file| {
+                toolchain
+                    .temp_dir
+                    .join("bun")
+                    .join(&config.version)
+                    .join(file)
+            }),
+            install_dir,
+            log_target: String::from("moon:toolchain:bun"),
+        })
+    }
+
+    /// Create a command to run that wraps the binary.
+    pub fn create_command(&self) -> Command {
+        let mut cmd = Command::new(&self.bin_path);
+        cmd.env("PATH", get_path_env_var(&self.install_dir));
+        cmd
+    }
+
+    /// Return the lockfile name that `bun install` generates.
+    pub fn get_lock_filename(&self) -> String {
+        String::from(BUN_INSTALL.lock_filenames[0])
+    }
+
+    /// Install dependencies for the entire workspace.
+    pub async fn install_dependencies(&self, toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        let mut args = vec!["install"];
+
+        if is_ci() {
+            let lockfile = toolchain.workspace_root.join(self.get_lock_filename());
+
+            // Will fail with a frozen lockfile if one does not exist!
+            if lockfile.exists() {
+                args.push("--frozen-lockfile");
+            }
+        }
+
+        let mut cmd = self.create_command();
+
+        cmd.args(args).cwd(&toolchain.workspace_root);
+
+        if env::var("MOON_TEST_HIDE_INSTALL_OUTPUT").is_ok() {
+            cmd.exec_capture_output().await?;
+        } else {
+            cmd.exec_stream_output().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Logable for BunTool {
+    fn get_log_target(&self) -> &str {
+        &self.log_target
+    }
+}
+
+#[async_trait]
+impl Downloadable<Toolchain> for BunTool {
+    fn get_download_path(&self) -> Result<&PathBuf, ToolchainError> {
+        match &self.download_path {
+            Some(path) => Ok(path),
+            None => Err(bun::get_download_file().unwrap_err().into()),
+        }
+    }
+
+    async fn is_downloaded(&self) -> Result<bool, ToolchainError> {
+        Ok(self.get_download_path()?.exists())
+    }
+
+    async fn download(
+        &self,
+        _toolchain: &Toolchain,
+        base_host: Option<&str>,
+    ) -> Result<(), ToolchainError> {
+        let version = &self.config.version;
+        let host = base_host.unwrap_or("https://github.com");
+        let log_target = self.get_log_target();
+
+        // Download the bun.zip archive
+        let download_url = bun::get_bun_url(version, host, bun::get_download_file()?);
+        let download_path = self.get_download_path()?;
+
+        download_file_from_url(&download_url, download_path).await?;
+
+        // Download the SHASUMS256.txt file
+        let shasums_url = bun::get_bun_url(version, host, "SHASUMS256.txt");
+        let shasums_path = download_path.parent().unwrap().join("SHASUMS256.txt");
+
+        download_file_from_url(&shasums_url, &shasums_path).await?;
+
+        debug!(
+            target: log_target,
+            "Verifying shasum against {}",
+            color::url(&shasums_url),
+        );
+
+        // Verify the binary
+        if let Err(error) = verify_shasum(&download_url, download_path, &shasums_path) {
+            error!(
+                target: log_target,
+                "Shasum verification has failed. The downloaded file has been deleted, please try again."
+            );
+
+            fs::remove_file(download_path).await?;
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Installable<Toolchain> for BunTool {
+    fn get_install_dir(&self) -> Result<&PathBuf, ToolchainError> {
+        Ok(&self.install_dir)
+    }
+
+    async fn get_installed_version(&self) -> Result<String, ToolchainError> {
+        Ok(get_bin_version(self.get_bin_path()).await?)
+    }
+
+    async fn is_installed(
+        &self,
+        _toolchain: &Toolchain,
+        _check_version: bool,
+    ) -> Result<bool, ToolchainError> {
+        Ok(self.get_install_dir()?.exists())
+    }
+
+    async fn install(&self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        let download_path = self.get_download_path()?;
+        let install_dir = self.get_install_dir()?;
+        let prefix = bun::get_download_file_name()?;
+
+        unpack(download_path, install_dir, &prefix).await?;
+
+        debug!(
+            target: self.get_log_target(),
+            "Unpacked and installed to {}",
+            color::path(install_dir)
+        );
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Executable<Toolchain> for BunTool {
+    async fn find_bin_path(&mut self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        Ok(())
+    }
+
+    fn get_bin_path(&self) -> &PathBuf {
+        &self.bin_path
+    }
+
+    fn is_executable(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Lifecycle<Toolchain> for BunTool {}
+
+impl Tool for BunTool {}
@@ -1 +1,4 @@
+pub mod bun;
+pub mod deno;
 pub mod node;
+pub mod python;
@@ -1,6 +1,7 @@
 use crate::errors::ToolchainError;
 use crate::helpers::{
-    download_file_from_url, get_bin_version, get_file_sha256_hash, get_path_env_var, unpack,
+    download_file_from_url, file_url_to_path, format_dist_url, get_bin_version, get_path_env_var,
+    unpack, verify_shasum,
 };
 use crate::pms::npm::NpmTool;
 use crate::pms::pnpm::PnpmTool;
@@ -10,40 +11,185 @@ use crate::Toolchain;
 use async_trait::async_trait;
 use moon_config::{NodeConfig, NodePackageManager};
 use moon_error::map_io_to_fs_error;
-use moon_lang::LangError;
-use moon_lang_node::node;
-use moon_logger::{color, debug, error, Logable};
-use moon_utils::fs;
+use moon_lang_node::package::PackageJson;
+use moon_lang_node::{node, NODENV, NVMRC};
+use moon_logger::{color, debug, error, warn, Logable};
 use moon_utils::process::Command;
-use moon_utils::semver::{Version, VersionReq};
+use moon_utils::semver::{find_highest_matching, parse_npm_range, Version, VersionReq};
+use moon_utils::{fs, is_offline};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::ffi::OsStr;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-// https://github.com/nodejs/node#verifying-binaries
-#[track_caller]
-fn verify_shasum(
-    download_url: &str,
-    download_path: &Path,
-    shasums_path: &Path,
-) -> Result<(), ToolchainError> {
-    let sha_hash = get_file_sha256_hash(download_path)?;
-    let file_name = download_path.file_name().unwrap().to_str().unwrap();
-    let file_handle =
-        File::open(shasums_path).map_err(|e| map_io_to_fs_error(e, shasums_path.to_path_buf()))?;
-
-    for line in BufReader::new(file_handle).lines().flatten() {
-        // hash1923hnsdouahsd91houn79h1beyasdpaksdm  node-vx.x.x-darwin-arm64.tar.gz
-        if line.starts_with(sha_hash.as_str()) && line.ends_with(file_name) {
-            return Ok(());
+const LOG_TARGET: &str = "moon:toolchain:node";
+
+// 24 hours
+const RELEASE_INDEX_TTL: u64 = 86400;
+
+#[derive(Deserialize)]
+struct NodeRelease {
+    version: String,
+}
+
+/// Detect a Node.js version or range from the workspace root, by reading the
+/// `.nvmrc` or `.node-version` files, or the `engines.node` field in `package.json`.
+pub fn detect_workspace_version(root_dir: &Path) -> Result<Option<String>, ToolchainError> {
+    for version_manager in [NVMRC, NODENV] {
+        let version_path = root_dir.join(version_manager.version_filename);
+
+        if !version_path.exists() {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&version_path)
+            .map_err(|e| map_io_to_fs_error(e, version_path.clone()))?;
+        let version = contents.trim().trim_start_matches('v');
+
+        if parse_npm_range(version).is_some() {
+            return Ok(Some(version.to_owned()));
+        }
+
+        warn!(
+            target: LOG_TARGET,
+            "Unsupported Node.js version {} in {}, ignoring",
+            color::symbol(version),
+            color::file(version_manager.version_filename),
+        );
+    }
+
+    if let Some(package_json) = PackageJson::read(root_dir)? {
+        if let Some(range) = package_json.engines.as_ref().and_then(|e| e.get("node")) {
+            if parse_npm_range(range).is_some() {
+                return Ok(Some(range.to_owned()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+async fn download_release_index(url: &str, dest: &Path) -> Result<(), ToolchainError> {
+    fs::create_dir_all(dest.parent().unwrap()).await?;
+
+    // Local mirrors are copied directly, as they can't be requested
+    if let Some(path) = file_url_to_path(url) {
+        fs::copy_file(path, dest).await?;
+
+        return Ok(());
+    }
+
+    let response = reqwest::get(url).await?.error_for_status()?;
+    let contents = response.bytes().await?;
+
+    fs::write(dest, contents).await?;
+
+    Ok(())
+}
+
+/// Return the URL of the Node.js release index. An explicit host takes precedence,
+/// followed by the root of the configured `distUrl` mirror (everything before the
+/// first placeholder), and then the official host.
+pub fn get_index_url(base_host: Option<&str>, dist_url: Option<&str>) -> String {
+    if let Some(host) = base_host {
+        return format!("{}/dist/index.json", host);
+    }
+
+    if let Some(template) = dist_url {
+        let placeholder = [template.find("{version}"), template.find("{file}")]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(template.len());
+
+        if let Some(root) = template[..placeholder].rfind('/') {
+            return format!("{}index.json", &template[..=root]);
         }
     }
 
-    Err(ToolchainError::Lang(LangError::InvalidShasum(
-        String::from(download_path.to_string_lossy()),
-        String::from(download_url),
-    )))
+    String::from("https://nodejs.org/dist/index.json")
+}
+
+/// Return the path the release index at the provided URL is cached to. Each URL
+/// is cached separately, so that switching mirrors does not use a stale index.
+pub fn get_index_path(cache_dir: &Path, index_url: &str) -> PathBuf {
+    cache_dir.join(format!(
+        "index-{:x}.json",
+        Sha256::digest(index_url.as_bytes())
+    ))
+}
+
+/// Load all available versions from the Node.js release index, which is cached
+/// within the provided directory. When the index cannot be downloaded, the stale
+/// cache will be used instead.
+async fn load_release_index(
+    cache_dir: &Path,
+    index_url: &str,
+) -> Result<Vec<Version>, ToolchainError> {
+    let index_path = get_index_path(cache_dir, index_url);
+    let is_stale = match fs::metadata(&index_path).await {
+        Ok(meta) => meta
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .map(|elapsed| elapsed.as_secs() > RELEASE_INDEX_TTL)
+            .unwrap_or(true),
+        Err(_) => true,
+    };
+
+    if is_stale && !is_offline() {
+        debug!(
+            target: LOG_TARGET,
+            "Downloading release index from {}",
+            color::url(index_url),
+        );
+
+        if let Err(error) = download_release_index(index_url, &index_path).await {
+            if !index_path.exists() {
+                return Err(error);
+            }
+
+            warn!(
+                target: LOG_TARGET,
+                "Failed to download release index, using cached index instead: {}", error
+            );
+        }
+    }
+
+    if !index_path.exists() {
+        return Err(ToolchainError::InternetConnectionRequired);
+    }
+
+    let releases: Vec<NodeRelease> = fs::read_json(&index_path).await?;
+
+    Ok(releases
+        .iter()
+        .filter_map(|release| Version::parse(release.version.trim_start_matches('v')).ok())
+        .collect())
+}
+
+/// Resolve a Node.js version range to the highest matching version in the
+/// release index, as found at the provided URL. Exact versions are returned as-is.
+pub async fn resolve_version(
+    range: &str,
+    cache_dir: &Path,
+    index_url: &str,
+) -> Result<String, ToolchainError> {
+    if Version::parse(range).is_ok() {
+        return Ok(range.to_owned());
+    }
+
+    let requirements = match parse_npm_range(range) {
+        Some(requirements) => requirements,
+        None => return Err(ToolchainError::UnresolvedNodeVersion(range.to_owned())),
+    };
+
+    let versions = load_release_index(cache_dir, index_url).await?;
+
+    match find_highest_matching(&requirements, &versions) {
+        Some(version) => Ok(version.to_string()),
+        None => Err(ToolchainError::UnresolvedNodeVersion(range.to_owned())),
+    }
 }
 
 pub struct NodeTool {
@@ -76,7 +222,7 @@ impl NodeTool {
                 .join("node")
                 .join(node::get_download_file(&config.version)?),
             install_dir,
-            log_target: String::from("moon:toolchain:node"),
+            log_target: String::from(LOG_TARGET),
             npm: None,
             pnpm: None,
             yarn: None,
@@ -157,6 +303,41 @@ impl NodeTool {
         self.get_npm()
     }
 
+    /// Return the URL to download a distribution file from. An explicit host takes
+    /// precedence, followed by the configured `distUrl`, and then the official host.
+    pub fn get_dist_url(&self, base_host: Option<&str>, file: &str) -> String {
+        let version = &self.config.version;
+
+        match (base_host, &self.config.dist_url) {
+            (Some(host), _) => node::get_nodejs_url(version, host, file),
+            (None, Some(template)) => format_dist_url(template, version, file),
+            (None, None) => node::get_nodejs_url(version, "https://nodejs.org", file),
+        }
+    }
+
+    /// Return true if package managers should be enabled through corepack. Package
+    /// managers with a `distUrl` are installed from their mirror instead.
+    #[track_caller]
+    pub fn is_corepack_enabled(&self) -> bool {
+        let has_dist_url = match self.config.package_manager {
+            NodePackageManager::Npm => self.config.npm.dist_url.is_some(),
+            NodePackageManager::Pnpm => self
+                .config
+                .pnpm
+                .as_ref()
+                .map(|pnpm| pnpm.dist_url.is_some())
+                .unwrap_or_default(),
+            NodePackageManager::Yarn => self
+                .config
+                .yarn
+                .as_ref()
+                .map(|yarn| yarn.dist_url.is_some())
+                .unwrap_or_default(),
+        };
+
+        !has_dist_url && self.is_corepack_aware()
+    }
+
     #[track_caller]
     pub fn is_corepack_aware(&self) -> bool {
         let cfg_version = Version::parse(&self.config.version).unwrap();
@@ -188,17 +369,16 @@ impl Downloadable<Toolchain> for NodeTool {
         base_host: Option<&str>,
     ) -> Result<(), ToolchainError> {
         let version = &self.config.version;
-        let host = base_host.unwrap_or("https://nodejs.org");
         let log_target = self.get_log_target();
 
         // Download the node.tar.gz archive
-        let download_url = node::get_nodejs_url(version, host, node::get_download_file(version)?);
+        let download_url = self.get_dist_url(base_host, &node::get_download_file(version)?);
         let download_path = self.get_download_path()?;
 
         download_file_from_url(&download_url, download_path).await?;
 
         // Download the SHASUMS256.txt file
-        let shasums_url = node::get_nodejs_url(version, host, "SHASUMS256.txt");
+        let shasums_url = self.get_dist_url(base_host, "SHASUMS256.txt");
         let shasums_path = download_path
             .parent()
             .unwrap()
@@ -285,7 +465,7 @@ impl Lifecycle<Toolchain> for NodeTool {
         _toolchain: &Toolchain,
         check_version: bool,
     ) -> Result<u8, ToolchainError> {
-        if self.is_corepack_aware() && check_version {
+        if self.is_corepack_enabled() && check_version {
             debug!(
                 target: self.get_log_target(),
                 "Enabling corepack for package manager control"
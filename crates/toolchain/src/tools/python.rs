@@ -0,0 +1,269 @@
+use crate::errors::ToolchainError;
+use crate::helpers::get_path_env_var;
+use crate::traits::{Downloadable, Executable, Installable, Lifecycle, Tool};
+use crate::Toolchain;
+use async_trait::async_trait;
+use moon_config::{PythonConfig, PythonVenvScope};
+use moon_lang_python::{python, PYTHON};
+use moon_logger::{color, debug, Logable};
+use moon_utils::process::{output_to_trimmed_string, Command};
+use moon_utils::semver::{find_highest_matching, parse_npm_range, Version};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Find an interpreter on `PATH`, preferring a binary for the configured
+/// minor version (`python3.10`), followed by `python3` and `python`.
+fn find_interpreter(version: &str) -> Option<PathBuf> {
+    let mut candidates = vec![];
+    let parts = version.split('.').take(2).collect::<Vec<_>>();
+
+    if parts.len() == 2 && parts.iter().all(|part| part.parse::<u64>().is_ok()) {
+        candidates.push(format!("python{}", parts.join(".")));
+    }
+
+    candidates.push(String::from(PYTHON.binary));
+    candidates.push(String::from("python"));
+
+    candidates
+        .iter()
+        .find_map(|name| python::find_bin_on_path(name))
+}
+
+pub struct PythonTool {
+    bin_path: PathBuf,
+
+    pub config: PythonConfig,
+
+    install_dir: PathBuf,
+
+    log_target: String,
+}
+
+impl PythonTool {
+    pub fn new(toolchain: &Toolchain, config: &PythonConfig) -> Result<PythonTool, ToolchainError> {
+        let bin_path = match &config.interpreter {
+            // Relative paths are resolved from the workspace root, while
+            // plain names are looked up on `PATH`
+            Some(interpreter) => {
+                if interpreter.contains('/') || interpreter.contains('\\') {
+                    toolchain.workspace_root.join(interpreter)
+                } else {
+                    python::find_bin_on_path(interpreter)
+                        .unwrap_or_else(|| PathBuf::from(interpreter))
+                }
+            }
+            None => {
+                find_interpreter(&config.version).unwrap_or_else(|| PathBuf::from(PYTHON.binary))
+            }
+        };
+
+        Ok(PythonTool {
+            install_dir: match bin_path.parent() {
+                Some(dir) => dir.to_path_buf(),
+                None => PathBuf::new(),
+            },
+            bin_path,
+            config: config.to_owned(),
+            log_target: String::from("moon:toolchain:python"),
+        })
+    }
+
+    /// Return the virtual environment directory for the project, based on the
+    /// configured scope.
+    pub fn get_venv_dir(&self, workspace_root: &Path, project_root: &Path) -> PathBuf {
+        match self.config.venv_scope {
+            PythonVenvScope::Project => project_root.join(&self.config.venv_name),
+            PythonVenvScope::Workspace => workspace_root.join(&self.config.venv_name),
+        }
+    }
+
+    /// Return the `PATH` environment variable with the virtual environment's
+    /// binaries, and the interpreter's directory, prepended.
+    pub fn get_venv_path_env_var(&self, venv_dir: &Path) -> std::ffi::OsString {
+        let path = get_path_env_var(&self.install_dir);
+        let mut paths = vec![python::get_venv_bin_dir(venv_dir)];
+
+        paths.extend(env::split_paths(&path).collect::<Vec<_>>());
+
+        env::join_paths(paths).unwrap()
+    }
+
+    /// Create a virtual environment at the provided directory, if it does not exist.
+    pub async fn create_venv(&self, venv_dir: &Path) -> Result<(), ToolchainError> {
+        if venv_dir.exists() {
+            return Ok(());
+        }
+
+        debug!(
+            target: self.get_log_target(),
+            "Creating virtual environment at {}",
+            color::path(venv_dir)
+        );
+
+        Command::new(&self.bin_path)
+            .args(["-m", "venv"])
+            .arg(venv_dir)
+            .env("PATH", get_path_env_var(&self.install_dir))
+            .exec_capture_output()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Install dependencies into the virtual environment with pip, from either
+    /// a `requirements.txt` or `pyproject.toml` within the working directory.
+    pub async fn install_dependencies(
+        &self,
+        venv_dir: &Path,
+        working_dir: &Path,
+    ) -> Result<(), ToolchainError> {
+        let deps_file = match python::find_dependencies_file(working_dir) {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        let mut args = vec!["-m", "pip", "install"];
+
+        if deps_file.extension().unwrap_or_default() == "txt" {
+            args.extend(["-r", deps_file.file_name().unwrap().to_str().unwrap()]);
+        } else {
+            args.extend(["-e", "."]);
+        }
+
+        let venv_python = python::get_venv_bin_dir(venv_dir).join(python::get_bin_name("python"));
+        let mut cmd = Command::new(venv_python);
+
+        cmd.args(args)
+            .cwd(working_dir)
+            .env("PATH", self.get_venv_path_env_var(venv_dir))
+            .env("VIRTUAL_ENV", venv_dir);
+
+        if env::var("MOON_TEST_HIDE_INSTALL_OUTPUT").is_ok() {
+            cmd.exec_capture_output().await?;
+        } else {
+            cmd.exec_stream_output().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Logable for PythonTool {
+    fn get_log_target(&self) -> &str {
+        &self.log_target
+    }
+}
+
+// Python is not downloaded or installed by moon, and instead uses an interpreter
+// provided by the system, so these lifecycles must never remove any files!
+
+#[async_trait]
+impl Downloadable<Toolchain> for PythonTool {
+    fn get_download_path(&self) -> Result<&PathBuf, ToolchainError> {
+        Ok(&self.bin_path)
+    }
+
+    async fn is_downloaded(&self) -> Result<bool, ToolchainError> {
+        Ok(true)
+    }
+
+    async fn download(
+        &self,
+        _toolchain: &Toolchain,
+        _base_host: Option<&str>,
+    ) -> Result<(), ToolchainError> {
+        Ok(())
+    }
+
+    async fn undownload(&self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Installable<Toolchain> for PythonTool {
+    fn get_install_dir(&self) -> Result<&PathBuf, ToolchainError> {
+        Ok(&self.install_dir)
+    }
+
+    async fn get_installed_version(&self) -> Result<String, ToolchainError> {
+        let output = Command::new(&self.bin_path)
+            .arg("--version")
+            .exec_capture_output()
+            .await?;
+
+        // Older versions write to stderr instead of stdout
+        let mut version = output_to_trimmed_string(&output.stdout);
+
+        if version.is_empty() {
+            version = output_to_trimmed_string(&output.stderr);
+        }
+
+        Ok(python::parse_version_output(&version).unwrap_or_else(|| String::from("0.0.0")))
+    }
+
+    async fn is_installed(
+        &self,
+        _toolchain: &Toolchain,
+        check_version: bool,
+    ) -> Result<bool, ToolchainError> {
+        if !self.bin_path.is_file() {
+            return Ok(false);
+        }
+
+        if !check_version {
+            return Ok(true);
+        }
+
+        let installed_version = match Version::parse(&self.get_installed_version().await?) {
+            Ok(version) => version,
+            Err(_) => return Ok(false),
+        };
+
+        let requirements = match parse_npm_range(&self.config.version) {
+            Some(requirements) => requirements,
+            None => return Ok(false),
+        };
+
+        let matches = find_highest_matching(&requirements, [&installed_version]).is_some();
+
+        debug!(
+            target: self.get_log_target(),
+            "Found interpreter {} with version {}",
+            color::path(&self.bin_path),
+            color::symbol(&installed_version.to_string())
+        );
+
+        Ok(matches)
+    }
+
+    async fn install(&self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        Err(ToolchainError::MissingPythonInterpreter(
+            self.config.version.clone(),
+        ))
+    }
+
+    async fn uninstall(&self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Executable<Toolchain> for PythonTool {
+    async fn find_bin_path(&mut self, _toolchain: &Toolchain) -> Result<(), ToolchainError> {
+        Ok(())
+    }
+
+    fn get_bin_path(&self) -> &PathBuf {
+        &self.bin_path
+    }
+
+    fn is_executable(&self) -> bool {
+        self.bin_path.is_file()
+    }
+}
+
+#[async_trait]
+impl Lifecycle<Toolchain> for PythonTool {}
+
+impl Tool for PythonTool {}
@@ -0,0 +1,240 @@
+use crate::errors::ToolchainError;
+use crate::helpers::LOG_TARGET;
+use moon_error::map_io_to_fs_error;
+use moon_logger::{color, trace, warn};
+use moon_utils::fs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::time::sleep;
+
+pub const USAGE_FILENAME: &str = "usage.json";
+
+// Locks that haven't been refreshed within this age are assumed
+// to be from a process that crashed
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+pub fn now_millis() -> u128 {
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(d) => d.as_millis(),
+        Err(_) => 0,
+    }
+}
+
+/// A tool version that has been installed into the toolchain.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledTool {
+    /// Unique identifier, in the format of "<name>/<version>".
+    pub id: String,
+
+    pub last_used_time: Option<u128>,
+
+    pub name: String,
+
+    pub path: PathBuf,
+
+    pub size: u64,
+
+    pub version: String,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceUsage {
+    pub last_used_time: u128,
+
+    /// Tool identifiers, in the format of "<name>/<version>".
+    pub tools: Vec<String>,
+}
+
+/// An exclusive lock on the usage file, held across a read-modify-write,
+/// so that concurrent processes don't overwrite each other's changes.
+/// The lock file contains the PID of the owning process, and is released
+/// when dropped. Long running operations must periodically `refresh` it.
+pub struct ToolchainUsageLock {
+    path: PathBuf,
+}
+
+impl ToolchainUsageLock {
+    pub async fn acquire(tools_dir: &Path) -> Result<ToolchainUsageLock, ToolchainError> {
+        let path = tools_dir.join(format!("{}.lock", USAGE_FILENAME));
+
+        fs::create_dir_all(tools_dir).await?;
+
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    file.write_all(std::process::id().to_string().as_bytes())
+                        .map_err(|e| map_io_to_fs_error(e, path.clone()))?;
+
+                    break;
+                }
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                    let is_stale = std::fs::metadata(&path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .map(|elapsed| elapsed > STALE_LOCK_AGE)
+                        .unwrap_or(false);
+
+                    if is_stale {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Removing stale toolchain usage lock {} (held by process {})",
+                            color::path(&path),
+                            std::fs::read_to_string(&path).unwrap_or_default().trim()
+                        );
+
+                        let _ = std::fs::remove_file(&path);
+                    } else {
+                        sleep(Duration::from_millis(50)).await;
+                    }
+                }
+                Err(error) => return Err(map_io_to_fs_error(error, path).into()),
+            }
+        }
+
+        trace!(
+            target: LOG_TARGET,
+            "Acquired toolchain usage lock {}",
+            color::path(&path)
+        );
+
+        Ok(ToolchainUsageLock { path })
+    }
+
+    /// Rewrite the lock file to update its modified time,
+    /// so that other processes don't consider the lock stale.
+    pub fn refresh(&self) -> Result<(), ToolchainError> {
+        std::fs::write(&self.path, std::process::id().to_string())
+            .map_err(|e| map_io_to_fs_error(e, self.path.clone()))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for ToolchainUsageLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Tracks the tool versions that each workspace has used, and when,
+/// so that versions no longer in use can be pruned from the toolchain.
+/// This is persisted to ~/.moon/tools/usage.json.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolchainUsage {
+    #[serde(skip)]
+    pub path: PathBuf,
+
+    /// When the usage was last missing or unreadable, and started over.
+    /// Workspaces used before this time may have been forgotten.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset_time: Option<u128>,
+
+    /// Keyed by the absolute path of the workspace root.
+    pub workspaces: BTreeMap<String, WorkspaceUsage>,
+}
+
+impl ToolchainUsage {
+    pub async fn load(tools_dir: &Path) -> Result<ToolchainUsage, ToolchainError> {
+        let path = tools_dir.join(USAGE_FILENAME);
+
+        let mut usage = if path.exists() {
+            trace!(
+                target: LOG_TARGET,
+                "Loading toolchain usage from {}",
+                color::path(&path)
+            );
+
+            // A corrupt file should never block the toolchain, so start over
+            match fs::read_json::<_, ToolchainUsage>(&path).await {
+                Ok(usage) => usage,
+                Err(error) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Unable to read toolchain usage {}, resetting: {}",
+                        color::path(&path),
+                        error
+                    );
+
+                    ToolchainUsage::reset()
+                }
+            }
+        } else {
+            ToolchainUsage::reset()
+        };
+
+        usage.path = path;
+
+        Ok(usage)
+    }
+
+    fn reset() -> ToolchainUsage {
+        ToolchainUsage {
+            reset_time: Some(now_millis()),
+            ..ToolchainUsage::default()
+        }
+    }
+
+    /// Save the usage by writing to a temporary file and renaming it,
+    /// so that the usage file is never partially written.
+    pub async fn save(&self) -> Result<(), ToolchainError> {
+        trace!(
+            target: LOG_TARGET,
+            "Saving toolchain usage to {}",
+            color::path(&self.path)
+        );
+
+        let temp_path = self.path.with_extension("json.tmp");
+
+        fs::write_json(&temp_path, self, false).await?;
+
+        std::fs::rename(&temp_path, &self.path)
+            .map_err(|e| map_io_to_fs_error(e, self.path.clone()))?;
+
+        Ok(())
+    }
+
+    /// Return true if the usage was reset after the provided time,
+    /// and may be missing workspaces that have been used since.
+    pub fn was_reset_since(&self, since: u128) -> bool {
+        self.reset_time.map(|time| time > since).unwrap_or(false)
+    }
+
+    /// Return the most recent time that any workspace used the tool.
+    pub fn get_last_used_time(&self, id: &str) -> Option<u128> {
+        self.workspaces
+            .values()
+            .filter(|workspace| workspace.tools.iter().any(|tool| tool == id))
+            .map(|workspace| workspace.last_used_time)
+            .max()
+    }
+
+    /// Remove workspaces that have not been used since the provided time,
+    /// or whose root no longer exists on the file system.
+    pub fn remove_stale_workspaces(&mut self, since: u128) {
+        self.workspaces.retain(|root, workspace| {
+            workspace.last_used_time >= since && Path::new(root).exists()
+        });
+    }
+
+    /// Record the tools that a workspace is currently using.
+    pub fn track(&mut self, workspace_root: &Path, tools: Vec<String>, time: u128) {
+        self.workspaces.insert(
+            workspace_root.to_string_lossy().to_string(),
+            WorkspaceUsage {
+                last_used_time: time,
+                tools,
+            },
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use moon_config::{BunConfig, WorkspaceConfig};
+use moon_lang_bun::bun;
+use moon_toolchain::{Downloadable, Executable, Installable, Toolchain};
+use predicates::prelude::*;
+use std::env;
+use std::path::PathBuf;
+
+async fn create_bun_tool() -> (Toolchain, assert_fs::TempDir) {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+
+    let config = WorkspaceConfig {
+        bun: Some(BunConfig {
+            version: String::from("1.0.0"),
+        }),
+        ..WorkspaceConfig::default()
+    };
+
+    let toolchain = Toolchain::create_from_dir(base_dir.path(), &env::temp_dir(), &config)
+        .await
+        .unwrap();
+
+    (toolchain, base_dir)
+}
+
+fn get_download_file() -> String {
+    bun::get_download_file().unwrap()
+}
+
+fn create_shasums(hash: &str) -> String {
+    format!("{hash}  bun-darwin-aarch64.zip\n{hash}  bun-darwin-x64.zip\n{hash}  bun-linux-aarch64.zip\n{hash}  bun-linux-x64.zip\n", hash = hash)
+}
+
+#[tokio::test]
+async fn is_not_created_when_not_configured() {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+    let toolchain = Toolchain::create_from_dir(
+        base_dir.path(),
+        &env::temp_dir(),
+        &WorkspaceConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(toolchain.get_bun().is_none());
+
+    base_dir.close().unwrap();
+}
+
+#[tokio::test]
+async fn generates_paths() {
+    let (toolchain, temp_dir) = create_bun_tool().await;
+    let bun = toolchain.get_bun().unwrap();
+
+    assert!(predicates::str::ends_with(
+        PathBuf::from(".moon")
+            .join("tools")
+            .join("bun")
+            .join("1.0.0")
+            .to_str()
+            .unwrap()
+    )
+    .eval(bun.get_install_dir().unwrap().to_str().unwrap()));
+
+    let bin_path = PathBuf::from(".moon")
+        .join("tools")
+        .join("bun")
+        .join("1.0.0")
+        .join("bun");
+
+    assert!(predicates::str::ends_with(bin_path.to_str().unwrap())
+        .eval(bun.get_bin_path().to_str().unwrap()));
+
+    assert!(predicates::str::ends_with(
+        PathBuf::from(".moon")
+            .join("temp")
+            .join("bun")
+            .join("1.0.0")
+            .join(get_download_file())
+            .to_str()
+            .unwrap()
+    )
+    .eval(bun.get_download_path().unwrap().to_str().unwrap()));
+
+    temp_dir.close().unwrap();
+}
+
+mod download {
+    use super::*;
+    use mockito::mock;
+
+    #[tokio::test]
+    async fn is_downloaded_checks() {
+        let (toolchain, temp_dir) = create_bun_tool().await;
+        let bun = toolchain.get_bun().unwrap();
+
+        assert!(!bun.is_downloaded().await.unwrap());
+
+        let dl_path = bun.get_download_path().unwrap();
+
+        std::fs::create_dir_all(dl_path.parent().unwrap()).unwrap();
+        std::fs::write(dl_path, "").unwrap();
+
+        assert!(bun.is_downloaded().await.unwrap());
+
+        std::fs::remove_file(dl_path).unwrap();
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_to_temp_dir() {
+        let (toolchain, temp_dir) = create_bun_tool().await;
+        let bun = toolchain.get_bun().unwrap();
+
+        assert!(!bun.get_download_path().unwrap().exists());
+
+        let archive = mock(
+            "GET",
+            format!(
+                "/oven-sh/bun/releases/download/bun-v1.0.0/{}",
+                get_download_file()
+            )
+            .as_str(),
+        )
+        .with_body("binary")
+        .create();
+
+        let shasums = mock(
+            "GET",
+            "/oven-sh/bun/releases/download/bun-v1.0.0/SHASUMS256.txt",
+        )
+        .with_body(create_shasums(
+            "9a3a45d01531a20e89ac6ae10b0b0beb0492acd7216a368aa062d1a5fecaf9cd",
+        ))
+        .create();
+
+        bun.download(&toolchain, Some(&mockito::server_url()))
+            .await
+            .unwrap();
+
+        archive.assert();
+        shasums.assert();
+
+        assert!(bun.get_download_path().unwrap().exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidShasum")]
+    async fn fails_on_invalid_shasum() {
+        let (toolchain, temp_dir) = create_bun_tool().await;
+        let bun = toolchain.get_bun().unwrap();
+
+        let archive = mock(
+            "GET",
+            format!(
+                "/oven-sh/bun/releases/download/bun-v1.0.0/{}",
+                get_download_file()
+            )
+            .as_str(),
+        )
+        .with_body("binary")
+        .create();
+
+        let shasums = mock(
+            "GET",
+            "/oven-sh/bun/releases/download/bun-v1.0.0/SHASUMS256.txt",
+        )
+        .with_body(create_shasums("fakehash"))
+        .create();
+
+        bun.download(&toolchain, Some(&mockito::server_url()))
+            .await
+            .unwrap();
+
+        archive.assert();
+        shasums.assert();
+
+        temp_dir.close().unwrap();
+    }
+}
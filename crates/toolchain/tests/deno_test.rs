@@ -0,0 +1,134 @@
+use moon_config::{DenoConfig, WorkspaceConfig};
+use moon_lang_deno::deno;
+use moon_toolchain::{Downloadable, Executable, Installable, Toolchain};
+use predicates::prelude::*;
+use std::env;
+use std::path::PathBuf;
+
+async fn create_deno_tool() -> (Toolchain, assert_fs::TempDir) {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+
+    let config = WorkspaceConfig {
+        deno: Some(DenoConfig {
+            version: String::from("1.0.0"),
+        }),
+        ..WorkspaceConfig::default()
+    };
+
+    let toolchain = Toolchain::create_from_dir(base_dir.path(), &env::temp_dir(), &config)
+        .await
+        .unwrap();
+
+    (toolchain, base_dir)
+}
+
+fn get_download_file() -> String {
+    deno::get_download_file().unwrap()
+}
+
+#[tokio::test]
+async fn is_not_created_when_not_configured() {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+    let toolchain = Toolchain::create_from_dir(
+        base_dir.path(),
+        &env::temp_dir(),
+        &WorkspaceConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(toolchain.get_deno().is_none());
+
+    base_dir.close().unwrap();
+}
+
+#[tokio::test]
+async fn generates_paths() {
+    let (toolchain, temp_dir) = create_deno_tool().await;
+    let deno = toolchain.get_deno().unwrap();
+
+    assert!(predicates::str::ends_with(
+        PathBuf::from(".moon")
+            .join("tools")
+            .join("deno")
+            .join("1.0.0")
+            .to_str()
+            .unwrap()
+    )
+    .eval(deno.get_install_dir().unwrap().to_str().unwrap()));
+
+    let bin_path = PathBuf::from(".moon")
+        .join("tools")
+        .join("deno")
+        .join("1.0.0")
+        .join(deno::get_bin_name("deno"));
+
+    assert!(predicates::str::ends_with(bin_path.to_str().unwrap())
+        .eval(deno.get_bin_path().to_str().unwrap()));
+
+    assert!(predicates::str::ends_with(
+        PathBuf::from(".moon")
+            .join("temp")
+            .join("deno")
+            .join("1.0.0")
+            .join(get_download_file())
+            .to_str()
+            .unwrap()
+    )
+    .eval(deno.get_download_path().unwrap().to_str().unwrap()));
+
+    temp_dir.close().unwrap();
+}
+
+mod download {
+    use super::*;
+    use mockito::mock;
+
+    #[tokio::test]
+    async fn is_downloaded_checks() {
+        let (toolchain, temp_dir) = create_deno_tool().await;
+        let deno = toolchain.get_deno().unwrap();
+
+        assert!(!deno.is_downloaded().await.unwrap());
+
+        let dl_path = deno.get_download_path().unwrap();
+
+        std::fs::create_dir_all(dl_path.parent().unwrap()).unwrap();
+        std::fs::write(dl_path, "").unwrap();
+
+        assert!(deno.is_downloaded().await.unwrap());
+
+        std::fs::remove_file(dl_path).unwrap();
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_to_temp_dir() {
+        let (toolchain, temp_dir) = create_deno_tool().await;
+        let deno = toolchain.get_deno().unwrap();
+
+        assert!(!deno.get_download_path().unwrap().exists());
+
+        let archive = mock(
+            "GET",
+            format!(
+                "/denoland/deno/releases/download/v1.0.0/{}",
+                get_download_file()
+            )
+            .as_str(),
+        )
+        .with_body("binary")
+        .create();
+
+        deno.download(&toolchain, Some(&mockito::server_url()))
+            .await
+            .unwrap();
+
+        archive.assert();
+
+        assert!(deno.get_download_path().unwrap().exists());
+
+        temp_dir.close().unwrap();
+    }
+}
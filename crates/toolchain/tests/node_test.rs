@@ -148,3 +148,326 @@ mod download {
         temp_dir.close().unwrap();
     }
 }
+
+mod dist_url {
+    use super::*;
+    use assert_fs::prelude::*;
+    use moon_toolchain::helpers::get_dist_tarball;
+
+    async fn create_node_tool_with_dist_url(dist_url: &str) -> (Toolchain, assert_fs::TempDir) {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+
+        let mut config = WorkspaceConfig::default();
+
+        config.node.version = String::from("1.0.0");
+        config.node.dist_url = Some(dist_url.to_owned());
+
+        let toolchain = Toolchain::create_from_dir(base_dir.path(), &env::temp_dir(), &config)
+            .await
+            .unwrap();
+
+        (toolchain, base_dir)
+    }
+
+    fn create_local_mirror() -> (assert_fs::TempDir, String) {
+        let mirror = assert_fs::TempDir::new().unwrap();
+
+        mirror
+            .child(format!("v1.0.0/{}", get_download_file()))
+            .write_str("binary")
+            .unwrap();
+
+        mirror
+            .child("v1.0.0/SHASUMS256.txt")
+            .write_str(&create_shasums(
+                "9a3a45d01531a20e89ac6ae10b0b0beb0492acd7216a368aa062d1a5fecaf9cd",
+            ))
+            .unwrap();
+
+        let dist_url = format!(
+            "{}v{{version}}/{{file}}",
+            reqwest::Url::from_directory_path(mirror.path()).unwrap()
+        );
+
+        (mirror, dist_url)
+    }
+
+    #[tokio::test]
+    async fn formats_dist_url_placeholders() {
+        let (toolchain, temp_dir) =
+            create_node_tool_with_dist_url("https://mirror.internal/node/v{version}/{file}").await;
+        let node = toolchain.get_node();
+
+        assert_eq!(
+            node.get_dist_url(None, "SHASUMS256.txt"),
+            "https://mirror.internal/node/v1.0.0/SHASUMS256.txt"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn defaults_to_official_host() {
+        let (toolchain, temp_dir) = create_node_tool().await;
+        let node = toolchain.get_node();
+
+        assert_eq!(
+            node.get_dist_url(None, "SHASUMS256.txt"),
+            "https://nodejs.org/dist/v1.0.0/SHASUMS256.txt"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_from_local_mirror() {
+        let (mirror, dist_url) = create_local_mirror();
+        let (toolchain, temp_dir) = create_node_tool_with_dist_url(&dist_url).await;
+        let node = toolchain.get_node();
+
+        assert!(!node.get_download_path().unwrap().exists());
+
+        node.download(&toolchain, None).await.unwrap();
+
+        assert!(node.get_download_path().unwrap().exists());
+
+        temp_dir.close().unwrap();
+        mirror.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidShasum")]
+    async fn fails_on_invalid_shasum_from_local_mirror() {
+        let (mirror, dist_url) = create_local_mirror();
+
+        mirror
+            .child("v1.0.0/SHASUMS256.txt")
+            .write_str(&create_shasums("fakehash"))
+            .unwrap();
+
+        let (toolchain, _temp_dir) = create_node_tool_with_dist_url(&dist_url).await;
+
+        toolchain
+            .get_node()
+            .download(&toolchain, None)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn resolves_package_tarballs() {
+        assert_eq!(
+            get_dist_tarball("https://mirror.internal/npm/pnpm/-/{file}", "pnpm", "7.0.0"),
+            "https://mirror.internal/npm/pnpm/-/pnpm-7.0.0.tgz"
+        );
+
+        let mirror = assert_fs::TempDir::new().unwrap();
+        let dist_url = format!(
+            "{}{{file}}",
+            reqwest::Url::from_directory_path(mirror.path()).unwrap()
+        );
+
+        assert_eq!(
+            PathBuf::from(get_dist_tarball(&dist_url, "yarn", "1.22.0")),
+            mirror.path().join("yarn-1.22.0.tgz")
+        );
+    }
+}
+
+mod resolve_version {
+    use assert_fs::prelude::*;
+    use mockito::mock;
+    use moon_toolchain::tools::node::{
+        detect_workspace_version, get_index_path, get_index_url, resolve_version,
+    };
+
+    const RELEASE_INDEX: &str = r#"[
+        { "version": "v18.10.0" },
+        { "version": "v16.17.1" },
+        { "version": "v16.13.2" },
+        { "version": "v16.13.0" },
+        { "version": "v14.20.0" }
+    ]"#;
+
+    #[tokio::test]
+    async fn returns_exact_versions_as_is() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let index_url = get_index_url(None, None);
+
+        assert_eq!(
+            resolve_version("16.13.0", temp_dir.path(), &index_url)
+                .await
+                .unwrap(),
+            "16.13.0"
+        );
+
+        assert!(!get_index_path(temp_dir.path(), &index_url).exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolves_highest_matching_version() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+
+        let index_url = get_index_url(Some(&mockito::server_url()), None);
+
+        let index = mock("GET", "/dist/index.json")
+            .with_body(RELEASE_INDEX)
+            .create();
+
+        assert_eq!(
+            resolve_version(">=14 <17", temp_dir.path(), &index_url)
+                .await
+                .unwrap(),
+            "16.17.1"
+        );
+
+        index.assert();
+
+        assert!(get_index_path(temp_dir.path(), &index_url).exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn uses_cached_index() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let index_url = get_index_url(Some("http://localhost:1"), None);
+
+        std::fs::write(get_index_path(temp_dir.path(), &index_url), RELEASE_INDEX).unwrap();
+
+        // The cache is fresh, so the unreachable host is never requested
+        assert_eq!(
+            resolve_version("16.13", temp_dir.path(), &index_url)
+                .await
+                .unwrap(),
+            "16.13.2"
+        );
+
+        // Other URLs are cached separately
+        assert!(!get_index_path(temp_dir.path(), &get_index_url(None, None)).exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolves_from_local_mirror() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let mirror = assert_fs::TempDir::new().unwrap();
+
+        mirror.child("index.json").write_str(RELEASE_INDEX).unwrap();
+
+        let dist_url = format!(
+            "{}v{{version}}/{{file}}",
+            reqwest::Url::from_directory_path(mirror.path()).unwrap()
+        );
+
+        let index_url = get_index_url(None, Some(&dist_url));
+
+        assert_eq!(
+            resolve_version("^16", temp_dir.path(), &index_url)
+                .await
+                .unwrap(),
+            "16.17.1"
+        );
+
+        assert!(get_index_path(temp_dir.path(), &index_url).exists());
+
+        temp_dir.close().unwrap();
+        mirror.close().unwrap();
+    }
+
+    #[test]
+    fn derives_index_url_from_dist_url() {
+        assert_eq!(
+            get_index_url(None, Some("https://mirror.internal/node/v{version}/{file}")),
+            "https://mirror.internal/node/index.json"
+        );
+        assert_eq!(
+            get_index_url(None, Some("https://mirror.internal/node/{file}")),
+            "https://mirror.internal/node/index.json"
+        );
+        assert_eq!(
+            get_index_url(
+                Some("https://host.internal"),
+                Some("https://mirror.internal/node/v{version}/{file}")
+            ),
+            "https://host.internal/dist/index.json"
+        );
+        assert_eq!(
+            get_index_url(None, None),
+            "https://nodejs.org/dist/index.json"
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "UnresolvedNodeVersion")]
+    async fn errors_when_no_version_matches() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let index_url = get_index_url(None, None);
+
+        std::fs::write(get_index_path(temp_dir.path(), &index_url), RELEASE_INDEX).unwrap();
+
+        resolve_version("^20", temp_dir.path(), &index_url)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn detects_from_nvmrc() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+
+        temp_dir.child(".nvmrc").write_str("v16.13.0\n").unwrap();
+        temp_dir.child(".node-version").write_str("18").unwrap();
+
+        assert_eq!(
+            detect_workspace_version(temp_dir.path()).unwrap(),
+            Some("16.13.0".into())
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn detects_from_node_version() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+
+        temp_dir.child(".node-version").write_str("18").unwrap();
+
+        assert_eq!(
+            detect_workspace_version(temp_dir.path()).unwrap(),
+            Some("18".into())
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn detects_from_engines() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+
+        temp_dir
+            .child("package.json")
+            .write_str(r#"{ "name": "root", "engines": { "node": ">=16 <19" } }"#)
+            .unwrap();
+
+        assert_eq!(
+            detect_workspace_version(temp_dir.path()).unwrap(),
+            Some(">=16 <19".into())
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn skips_unsupported_aliases() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+
+        temp_dir.child(".nvmrc").write_str("lts/*").unwrap();
+
+        assert_eq!(detect_workspace_version(temp_dir.path()).unwrap(), None);
+
+        temp_dir.close().unwrap();
+    }
+}
@@ -13,6 +13,7 @@ async fn create_pnpm_tool() -> (Toolchain, assert_fs::TempDir) {
     config.node.version = String::from("1.0.0");
     config.node.package_manager = NodePackageManager::Pnpm;
     config.node.pnpm = Some(PnpmConfig {
+        dist_url: None,
         version: String::from("6.0.0"),
     });
 
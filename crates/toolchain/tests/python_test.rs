@@ -0,0 +1,220 @@
+// These tests use a stub interpreter (a shell script), so only run on Unix.
+#![cfg(unix)]
+
+use moon_config::{PythonConfig, PythonVenvScope, WorkspaceConfig};
+use moon_toolchain::{Executable, Installable, Toolchain};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+const STUB_INTERPRETER: &str = r#"#!/bin/sh
+if [ "$1" = "--version" ]; then
+    echo "Python 3.10.4"
+elif [ "$1" = "-m" ] && [ "$2" = "venv" ]; then
+    mkdir -p "$3/bin"
+    cp "$0" "$3/bin/python"
+elif [ "$1" = "-m" ] && [ "$2" = "pip" ]; then
+    shift 2
+    echo "$@" > "$VIRTUAL_ENV/pip-args.txt"
+fi
+"#;
+
+fn create_stub_interpreter(dir: &Path) {
+    let bin_path = dir.join("python-stub");
+
+    fs::write(&bin_path, STUB_INTERPRETER).unwrap();
+    fs::set_permissions(&bin_path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+async fn create_python_tool(
+    version: &str,
+    venv_scope: PythonVenvScope,
+) -> (Toolchain, assert_fs::TempDir) {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+
+    create_stub_interpreter(base_dir.path());
+
+    let config = WorkspaceConfig {
+        python: Some(PythonConfig {
+            interpreter: Some(String::from("./python-stub")),
+            venv_scope,
+            version: String::from(version),
+            ..PythonConfig::default()
+        }),
+        ..WorkspaceConfig::default()
+    };
+
+    let toolchain = Toolchain::create_from_dir(base_dir.path(), base_dir.path(), &config)
+        .await
+        .unwrap();
+
+    (toolchain, base_dir)
+}
+
+#[tokio::test]
+async fn is_not_created_when_not_configured() {
+    let base_dir = assert_fs::TempDir::new().unwrap();
+    let toolchain = Toolchain::create_from_dir(
+        base_dir.path(),
+        base_dir.path(),
+        &WorkspaceConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(toolchain.get_python().is_none());
+
+    base_dir.close().unwrap();
+}
+
+#[tokio::test]
+async fn resolves_interpreter_from_workspace_root() {
+    let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+    let python = toolchain.get_python().unwrap();
+
+    assert_eq!(
+        python.get_bin_path(),
+        &temp_dir.path().join("./python-stub")
+    );
+    assert!(python.is_executable());
+
+    temp_dir.close().unwrap();
+}
+
+mod install {
+    use super::*;
+
+    #[tokio::test]
+    async fn matches_version_requirement() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+
+        assert_eq!(python.get_installed_version().await.unwrap(), "3.10.4");
+        assert!(python.is_installed(&toolchain, true).await.unwrap());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn doesnt_match_version_requirement() {
+        let (toolchain, temp_dir) = create_python_tool("3.11", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+
+        assert!(python.is_installed(&toolchain, false).await.unwrap());
+        assert!(!python.is_installed(&toolchain, true).await.unwrap());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "MissingPythonInterpreter")]
+    async fn errors_on_install_as_its_not_managed() {
+        let (toolchain, temp_dir) = create_python_tool("3.11", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+
+        python.install(&toolchain).await.unwrap();
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn never_removes_the_interpreter() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+
+        python.uninstall(&toolchain).await.unwrap();
+
+        assert!(python.get_bin_path().exists());
+
+        temp_dir.close().unwrap();
+    }
+}
+
+mod venv {
+    use super::*;
+
+    #[tokio::test]
+    async fn uses_workspace_root_for_workspace_scope() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+        let root = temp_dir.path();
+
+        assert_eq!(
+            python.get_venv_dir(root, &root.join("project")),
+            root.join(".venv")
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn uses_project_root_for_project_scope() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Project).await;
+        let python = toolchain.get_python().unwrap();
+        let root = temp_dir.path();
+
+        assert_eq!(
+            python.get_venv_dir(root, &root.join("project")),
+            root.join("project/.venv")
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn creates_venv() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+        let venv_dir = temp_dir.path().join(".venv");
+
+        python.create_venv(&venv_dir).await.unwrap();
+
+        assert!(venv_dir.join("bin/python").exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn installs_from_requirements() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+        let venv_dir = temp_dir.path().join(".venv");
+
+        fs::write(temp_dir.path().join("requirements.txt"), "requests").unwrap();
+
+        python.create_venv(&venv_dir).await.unwrap();
+        python
+            .install_dependencies(&venv_dir, temp_dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(venv_dir.join("pip-args.txt")).unwrap(),
+            "install -r requirements.txt\n"
+        );
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn installs_from_pyproject() {
+        let (toolchain, temp_dir) = create_python_tool("3.10", PythonVenvScope::Workspace).await;
+        let python = toolchain.get_python().unwrap();
+        let venv_dir = temp_dir.path().join(".venv");
+
+        fs::write(temp_dir.path().join("pyproject.toml"), "").unwrap();
+
+        python.create_venv(&venv_dir).await.unwrap();
+        python
+            .install_dependencies(&venv_dir, temp_dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(venv_dir.join("pip-args.txt")).unwrap(),
+            "install -e .\n"
+        );
+
+        temp_dir.close().unwrap();
+    }
+}
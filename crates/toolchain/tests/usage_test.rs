@@ -0,0 +1,276 @@
+use moon_config::{BunConfig, WorkspaceConfig};
+use moon_toolchain::{now_millis, Toolchain, ToolchainUsage, ToolchainUsageLock, USAGE_FILENAME};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const DAY: u128 = 86_400_000;
+
+async fn create_toolchain(base_dir: &Path) -> Toolchain {
+    let mut config = WorkspaceConfig::default();
+
+    config.node.version = String::from("1.0.0");
+    config.bun = Some(BunConfig {
+        version: String::from("1.0.0"),
+    });
+
+    Toolchain::create_from_dir(base_dir, base_dir, &config)
+        .await
+        .unwrap()
+}
+
+fn create_tool(toolchain: &Toolchain, id: &str, contents: &str) {
+    let dir = toolchain.tools_dir.join(id);
+
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("bin"), contents).unwrap();
+}
+
+mod usage {
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_default_when_missing() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let usage = ToolchainUsage::load(base_dir.path()).await.unwrap();
+
+        assert!(usage.workspaces.is_empty());
+        assert!(usage.reset_time.is_some());
+        assert_eq!(usage.path, base_dir.path().join(USAGE_FILENAME));
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn loads_default_when_corrupt() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+
+        fs::write(base_dir.path().join(USAGE_FILENAME), "{invalid").unwrap();
+
+        let usage = ToolchainUsage::load(base_dir.path()).await.unwrap();
+
+        assert!(usage.workspaces.is_empty());
+        assert!(usage.reset_time.is_some());
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn saves_and_loads() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let mut usage = ToolchainUsage::load(base_dir.path()).await.unwrap();
+
+        usage.track(base_dir.path(), vec![String::from("node/1.0.0")], 123);
+        usage.save().await.unwrap();
+
+        assert_eq!(ToolchainUsage::load(base_dir.path()).await.unwrap(), usage);
+        assert!(!base_dir.path().join("usage.json.tmp").exists());
+
+        base_dir.close().unwrap();
+    }
+
+    #[test]
+    fn checks_if_reset_since() {
+        let mut usage = ToolchainUsage::default();
+
+        assert!(!usage.was_reset_since(100));
+
+        usage.reset_time = Some(200);
+
+        assert!(usage.was_reset_since(100));
+        assert!(!usage.was_reset_since(300));
+    }
+
+    #[tokio::test]
+    async fn releases_lock_when_dropped() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let lock_path = base_dir.path().join("usage.json.lock");
+
+        let lock = ToolchainUsageLock::acquire(base_dir.path()).await.unwrap();
+
+        assert!(lock_path.exists());
+
+        drop(lock);
+
+        assert!(!lock_path.exists());
+
+        base_dir.close().unwrap();
+    }
+
+    #[test]
+    fn returns_most_recent_time_across_workspaces() {
+        let mut usage = ToolchainUsage::default();
+
+        usage.track(
+            Path::new("/a"),
+            vec![String::from("node/1.0.0"), String::from("bun/1.0.0")],
+            100,
+        );
+        usage.track(Path::new("/b"), vec![String::from("node/1.0.0")], 200);
+
+        assert_eq!(usage.get_last_used_time("node/1.0.0"), Some(200));
+        assert_eq!(usage.get_last_used_time("bun/1.0.0"), Some(100));
+        assert_eq!(usage.get_last_used_time("deno/1.0.0"), None);
+    }
+
+    #[test]
+    fn removes_old_and_missing_workspaces() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let old_dir = base_dir.path().join("old");
+        let recent_dir = base_dir.path().join("recent");
+
+        fs::create_dir_all(&old_dir).unwrap();
+        fs::create_dir_all(&recent_dir).unwrap();
+
+        let mut usage = ToolchainUsage::default();
+
+        usage.track(&old_dir, vec![], 100);
+        usage.track(&recent_dir, vec![], 300);
+        usage.track(&base_dir.path().join("missing"), vec![], 300);
+        usage.remove_stale_workspaces(200);
+
+        assert_eq!(
+            usage.workspaces.keys().cloned().collect::<Vec<_>>(),
+            vec![recent_dir.to_string_lossy().to_string()]
+        );
+
+        base_dir.close().unwrap();
+    }
+}
+
+mod toolchain {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_used_tools() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let toolchain = create_toolchain(base_dir.path()).await;
+
+        assert_eq!(
+            toolchain.get_used_tools().unwrap(),
+            vec!["bun/1.0.0", "node/1.0.0"]
+        );
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn tracks_usage_for_workspace() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let toolchain = create_toolchain(base_dir.path()).await;
+
+        toolchain.track_usage().await.unwrap();
+
+        let usage = ToolchainUsage::load(&toolchain.tools_dir).await.unwrap();
+        let workspace = usage
+            .workspaces
+            .get(&base_dir.path().to_string_lossy().to_string())
+            .unwrap();
+
+        assert_eq!(workspace.tools, vec!["bun/1.0.0", "node/1.0.0"]);
+        assert!(workspace.last_used_time > 0);
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn lists_installed_tools() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let toolchain = create_toolchain(base_dir.path()).await;
+
+        create_tool(&toolchain, "node/1.0.0", "12345");
+        create_tool(&toolchain, "node/0.9.0", "123");
+        create_tool(&toolchain, "deno/1.0.0", "1");
+
+        toolchain.track_usage().await.unwrap();
+
+        let tools = toolchain.list_installed().await.unwrap();
+
+        assert_eq!(
+            tools.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            vec!["deno/1.0.0", "node/0.9.0", "node/1.0.0"]
+        );
+
+        assert_eq!(tools[0].name, "deno");
+        assert_eq!(tools[0].version, "1.0.0");
+        assert_eq!(tools[0].size, 1);
+        assert_eq!(tools[0].last_used_time, None);
+
+        assert_eq!(tools[1].size, 3);
+        assert_eq!(tools[1].last_used_time, None);
+
+        assert_eq!(tools[2].size, 5);
+        assert!(tools[2].last_used_time.is_some());
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    async fn prunes_unused_tools() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let toolchain = create_toolchain(base_dir.path()).await;
+        let other_dir = base_dir.path().join("other");
+        let stale_dir = base_dir.path().join("stale");
+
+        fs::create_dir_all(&other_dir).unwrap();
+        fs::create_dir_all(&stale_dir).unwrap();
+
+        create_tool(&toolchain, "node/1.0.0", "");
+        create_tool(&toolchain, "node/0.9.0", "");
+        create_tool(&toolchain, "node/0.8.0", "");
+        create_tool(&toolchain, "deno/1.0.0", "");
+
+        fs::create_dir_all(toolchain.temp_dir.join("deno/1.0.0")).unwrap();
+
+        // Another workspace used recently, and another a long time ago
+        let mut usage = ToolchainUsage::load(&toolchain.tools_dir).await.unwrap();
+        let now = now_millis();
+
+        usage.reset_time = Some(now - DAY * 90);
+        usage.track(&other_dir, vec![String::from("node/0.9.0")], now - DAY);
+        usage.track(&stale_dir, vec![String::from("node/0.8.0")], now - DAY * 60);
+        usage.save().await.unwrap();
+
+        let removed = toolchain
+            .prune(Duration::from_secs(30 * 86400))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            removed.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            vec!["deno/1.0.0", "node/0.8.0"]
+        );
+
+        assert!(toolchain.tools_dir.join("node/1.0.0").exists());
+        assert!(toolchain.tools_dir.join("node/0.9.0").exists());
+        assert!(!toolchain.tools_dir.join("node/0.8.0").exists());
+        assert!(!toolchain.tools_dir.join("deno/1.0.0").exists());
+        assert!(!toolchain.temp_dir.join("deno/1.0.0").exists());
+
+        // Stale workspaces are forgotten
+        let usage = ToolchainUsage::load(&toolchain.tools_dir).await.unwrap();
+
+        assert!(!usage
+            .workspaces
+            .contains_key(&stale_dir.to_string_lossy().to_string()));
+
+        base_dir.close().unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "UnreliableToolchainUsage")]
+    async fn refuses_to_prune_when_usage_was_reset() {
+        let base_dir = assert_fs::TempDir::new().unwrap();
+        let toolchain = create_toolchain(base_dir.path()).await;
+
+        create_tool(&toolchain, "node/0.9.0", "");
+
+        fs::create_dir_all(&toolchain.tools_dir).unwrap();
+        fs::write(toolchain.tools_dir.join(USAGE_FILENAME), "{invalid").unwrap();
+
+        toolchain
+            .prune(Duration::from_secs(30 * 86400))
+            .await
+            .unwrap();
+    }
+}
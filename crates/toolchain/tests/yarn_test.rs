@@ -13,6 +13,7 @@ async fn create_yarn_tool() -> (Toolchain, assert_fs::TempDir) {
     config.node.version = String::from("1.0.0");
     config.node.package_manager = NodePackageManager::Yarn;
     config.node.yarn = Some(YarnConfig {
+        dist_url: None,
         version: String::from("6.0.0"),
     });
 
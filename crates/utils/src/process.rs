@@ -22,7 +22,7 @@ fn create_windows_cmd(shell: Option<&str>) -> (String, TokioCommand) {
 
     let mut cmd = TokioCommand::new(&shell);
 
-    if shell.contains("cmd.exe") {
+    if is_cmd_shell(&shell) {
         cmd.arg("/d");
         cmd.arg("/s");
         cmd.arg("/q"); // Hide the script from echoing in the output
@@ -49,6 +49,110 @@ pub fn is_windows_script(bin: &str) -> bool {
     bin.ends_with(".cmd") || bin.ends_with(".bat") || bin.ends_with(".ps1")
 }
 
+/// Return true if the shell is cmd.exe, which has its own quoting rules.
+pub fn is_cmd_shell(shell: &str) -> bool {
+    // Split on both separators, as Windows paths are also checked on Unix
+    let bin_name = shell
+        .rsplit(|c: char| c == '/' || c == '\\')
+        .next()
+        .unwrap_or(shell)
+        .to_lowercase();
+
+    bin_name == "cmd" || bin_name == "cmd.exe"
+}
+
+/// Return the shell that command lines are ran through,
+/// when a specific shell has not been configured.
+pub fn get_default_shell() -> String {
+    if cfg!(windows) {
+        env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".into())
+    } else {
+        "sh".into()
+    }
+}
+
+/// Join arguments into a command line that will be ran through a shell.
+/// Arguments that contain whitespace or quotes are quoted for the shell,
+/// while all other arguments are passed through as-is, so that operators,
+/// redirects, globs, and variables are interpreted by the shell.
+pub fn join_shell_args<I, S>(shell: &str, args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            {
+                join_args_for_shell(shell, [arg])
+            } else {
+                arg.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Join arguments into a command line for cmd.exe, which does not understand
+/// POSIX single quotes. Arguments that contain whitespace, quotes, or special
+/// characters are wrapped in double quotes, so that they are passed through as-is.
+pub fn join_windows_args<I, S>(args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+
+            if !arg.is_empty()
+                && !arg.contains(|c: char| c.is_whitespace() || "\"&|<>^()%!".contains(c))
+            {
+                return arg.to_owned();
+            }
+
+            let mut quoted = String::from("\"");
+            let mut backslashes = 0;
+
+            for c in arg.chars() {
+                if c == '\\' {
+                    backslashes += 1;
+                } else {
+                    // Backslashes are only special when they precede a quote
+                    if c == '"' {
+                        quoted.push_str(&"\\".repeat(backslashes + 1));
+                    }
+
+                    backslashes = 0;
+                }
+
+                quoted.push(c);
+            }
+
+            quoted.push_str(&"\\".repeat(backslashes));
+            quoted.push('"');
+            quoted
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Join arguments into a command line for the provided shell, quoting them
+/// so that they are never interpreted by the shell.
+pub fn join_args_for_shell<I, S>(shell: &str, args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    if is_cmd_shell(shell) {
+        join_windows_args(args)
+    } else {
+        join_args(args)
+    }
+}
+
 pub fn output_to_string(data: &[u8]) -> String {
     String::from_utf8(data.to_vec()).unwrap_or_default()
 }
@@ -98,6 +202,19 @@ impl Command {
         }
     }
 
+    /// Create a command that runs the command line through the provided shell.
+    pub fn new_shell(shell: &str, command_line: &str) -> Self {
+        let (bin_name, mut cmd) = create_windows_cmd(Some(shell));
+
+        cmd.arg(command_line);
+
+        Command {
+            bin: bin_name,
+            cmd,
+            error: true,
+        }
+    }
+
     pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Command {
         self.cmd.arg(arg);
         self
@@ -404,3 +521,51 @@ impl Command {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_shell_args() {
+        assert_eq!(
+            join_shell_args(
+                "sh",
+                ["echo", "hello world", "&&", "ls", "*.js", ">", "out.txt"]
+            ),
+            "echo 'hello world' && ls *.js > out.txt"
+        );
+    }
+
+    #[test]
+    fn quotes_empty_and_quoted_shell_args() {
+        assert_eq!(
+            join_shell_args("sh", ["echo", "", "it's"]),
+            "echo '' 'it'\\''s'"
+        );
+        assert_eq!(
+            join_shell_args("cmd.exe", ["echo", "", "hello world", "&&", "dir"]),
+            "echo \"\" \"hello world\" && dir"
+        );
+    }
+
+    #[test]
+    fn quotes_all_special_args_for_posix_shells() {
+        assert_eq!(
+            join_args_for_shell("sh", ["--filter", "*.js", "a && b", "$HOME"]),
+            "--filter '*.js' 'a && b' '$HOME'"
+        );
+    }
+
+    #[test]
+    fn quotes_args_with_double_quotes_for_cmd() {
+        assert_eq!(
+            join_args_for_shell("C:\\Windows\\System32\\cmd.exe", ["--filter", "", "a && b"]),
+            "--filter \"\" \"a && b\""
+        );
+        assert_eq!(
+            join_windows_args(["say \"hi\"", "C:\\some dir\\"]),
+            "\"say \\\"hi\\\"\" \"C:\\some dir\\\\\""
+        );
+    }
+}
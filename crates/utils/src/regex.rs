@@ -17,6 +17,9 @@ lazy_static! {
     // Input values
     pub static ref ENV_VAR: Regex = Regex::new("^\\$[A-Z0-9_]+$").unwrap();
 
+    // Env var interpolation: `${VAR}` or `${VAR:-default}`
+    pub static ref ENV_VAR_SUBSTITUTE: Regex = Regex::new("\\$\\{([A-Za-z0-9_]+)(?::-([^}]*))?\\}").unwrap();
+
     // Token function: `@func(arg)`
     static ref TOKEN_GROUP: &'static str = "([0-9A-Za-z_-]+)";
 
@@ -25,9 +28,18 @@ lazy_static! {
     pub static ref TOKEN_VAR_PATTERN: Regex = Regex::new("\\$(language|projectRoot|projectSource|projectType|project|target|taskType|task|workspaceRoot)").unwrap();
 
     // Task commands (these are not exhaustive)
+    pub static ref BUN_COMMAND: regex::Regex =
+                Regex::new("^(bun|bunx)$").unwrap();
+
+    pub static ref DENO_COMMAND: regex::Regex =
+                Regex::new("^(deno)$").unwrap();
+
     pub static ref NODE_COMMAND: regex::Regex =
                 Regex::new("^(node|nodejs|npm|npx|yarn|pnpm|corepack)$").unwrap();
 
+    pub static ref PYTHON_COMMAND: regex::Regex =
+                Regex::new("^(python|python3|pip|pip3)$").unwrap();
+
     pub static ref UNIX_SYSTEM_COMMAND: regex::Regex =
                 Regex::new("^(bash|cat|cd|chmod|cp|docker|echo|find|git|grep|make|mkdir|mv|pwd|rm|rsync|svn)$").unwrap();
 
@@ -6,3 +6,106 @@ pub fn extract_major_version(version: &str) -> u64 {
         Err(_) => 0,
     }
 }
+
+fn is_operator(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| matches!(c, '<' | '>' | '=' | '~' | '^'))
+}
+
+// npm treats a bare version as exact, and a partial version as an X-range,
+// while the semver crate treats both as caret requirements.
+fn normalize_comparator(comparator: String) -> String {
+    if comparator.starts_with(|c: char| c.is_ascii_digit()) {
+        if semver::Version::parse(&comparator).is_ok() {
+            return format!("={}", comparator);
+        }
+
+        return format!("~{}", comparator);
+    }
+
+    comparator
+}
+
+/// Parse an npm compatible version range (`16.13`, `>=14 <17`, `^16 || ^18`, etc)
+/// into a list of requirements, of which any may match. Returns `None` if any
+/// part of the range is invalid.
+pub fn parse_npm_range(range: &str) -> Option<Vec<VersionReq>> {
+    range
+        .split("||")
+        .map(|part| {
+            let mut comparators: Vec<String> = vec![];
+
+            for token in part.split_whitespace() {
+                let token = token.trim_start_matches('v');
+
+                match comparators.last_mut() {
+                    Some(last) if is_operator(last) => last.push_str(token),
+                    _ => comparators.push(token.to_owned()),
+                };
+            }
+
+            if comparators.is_empty() {
+                return None;
+            }
+
+            let comparators = comparators
+                .into_iter()
+                .map(normalize_comparator)
+                .collect::<Vec<_>>();
+
+            VersionReq::parse(&comparators.join(", ")).ok()
+        })
+        .collect()
+}
+
+/// Return the highest version that satisfies any of the requirements.
+pub fn find_highest_matching<'a, I>(requirements: &[VersionReq], versions: I) -> Option<&'a Version>
+where
+    I: IntoIterator<Item = &'a Version>,
+{
+    versions
+        .into_iter()
+        .filter(|version| requirements.iter().any(|req| req.matches(version)))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> Vec<Version> {
+        ["14.20.0", "16.13.0", "16.13.2", "16.17.1", "18.10.0"]
+            .iter()
+            .map(|v| Version::parse(v).unwrap())
+            .collect()
+    }
+
+    fn resolve(range: &str) -> Option<String> {
+        let versions = versions();
+
+        find_highest_matching(&parse_npm_range(range).unwrap(), &versions).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn parses_npm_ranges() {
+        assert!(parse_npm_range("16").is_some());
+        assert!(parse_npm_range("v16.13.0").is_some());
+        assert!(parse_npm_range(">=14 <17").is_some());
+        assert!(parse_npm_range(">= 14").is_some());
+        assert!(parse_npm_range("^16 || ^18").is_some());
+        assert!(parse_npm_range("lts/*").is_none());
+        assert!(parse_npm_range("").is_none());
+    }
+
+    #[test]
+    fn resolves_highest_matching_version() {
+        assert_eq!(resolve("16"), Some("16.17.1".into()));
+        assert_eq!(resolve("16.13"), Some("16.13.2".into()));
+        assert_eq!(resolve("16.13.0"), Some("16.13.0".into()));
+        assert_eq!(resolve(">=14 <17"), Some("16.17.1".into()));
+        assert_eq!(resolve("^14 || ^18"), Some("18.10.0".into()));
+        assert_eq!(resolve("^20"), None);
+    }
+}
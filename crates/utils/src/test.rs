@@ -61,6 +61,83 @@ pub fn create_sandbox_with_git<T: AsRef<str>>(fixture: T) -> assert_fs::fixture:
     temp_dir
 }
 
+pub fn create_sandbox_with_git_submodule<T: AsRef<str>>(
+    fixture: T,
+    submodule_fixture: T,
+    submodule_path: &str,
+) -> assert_fs::fixture::TempDir {
+    let temp_dir = create_sandbox_with_git(fixture);
+    let submodule_dir = create_sandbox_with_git(submodule_fixture);
+
+    // Local submodules are disallowed by default in newer git versions
+    run_git_command(temp_dir.path(), |cmd| {
+        cmd.args(["-c", "protocol.file.allow=always", "submodule", "add"])
+            .arg(submodule_dir.path())
+            .arg(submodule_path);
+    });
+
+    run_git_command(temp_dir.path(), |cmd| {
+        cmd.args(["commit", "-m", "Submodule"])
+            .env("GIT_AUTHOR_NAME", "moon tests")
+            .env("GIT_AUTHOR_EMAIL", "fakeemail@moonrepo.dev")
+            .env("GIT_COMMITTER_NAME", "moon tests")
+            .env("GIT_COMMITTER_EMAIL", "fakeemail@moonrepo.dev");
+    });
+
+    temp_dir
+}
+
+pub fn run_hg_command<P, F>(dir: P, handler: F)
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut Command),
+{
+    let mut cmd = Command::new(if cfg!(windows) { "hg.exe" } else { "hg" });
+    cmd.current_dir(dir.as_ref())
+        .env("HGPLAIN", "1")
+        .env("HGUSER", "moon tests <fakeemail@moonrepo.dev>");
+
+    handler(&mut cmd);
+
+    let out = cmd.output().unwrap_or_else(|e| {
+        panic!("{:#?}", e);
+    });
+
+    if !out.status.success() {
+        println!("{}", output_to_string(&out.stdout));
+        eprintln!("{}", output_to_string(&out.stderr));
+    }
+}
+
+pub fn create_sandbox_with_hg<T: AsRef<str>>(fixture: T) -> assert_fs::fixture::TempDir {
+    let temp_dir = create_sandbox(fixture);
+    let gitignore = temp_dir.path().join(".gitignore");
+
+    // hg does not read .gitignore files, so mirror them as glob patterns
+    if gitignore.exists() {
+        std::fs::write(
+            temp_dir.path().join(".hgignore"),
+            format!(
+                "syntax: glob\n{}",
+                std::fs::read_to_string(gitignore).unwrap()
+            ),
+        )
+        .unwrap();
+    }
+
+    // Initialize a hg repo so that VCS commands work
+    run_hg_command(temp_dir.path(), |cmd| {
+        cmd.arg("init");
+    });
+
+    // And commit the files, which are added implicitly
+    run_hg_command(temp_dir.path(), |cmd| {
+        cmd.args(["commit", "--addremove", "--message", "Fixtures"]);
+    });
+
+    temp_dir
+}
+
 pub fn get_fixtures_root() -> PathBuf {
     let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     root.push("../../tests/fixtures");
@@ -0,0 +1,94 @@
+use crate::errors::VcsError;
+use crate::vcs::VcsResult;
+use moon_error::map_io_to_process_error;
+use moon_logger::{color, trace};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+const LOG_TARGET: &str = "moon:vcs:batch";
+
+/// A long-lived process that reads inputs from stdin, and writes a single line
+/// of output for each input line. For example, `git cat-file --batch-check`.
+pub struct BatchProcess {
+    command_line: String,
+
+    // Kept so that the process is killed when dropped
+    #[allow(dead_code)]
+    child: Child,
+
+    stdin: ChildStdin,
+
+    stdout: BufReader<ChildStdout>,
+}
+
+impl BatchProcess {
+    pub fn spawn(bin: &str, args: &[&str], cwd: &Path) -> VcsResult<Self> {
+        let command_line = format!("{} {}", bin, args.join(" "));
+
+        trace!(
+            target: LOG_TARGET,
+            "Spawning batch process {}",
+            color::shell(&command_line)
+        );
+
+        let mut child = Command::new(bin)
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| map_io_to_process_error(e, &command_line))?;
+
+        Ok(BatchProcess {
+            stdin: child.stdin.take().unwrap(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+            command_line,
+        })
+    }
+
+    /// Write all inputs to the process, and return an output line for each input.
+    pub async fn query(&mut self, inputs: &[String]) -> VcsResult<Vec<String>> {
+        let mut input = inputs.join("\n");
+        input.push('\n');
+
+        // Write and read at the same time, otherwise the process may block
+        // on a full stdout pipe while we're still writing to its stdin
+        let stdin = &mut self.stdin;
+        let stdout = &mut self.stdout;
+
+        let write = async {
+            stdin.write_all(input.as_bytes()).await?;
+            stdin.flush().await
+        };
+
+        let read = async {
+            let mut lines = Vec::with_capacity(inputs.len());
+
+            for _ in 0..inputs.len() {
+                let mut line = String::new();
+
+                if stdout.read_line(&mut line).await? == 0 {
+                    return Ok(None);
+                }
+
+                lines.push(line.trim_end().to_owned());
+            }
+
+            Ok::<_, std::io::Error>(Some(lines))
+        };
+
+        let (write_result, read_result) = tokio::join!(write, read);
+
+        write_result.map_err(|e| map_io_to_process_error(e, &self.command_line))?;
+
+        match read_result.map_err(|e| map_io_to_process_error(e, &self.command_line))? {
+            Some(lines) => Ok(lines),
+            None => Err(VcsError::BatchProcessExited(self.command_line.clone())),
+        }
+    }
+}
@@ -4,6 +4,9 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum VcsError {
+    #[error("Batch process <shell>{0}</shell> exited unexpectedly.")]
+    BatchProcessExited(String),
+
     #[error(transparent)]
     Ignore(#[from] IgnoreError),
 
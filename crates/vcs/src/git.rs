@@ -1,20 +1,79 @@
+use crate::batch::BatchProcess;
 use crate::errors::VcsError;
 use crate::vcs::{TouchedFiles, Vcs, VcsResult};
 use async_trait::async_trait;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use lazy_static::lazy_static;
+use moon_error::map_io_to_fs_error;
 use moon_utils::process::{output_to_string, output_to_trimmed_string, Command};
 use moon_utils::{fs, string_vec};
 use regex::Regex;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::SystemTime;
+use tokio::sync::{Mutex, OnceCell, RwLock};
+
+lazy_static! {
+    // `git status --porcelain` XY status codes
+    static ref STATUS_XY: Regex =
+        Regex::new(r"^(M|T|A|D|R|C|U|\?|!| )(M|T|A|D|R|C|U|\?|!| ) ").unwrap();
+
+    // `git diff --name-status` X status codes, with and without a similarity score
+    static ref DIFF_X_WITH_SCORE: Regex = Regex::new(r"^(C|M|R)(\d{3})$").unwrap();
+    static ref DIFF_X: Regex = Regex::new(r"^(A|D|M|T|U|X)$").unwrap();
+}
+
+// A submodule that has been initialized within the repository
+struct Submodule {
+    git: Git,
+
+    // Relative from the parent repository root
+    path: String,
+}
+
+impl Submodule {
+    /// Return the file path relative from the submodule root,
+    /// or an empty string if the path is the submodule itself.
+    fn relative_path<'a>(&self, file: &'a str) -> Option<&'a str> {
+        if file == self.path {
+            return Some("");
+        }
+
+        file.strip_prefix(&self.path)
+            .and_then(|rest| rest.strip_prefix('/'))
+    }
+
+    fn prefix_path(&self, file: &str) -> String {
+        format!("{}/{}", self.path, file)
+    }
+}
 
 pub struct Git {
     cache: Arc<RwLock<HashMap<String, String>>>,
+
+    // Long-lived `git cat-file --batch-check` process
+    cat_file: Arc<Mutex<Option<BatchProcess>>>,
+
     default_branch: String,
+
+    // Memoized tree hashes for the session, keyed by directory
+    file_tree_hashes: Arc<RwLock<HashMap<String, BTreeMap<String, String>>>>,
+
+    // Long-lived `git hash-object --stdin-paths` process
+    hash_object: Arc<Mutex<Option<BatchProcess>>>,
+
     ignore: Option<Gitignore>,
+
     root: PathBuf,
+
+    // Files modified after this time must be hashed from their contents
+    session_start: SystemTime,
+
+    submodules: Vec<Submodule>,
+
+    // Memoized touched files for the session
+    touched_files: Arc<OnceCell<TouchedFiles>>,
 }
 
 impl Git {
@@ -39,12 +98,97 @@ impl Git {
 
         Ok(Git {
             cache: Arc::new(RwLock::new(HashMap::new())),
+            cat_file: Arc::new(Mutex::new(None)),
             default_branch: String::from(default_branch),
+            file_tree_hashes: Arc::new(RwLock::new(HashMap::new())),
+            hash_object: Arc::new(Mutex::new(None)),
             ignore,
+            session_start: SystemTime::now(),
+            submodules: Git::load_submodules(&root, default_branch)?,
+            touched_files: Arc::new(OnceCell::new()),
             root,
         })
     }
 
+    fn load_submodules(root: &Path, default_branch: &str) -> VcsResult<Vec<Submodule>> {
+        let mut submodules = vec![];
+        let modules_path = root.join(".gitmodules");
+
+        if !modules_path.exists() {
+            return Ok(submodules);
+        }
+
+        let modules = std::fs::read_to_string(&modules_path)
+            .map_err(|e| map_io_to_fs_error(e, modules_path.to_path_buf()))?;
+
+        // [submodule "name"]
+        //     path = some/path
+        //     url = ...
+        for line in modules.lines() {
+            let path = match line.trim().strip_prefix("path") {
+                Some(rest) => match rest.trim_start().strip_prefix('=') {
+                    Some(path) => path.trim().trim_end_matches('/').to_owned(),
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            // Uninitialized submodules are an empty directory,
+            // and would resolve to this repository instead
+            if !root.join(&path).join(".git").exists() {
+                continue;
+            }
+
+            submodules.push(Submodule {
+                git: Git::new(default_branch, &root.join(&path))?,
+                path,
+            });
+        }
+
+        Ok(submodules)
+    }
+
+    fn find_submodule(&self, file: &str) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| submodule.relative_path(file).is_some())
+    }
+
+    /// Submodules are a single entry within the parent repository, so expand a
+    /// commit bump of a submodule into touches of every file within it, and
+    /// optionally include the submodule's own working tree changes.
+    async fn expand_submodule_touches(
+        &self,
+        touched_files: &mut TouchedFiles,
+        include_working_tree: bool,
+    ) -> VcsResult<()> {
+        for submodule in &self.submodules {
+            if touched_files.all.contains(&submodule.path) {
+                let staged = touched_files.staged.contains(&submodule.path);
+
+                for file in submodule.git.get_file_tree_hashes(".").await?.into_keys() {
+                    let file = submodule.prefix_path(&file);
+
+                    if staged {
+                        touched_files.staged.insert(file.clone());
+                    } else {
+                        touched_files.unstaged.insert(file.clone());
+                    }
+
+                    touched_files.modified.insert(file.clone());
+                    touched_files.all.insert(file);
+                }
+            }
+
+            if include_working_tree {
+                touched_files
+                    .merge_with_prefix(submodule.git.get_touched_files().await?, &submodule.path);
+            }
+        }
+
+        Ok(())
+    }
+
     async fn get_merge_base(&self, base: &str, head: &str) -> VcsResult<String> {
         let mut args = string_vec!["merge-base", head];
 
@@ -93,6 +237,156 @@ impl Git {
         }
     }
 
+    // https://git-scm.com/docs/git-status#_short_format
+    async fn load_touched_files(&self) -> VcsResult<TouchedFiles> {
+        let output = self
+            .run_command(
+                &mut self.create_command(vec![
+                    "status",
+                    "--porcelain",
+                    "--untracked-files",
+                    "--find-renames",
+                    // Changes within submodules are loaded from the submodule itself
+                    "--ignore-submodules=dirty",
+                    // We use this option so that file names with special characters
+                    // are displayed as-is and are not quoted/escaped
+                    "-z",
+                ]),
+                false,
+            )
+            .await?;
+
+        let mut added = HashSet::new();
+        let mut deleted = HashSet::new();
+        let mut modified = HashSet::new();
+        let mut untracked = HashSet::new();
+        let mut copied = HashMap::new();
+        let mut renamed = HashMap::new();
+        let mut staged = HashSet::new();
+        let mut unstaged = HashSet::new();
+        let mut all = HashSet::new();
+        let mut entries = output.split('\0');
+
+        // Lines are terminated by a NUL byte:
+        //  XY file\0
+        //  XY file\0orig_file\0
+        while let Some(line) = entries.next() {
+            if line.is_empty() || !STATUS_XY.is_match(line) {
+                continue;
+            }
+
+            // XY file\0
+            let mut chars = line.chars();
+            let x = chars.next().unwrap_or_default();
+            let y = chars.next().unwrap_or_default();
+            let file = String::from(&line[3..]);
+
+            // orig_file\0
+            if matches!(x, 'R' | 'C') || matches!(y, 'R' | 'C') {
+                let orig_file = entries.next().unwrap_or_default().to_owned();
+                let is_staged = matches!(x, 'R' | 'C');
+
+                if !orig_file.is_empty() {
+                    // The original file no longer exists when renamed
+                    if x == 'R' || y == 'R' {
+                        deleted.insert(orig_file.clone());
+                        all.insert(orig_file.clone());
+
+                        if is_staged {
+                            staged.insert(orig_file.clone());
+                        } else {
+                            unstaged.insert(orig_file.clone());
+                        }
+
+                        renamed.insert(file.clone(), orig_file);
+                    } else {
+                        copied.insert(file.clone(), orig_file);
+                    }
+                }
+            }
+
+            match x {
+                'A' | 'C' => {
+                    added.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                'D' => {
+                    deleted.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                'M' | 'R' => {
+                    modified.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                _ => {}
+            }
+
+            match y {
+                'A' | 'C' => {
+                    added.insert(file.clone());
+                    unstaged.insert(file.clone());
+                }
+                'D' => {
+                    deleted.insert(file.clone());
+                    unstaged.insert(file.clone());
+                }
+                'M' | 'R' => {
+                    modified.insert(file.clone());
+                    unstaged.insert(file.clone());
+                }
+                '?' => {
+                    untracked.insert(file.clone());
+                }
+                _ => {}
+            }
+
+            all.insert(file.clone());
+        }
+
+        let mut touched_files = TouchedFiles {
+            added,
+            all,
+            copied,
+            deleted,
+            modified,
+            renamed,
+            staged,
+            unstaged,
+            untracked,
+        };
+
+        self.expand_submodule_touches(&mut touched_files, true)
+            .await?;
+
+        Ok(touched_files)
+    }
+
+    async fn run_batch(
+        &self,
+        process: &Mutex<Option<BatchProcess>>,
+        args: &[&str],
+        inputs: &[String],
+    ) -> VcsResult<Vec<String>> {
+        if inputs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut process = process.lock().await;
+
+        if process.is_none() {
+            *process = Some(BatchProcess::spawn("git", args, &self.root)?);
+        }
+
+        let result = process.as_mut().unwrap().query(inputs).await;
+
+        // The process is in an unknown state, so spawn a new one on the next call
+        if result.is_err() {
+            *process = None;
+        }
+
+        result
+    }
+
     async fn run_command(&self, command: &mut Command, trim: bool) -> VcsResult<String> {
         let (cache_key, _) = command.get_command_line();
 
@@ -154,157 +448,179 @@ impl Vcs for Git {
         .await
     }
 
+    async fn get_hooks_dir(&self) -> VcsResult<Option<PathBuf>> {
+        // Respects `core.hooksPath` and worktrees. The path is relative
+        // from the working directory, unless configured as absolute.
+        let hooks_dir = self
+            .run_command(
+                &mut self.create_command(vec!["rev-parse", "--git-path", "hooks"]),
+                true,
+            )
+            .await?;
+
+        Ok(Some(self.root.join(hooks_dir)))
+    }
+
     async fn get_file_hashes(&self, files: &[String]) -> VcsResult<BTreeMap<String, String>> {
-        let mut objects = vec![];
+        let touched_files = self.get_touched_files().await?;
+        let mut committed = vec![];
+        let mut changed = vec![];
+        let mut map = BTreeMap::new();
 
-        for file in files {
-            if !self.is_file_ignored(file) {
-                objects.push(file.clone());
+        // Files within submodules must be hashed by the submodule itself
+        for submodule in &self.submodules {
+            let submodule_files = files
+                .iter()
+                .filter_map(|file| submodule.relative_path(file))
+                .filter(|file| !file.is_empty())
+                .map(|file| file.to_owned())
+                .collect::<Vec<_>>();
+
+            if !submodule_files.is_empty() {
+                for (file, hash) in submodule.git.get_file_hashes(&submodule_files).await? {
+                    map.insert(submodule.prefix_path(&file), hash);
+                }
             }
         }
 
-        let output = self
-            .create_command(vec!["hash-object", "--stdin-paths"])
-            .exec_capture_output_with_input(&objects.join("\n"))
-            .await?;
-        let output = output_to_trimmed_string(&output.stdout);
-
-        let mut map = BTreeMap::new();
+        for file in files {
+            if self.is_file_ignored(file) || self.find_submodule(file).is_some() {
+                continue;
+            }
 
-        for (index, hash) in output.split('\n').enumerate() {
-            if !hash.is_empty() {
-                map.insert(objects[index].clone(), hash.to_owned());
+            // Directories and missing files will crash `git hash-object`
+            let meta = match self.root.join(file).metadata() {
+                Ok(meta) if meta.is_file() => meta,
+                _ => continue,
+            };
+
+            // Files modified during this session (by another task for example)
+            // are not reflected in the memoized touched files
+            let modified_in_session = meta
+                .modified()
+                .map(|time| time >= self.session_start)
+                .unwrap_or(true);
+
+            if modified_in_session || touched_files.all.contains(file) {
+                changed.push(file.clone());
+            } else {
+                committed.push(file.clone());
             }
         }
 
-        Ok(map)
-    }
+        // Unchanged files have the same hash as their committed object,
+        // which is much cheaper to lookup than hashing their contents
+        let objects = committed
+            .iter()
+            .map(|file| format!("HEAD:{}", file))
+            .collect::<Vec<_>>();
 
-    async fn get_file_tree_hashes(&self, dir: &str) -> VcsResult<BTreeMap<String, String>> {
         let output = self
-            .run_command(
-                &mut self.create_command(vec!["ls-tree", "HEAD", "-r", dir]),
-                true,
-            )
+            .run_batch(&self.cat_file, &["cat-file", "--batch-check"], &objects)
             .await?;
 
-        let mut map = BTreeMap::new();
-
-        if output.is_empty() {
-            return Ok(map);
+        for (file, line) in committed.into_iter().zip(output) {
+            // <hash> <type> <size>
+            // <object> missing
+            if line.ends_with(" missing") {
+                changed.push(file);
+            } else if let Some(hash) = line.split(' ').next() {
+                map.insert(file, hash.to_owned());
+            }
         }
 
-        for line in output.split('\n') {
-            // <mode> <type> <hash>\t<file>
-            let parts = line.split(' ');
-            // <hash>\t<file>
-            let mut last_parts = parts.last().unwrap_or_default().split('\t');
-            let hash = last_parts.next().unwrap_or_default();
-            let file = last_parts.next().unwrap_or_default();
+        let output = self
+            .run_batch(
+                &self.hash_object,
+                &["hash-object", "--stdin-paths"],
+                &changed,
+            )
+            .await?;
 
-            if !hash.is_empty() && !file.is_empty() && !self.is_file_ignored(file) {
-                map.insert(file.to_owned(), hash.to_owned());
+        for (file, hash) in changed.into_iter().zip(output) {
+            if !hash.is_empty() {
+                map.insert(file, hash);
             }
         }
 
         Ok(map)
     }
 
-    // https://git-scm.com/docs/git-status#_short_format
-    async fn get_touched_files(&self) -> VcsResult<TouchedFiles> {
-        let output = self
-            .run_command(
-                &mut self.create_command(vec![
-                    "status",
-                    "--porcelain",
-                    "--untracked-files",
-                    // We use this option so that file names with special characters
-                    // are displayed as-is and are not quoted/escaped
-                    "-z",
-                ]),
-                false,
-            )
-            .await?;
+    async fn get_file_tree_hashes(&self, dir: &str) -> VcsResult<BTreeMap<String, String>> {
+        // Read first before computing and writing
+        {
+            let file_tree_hashes = self.file_tree_hashes.read().await;
 
-        if output.is_empty() {
-            return Ok(TouchedFiles::default());
+            if let Some(map) = file_tree_hashes.get(dir) {
+                return Ok(map.clone());
+            }
         }
 
-        let mut added = HashSet::new();
-        let mut deleted = HashSet::new();
-        let mut modified = HashSet::new();
-        let mut untracked = HashSet::new();
-        let mut staged = HashSet::new();
-        let mut unstaged = HashSet::new();
-        let mut all = HashSet::new();
-        let xy_regex = Regex::new(r"^(M|T|A|D|R|C|U|\?|!| )(M|T|A|D|R|C|U|\?|!| ) ").unwrap();
+        let mut map = BTreeMap::new();
 
-        // Lines are terminated by a NUL byte:
-        //  XY file\0
-        //  XY file\0orig_file\0
-        for line in output.split('\0') {
-            if line.is_empty() {
-                continue;
-            }
+        // The directory is within a submodule, so defer to the submodule
+        if let Some(submodule) = self.find_submodule(dir) {
+            let submodule_dir = match submodule.relative_path(dir) {
+                Some(path) if !path.is_empty() => path,
+                _ => ".",
+            };
 
-            // orig_file\0
-            if !xy_regex.is_match(line) {
-                continue;
+            for (file, hash) in submodule.git.get_file_tree_hashes(submodule_dir).await? {
+                map.insert(submodule.prefix_path(&file), hash);
             }
+        } else {
+            let output = self
+                .run_command(
+                    &mut self.create_command(vec!["ls-tree", "HEAD", "-r", dir]),
+                    true,
+                )
+                .await?;
+
+            for line in output.split('\n') {
+                // <mode> <type> <hash>\t<file>
+                let mut parts = line.split(' ');
+                let object_type = parts.nth(1).unwrap_or_default();
+                // <hash>\t<file>
+                let mut last_parts = parts.last().unwrap_or_default().split('\t');
+                let hash = last_parts.next().unwrap_or_default();
+                let file = last_parts.next().unwrap_or_default();
+
+                if hash.is_empty() || file.is_empty() || self.is_file_ignored(file) {
+                    continue;
+                }
 
-            // XY file\0
-            let mut chars = line.chars();
-            let x = chars.next().unwrap_or_default();
-            let y = chars.next().unwrap_or_default();
-            let file = String::from(&line[3..]);
+                // Submodules are listed as a commit, so recurse into them.
+                // Uninitialized submodules will use the commit hash instead.
+                if object_type == "commit" {
+                    if let Some(submodule) = self.find_submodule(file) {
+                        for (file, hash) in submodule.git.get_file_tree_hashes(".").await? {
+                            map.insert(submodule.prefix_path(&file), hash);
+                        }
 
-            match x {
-                'A' | 'C' => {
-                    added.insert(file.clone());
-                    staged.insert(file.clone());
+                        continue;
+                    }
                 }
-                'D' => {
-                    deleted.insert(file.clone());
-                    staged.insert(file.clone());
-                }
-                'M' | 'R' => {
-                    modified.insert(file.clone());
-                    staged.insert(file.clone());
-                }
-                _ => {}
-            }
 
-            match y {
-                'A' | 'C' => {
-                    added.insert(file.clone());
-                    unstaged.insert(file.clone());
-                }
-                'D' => {
-                    deleted.insert(file.clone());
-                    unstaged.insert(file.clone());
-                }
-                'M' | 'R' => {
-                    modified.insert(file.clone());
-                    unstaged.insert(file.clone());
-                }
-                '?' => {
-                    untracked.insert(file.clone());
-                }
-                _ => {}
+                map.insert(file.to_owned(), hash.to_owned());
             }
-
-            all.insert(file.clone());
         }
 
-        Ok(TouchedFiles {
-            added,
-            all,
-            deleted,
-            modified,
-            staged,
-            unstaged,
-            untracked,
-        })
+        self.file_tree_hashes
+            .write()
+            .await
+            .insert(dir.to_owned(), map.clone());
+
+        Ok(map)
+    }
+
+    // Touched files are memoized for the session, as they're requested for every target
+    async fn get_touched_files(&self) -> VcsResult<TouchedFiles> {
+        let touched_files = self
+            .touched_files
+            .get_or_try_init(|| self.load_touched_files())
+            .await?;
+
+        Ok(touched_files.clone())
     }
 
     async fn get_touched_files_against_previous_revision(
@@ -335,6 +651,7 @@ impl Vcs for Git {
                     "--no-pager",
                     "diff",
                     "--name-status",
+                    "--find-renames",
                     "--no-color",
                     "--relative",
                     // We use this option so that file names with special characters
@@ -346,37 +663,51 @@ impl Vcs for Git {
             )
             .await?;
 
-        if output.is_empty() {
-            return Ok(TouchedFiles::default());
-        }
-
         let mut added = HashSet::new();
         let mut deleted = HashSet::new();
         let mut modified = HashSet::new();
+        let mut copied = HashMap::new();
+        let mut renamed = HashMap::new();
         let mut staged = HashSet::new();
         let mut all = HashSet::new();
-        let x_with_score_regex = Regex::new(r"^(C|M|R)(\d{3})$").unwrap();
-        let x_regex = Regex::new(r"^(A|D|M|T|U|X)$").unwrap();
-        let mut last_status = "A";
+        let mut fields = output.split('\0').filter(|field| !field.is_empty());
 
         // Lines AND statuses are terminated by a NUL byte
         //  X\0file\0
         //  X000\0file\0
-        //  X000\0file\0renamed_file\0
-        for line in output.split('\0') {
-            if line.is_empty() {
-                continue;
-            }
-
+        //  X000\0orig_file\0file\0
+        while let Some(status) = fields.next() {
             // X\0
             // X000\0
-            if x_with_score_regex.is_match(line) || x_regex.is_match(line) {
-                last_status = &line[0..1];
+            if !DIFF_X_WITH_SCORE.is_match(status) && !DIFF_X.is_match(status) {
                 continue;
             }
 
-            let x = last_status.chars().next().unwrap_or_default();
-            let file = line.to_owned();
+            let x = status.chars().next().unwrap_or_default();
+            let mut file = match fields.next() {
+                Some(file) => file.to_owned(),
+                None => break,
+            };
+
+            // Renames and copies include the original file first
+            if x == 'R' || x == 'C' {
+                let orig_file = file;
+
+                file = match fields.next() {
+                    Some(file) => file.to_owned(),
+                    None => break,
+                };
+
+                // The original file no longer exists when renamed
+                if x == 'R' {
+                    deleted.insert(orig_file.clone());
+                    staged.insert(orig_file.clone());
+                    all.insert(orig_file.clone());
+                    renamed.insert(file.clone(), orig_file);
+                } else {
+                    copied.insert(file.clone(), orig_file);
+                }
+            }
 
             match x {
                 'A' | 'C' => {
@@ -397,15 +728,22 @@ impl Vcs for Git {
             all.insert(file.clone());
         }
 
-        Ok(TouchedFiles {
+        let mut touched_files = TouchedFiles {
             added,
             all,
+            copied,
             deleted,
             modified,
+            renamed,
             staged,
             unstaged: HashSet::new(),
             untracked: HashSet::new(),
-        })
+        };
+
+        self.expand_submodule_touches(&mut touched_files, false)
+            .await?;
+
+        Ok(touched_files)
     }
 
     fn is_default_branch(&self, branch: &str) -> bool {
@@ -429,7 +767,7 @@ impl Vcs for Git {
 mod test {
     use super::*;
     use moon_utils::string_vec;
-    use moon_utils::test::create_sandbox_with_git;
+    use moon_utils::test::{create_sandbox_with_git, run_git_command};
 
     mod get_file_hashes {
         use super::*;
@@ -457,6 +795,134 @@ mod test {
         }
     }
 
+    mod get_file_hashes_batch {
+        use super::*;
+
+        #[tokio::test]
+        async fn hashes_committed_and_modified_files() {
+            let fixture = create_sandbox_with_git("ignore");
+
+            std::fs::write(fixture.path().join("foo"), "modified").unwrap();
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let files = string_vec!["foo", "dir/qux", "missing"];
+            let hashes = git.get_file_hashes(&files).await.unwrap();
+
+            assert_eq!(
+                hashes,
+                BTreeMap::from([
+                    (
+                        "dir/qux".to_owned(),
+                        "100b0dec8c53a40e4de7714b2c612dad5fad9985".to_owned()
+                    ),
+                    (
+                        "foo".to_owned(),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    )
+                ])
+            );
+
+            // Reuses the running processes
+            assert_eq!(git.get_file_hashes(&files).await.unwrap(), hashes);
+        }
+
+        #[tokio::test]
+        async fn hashes_files_modified_during_session() {
+            let fixture = create_sandbox_with_git("ignore");
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            // Memoize touched files before modifying
+            assert!(git.get_touched_files().await.unwrap().all.is_empty());
+
+            std::fs::write(fixture.path().join("foo"), "modified").unwrap();
+
+            assert_eq!(
+                git.get_file_hashes(&string_vec!["foo"]).await.unwrap(),
+                BTreeMap::from([(
+                    "foo".to_owned(),
+                    "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                )])
+            );
+        }
+    }
+
+    mod get_touched_files {
+        use super::*;
+
+        #[tokio::test]
+        async fn tracks_renames() {
+            let fixture = create_sandbox_with_git("ignore");
+
+            run_git_command(fixture.path(), |cmd| {
+                cmd.args(["mv", "foo", "dir/moved"]);
+            });
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let touched_files = git.get_touched_files().await.unwrap();
+
+            assert_eq!(
+                touched_files.renamed,
+                HashMap::from([("dir/moved".to_owned(), "foo".to_owned())])
+            );
+            assert_eq!(touched_files.deleted, HashSet::from(["foo".to_owned()]));
+            assert_eq!(
+                touched_files.all,
+                HashSet::from(["foo".to_owned(), "dir/moved".to_owned()])
+            );
+        }
+
+        #[tokio::test]
+        async fn tracks_renames_between_revisions() {
+            let fixture = create_sandbox_with_git("ignore");
+
+            run_git_command(fixture.path(), |cmd| {
+                cmd.args(["mv", "foo", "dir/moved"]);
+            });
+
+            run_git_command(fixture.path(), |cmd| {
+                cmd.args(["commit", "-m", "Rename"])
+                    .env("GIT_AUTHOR_NAME", "moon tests")
+                    .env("GIT_AUTHOR_EMAIL", "fakeemail@moonrepo.dev")
+                    .env("GIT_COMMITTER_NAME", "moon tests")
+                    .env("GIT_COMMITTER_EMAIL", "fakeemail@moonrepo.dev");
+            });
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let touched_files = git
+                .get_touched_files_between_revisions("HEAD~1", "HEAD")
+                .await
+                .unwrap();
+
+            assert_eq!(
+                touched_files.renamed,
+                HashMap::from([("dir/moved".to_owned(), "foo".to_owned())])
+            );
+            assert_eq!(
+                touched_files.all,
+                HashSet::from(["foo".to_owned(), "dir/moved".to_owned()])
+            );
+        }
+
+        #[tokio::test]
+        async fn memoizes_for_session() {
+            let fixture = create_sandbox_with_git("ignore");
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            std::fs::write(fixture.path().join("foo"), "modified").unwrap();
+
+            let touched_files = git.get_touched_files().await.unwrap();
+
+            assert_eq!(touched_files.modified, HashSet::from(["foo".to_owned()]));
+
+            std::fs::write(fixture.path().join("dir/qux"), "modified").unwrap();
+
+            assert_eq!(
+                git.get_touched_files().await.unwrap().modified,
+                touched_files.modified
+            );
+        }
+    }
+
     mod get_file_tree_hashes {
         use super::*;
 
@@ -484,4 +950,143 @@ mod test {
             );
         }
     }
+
+    mod get_hooks_dir {
+        use super::*;
+
+        #[tokio::test]
+        async fn defaults_to_git_dir() {
+            let fixture = create_sandbox_with_git("ignore");
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            assert_eq!(
+                git.get_hooks_dir().await.unwrap(),
+                Some(fixture.path().join(".git/hooks"))
+            );
+        }
+
+        #[tokio::test]
+        async fn respects_hooks_path() {
+            let fixture = create_sandbox_with_git("ignore");
+
+            run_git_command(fixture.path(), |cmd| {
+                cmd.args(["config", "core.hooksPath", ".githooks"]);
+            });
+
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            assert_eq!(
+                git.get_hooks_dir().await.unwrap(),
+                Some(fixture.path().join(".githooks"))
+            );
+        }
+    }
+
+    mod submodules {
+        use super::*;
+        use moon_utils::test::create_sandbox_with_git_submodule;
+
+        fn commit(dir: &Path, message: &str) {
+            run_git_command(dir, |cmd| {
+                cmd.args(["commit", "--all", "-m", message])
+                    .env("GIT_AUTHOR_NAME", "moon tests")
+                    .env("GIT_AUTHOR_EMAIL", "fakeemail@moonrepo.dev")
+                    .env("GIT_COMMITTER_NAME", "moon tests")
+                    .env("GIT_COMMITTER_EMAIL", "fakeemail@moonrepo.dev");
+            });
+        }
+
+        #[tokio::test]
+        async fn recurses_into_tree_hashes() {
+            let fixture = create_sandbox_with_git_submodule("ignore", "ignore", "sub");
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            let hashes = git.get_file_tree_hashes(".").await.unwrap();
+
+            assert_eq!(
+                hashes.get("sub/dir/qux").unwrap(),
+                "100b0dec8c53a40e4de7714b2c612dad5fad9985"
+            );
+            assert!(hashes.contains_key("sub/foo"));
+            assert!(!hashes.contains_key("sub"));
+            assert!(!hashes.contains_key("sub/bar"));
+
+            assert_eq!(
+                git.get_file_tree_hashes("sub/dir").await.unwrap(),
+                BTreeMap::from([(
+                    "sub/dir/qux".to_owned(),
+                    "100b0dec8c53a40e4de7714b2c612dad5fad9985".to_owned()
+                )])
+            );
+        }
+
+        #[tokio::test]
+        async fn hashes_files_within_submodule() {
+            let fixture = create_sandbox_with_git_submodule("ignore", "ignore", "sub");
+
+            std::fs::write(fixture.path().join("sub/foo"), "modified").unwrap();
+
+            let git = Git::new("master", fixture.path()).unwrap();
+
+            assert_eq!(
+                git.get_file_hashes(&string_vec!["foo", "sub/foo", "sub/bar"])
+                    .await
+                    .unwrap(),
+                BTreeMap::from([
+                    (
+                        "foo".to_owned(),
+                        "257cc5642cb1a054f08cc83f2d943e56fd3ebe99".to_owned()
+                    ),
+                    (
+                        "sub/foo".to_owned(),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    )
+                ])
+            );
+        }
+
+        #[tokio::test]
+        async fn includes_touched_files_within_submodule() {
+            let fixture = create_sandbox_with_git_submodule("ignore", "ignore", "sub");
+
+            std::fs::write(fixture.path().join("sub/foo"), "modified").unwrap();
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let touched_files = git.get_touched_files().await.unwrap();
+
+            assert_eq!(
+                touched_files.modified,
+                HashSet::from(["sub/foo".to_owned()])
+            );
+            assert!(!touched_files.all.contains("sub/dir/qux"));
+        }
+
+        #[tokio::test]
+        async fn expands_commit_bumps_to_all_files() {
+            let fixture = create_sandbox_with_git_submodule("ignore", "ignore", "sub");
+
+            std::fs::write(fixture.path().join("sub/foo"), "modified").unwrap();
+            commit(&fixture.path().join("sub"), "Bump");
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let touched_files = git.get_touched_files().await.unwrap();
+
+            assert!(touched_files.all.contains("sub"));
+            assert!(touched_files.all.contains("sub/foo"));
+            assert!(touched_files.all.contains("sub/dir/qux"));
+            assert!(touched_files.unstaged.contains("sub/dir/qux"));
+
+            // And between revisions
+            commit(fixture.path(), "Bump submodule");
+
+            let git = Git::new("master", fixture.path()).unwrap();
+            let touched_files = git
+                .get_touched_files_between_revisions("HEAD~1", "HEAD")
+                .await
+                .unwrap();
+
+            assert!(touched_files.modified.contains("sub/foo"));
+            assert!(touched_files.modified.contains("sub/dir/qux"));
+        }
+    }
 }
@@ -0,0 +1,577 @@
+use crate::vcs::{TouchedFiles, Vcs, VcsResult};
+use async_trait::async_trait;
+use moon_utils::fs;
+use moon_utils::process::{output_to_string, output_to_trimmed_string, Command};
+use sha1::{Digest, Sha1};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+// Avoid exceeding the max command line length when passing files as arguments
+const FILES_PER_COMMAND: usize = 500;
+
+// The parent of a file that has no history
+const NULL_NODE: [u8; 20] = [0; 20];
+
+fn decode_node(hash: &str) -> Option<[u8; 20]> {
+    let mut node = NULL_NODE;
+
+    if hash.len() != 40 {
+        return None;
+    }
+
+    for (index, byte) in node.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hash.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+
+    Some(node)
+}
+
+pub struct Hg {
+    cache: Arc<RwLock<HashMap<String, String>>>,
+    default_branch: String,
+    root: PathBuf,
+}
+
+impl Hg {
+    pub fn new(default_branch: &str, working_dir: &Path) -> Self {
+        let root = match fs::find_upwards(".hg", working_dir) {
+            Some(dir) => dir.parent().unwrap().to_path_buf(),
+            None => working_dir.to_path_buf(),
+        };
+
+        Hg {
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            default_branch: String::from(default_branch),
+            root,
+        }
+    }
+
+    async fn get_ancestor(&self, base: &str, head: &str) -> VcsResult<String> {
+        // This is necessary to support comparisons between diverged heads!
+        if let Ok(node) = self
+            .get_revision_node(&format!("ancestor({}, {})", base, head))
+            .await
+        {
+            if !node.is_empty() {
+                return Ok(node);
+            }
+        }
+
+        Ok(base.to_owned())
+    }
+
+    // Map of tracked files to their filenode in the working directory's parent
+    async fn load_manifest(&self) -> VcsResult<BTreeMap<String, String>> {
+        let output = self
+            .run_command(
+                &mut self.create_command(vec!["manifest", "--debug", "--rev", "."]),
+                true,
+            )
+            .await?;
+
+        let mut map = BTreeMap::new();
+
+        for line in output.split('\n') {
+            // <hash> <mode> <flag> <file>
+            // The flag is empty for regular files, and `*` or `@` otherwise
+            let mut parts = line.splitn(3, ' ');
+
+            if let (Some(hash), Some(_mode), Some(rest)) =
+                (parts.next(), parts.next(), parts.next())
+            {
+                let file = rest
+                    .strip_prefix("* ")
+                    .or_else(|| rest.strip_prefix("@ "))
+                    .unwrap_or_else(|| rest.trim_start());
+
+                if !hash.is_empty() && !file.is_empty() {
+                    map.insert(file.to_owned(), hash.to_owned());
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    async fn get_ignored_files(&self, files: &[String]) -> VcsResult<HashSet<String>> {
+        let mut ignored = HashSet::new();
+
+        for chunk in files.chunks(FILES_PER_COMMAND) {
+            let mut args = vec!["status", "--ignored", "--no-status", "--print0"];
+            let patterns = chunk
+                .iter()
+                .map(|file| format!("path:{}", file))
+                .collect::<Vec<_>>();
+
+            args.extend(patterns.iter().map(|p| p.as_str()));
+
+            let output = self
+                .run_command(&mut self.create_command(args), false)
+                .await?;
+
+            for file in output.split('\0') {
+                if !file.is_empty() {
+                    ignored.insert(file.to_owned());
+                }
+            }
+        }
+
+        Ok(ignored)
+    }
+
+    async fn get_revision_node(&self, revision: &str) -> VcsResult<String> {
+        self.run_command(
+            &mut self.create_command(vec!["log", "--rev", revision, "--template", "{node}"]),
+            true,
+        )
+        .await
+    }
+
+    // https://www.mercurial-scm.org/doc/hg.1.html#status
+    fn process_touched_files(output: String) -> TouchedFiles {
+        if output.is_empty() {
+            return TouchedFiles::default();
+        }
+
+        let mut added = HashSet::new();
+        let mut deleted = HashSet::new();
+        let mut modified = HashSet::new();
+        let mut untracked = HashSet::new();
+        let mut staged = HashSet::new();
+        let mut unstaged = HashSet::new();
+        let mut all = HashSet::new();
+        let mut copies = vec![];
+        let mut last_added: Option<String> = None;
+
+        // Lines are terminated by a NUL byte:
+        //  X file\0
+        //  A file\0  orig_file\0
+        for line in output.split('\0') {
+            // Sources of copies and renames follow the added file
+            if let Some(orig_file) = line.strip_prefix("  ") {
+                if let Some(file) = &last_added {
+                    copies.push((file.clone(), orig_file.to_owned()));
+                }
+
+                continue;
+            }
+
+            if line.len() < 3 {
+                continue;
+            }
+
+            let x = line.chars().next().unwrap_or_default();
+            let file = String::from(&line[2..]);
+
+            last_added = if x == 'A' { Some(file.clone()) } else { None };
+
+            // hg has no index, so changes known to hg are considered staged,
+            // while missing files (deleted without `hg remove`) are not
+            match x {
+                'A' => {
+                    added.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                'R' => {
+                    deleted.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                '!' => {
+                    deleted.insert(file.clone());
+                    unstaged.insert(file.clone());
+                }
+                'M' => {
+                    modified.insert(file.clone());
+                    staged.insert(file.clone());
+                }
+                '?' => {
+                    untracked.insert(file.clone());
+                }
+                _ => {
+                    continue;
+                }
+            }
+
+            all.insert(file.clone());
+        }
+
+        let mut copied = HashMap::new();
+        let mut renamed = HashMap::new();
+
+        // hg records renames as a copy, and a removal of the original file
+        for (file, orig_file) in copies {
+            if deleted.contains(&orig_file) {
+                renamed.insert(file, orig_file);
+            } else {
+                copied.insert(file, orig_file);
+            }
+        }
+
+        TouchedFiles {
+            added,
+            all,
+            copied,
+            deleted,
+            modified,
+            renamed,
+            staged,
+            unstaged,
+            untracked,
+        }
+    }
+
+    async fn run_command(&self, command: &mut Command, trim: bool) -> VcsResult<String> {
+        let (cache_key, _) = command.get_command_line();
+
+        // Read first before locking with a write
+        {
+            let cache = self.cache.read().await;
+
+            if cache.contains_key(&cache_key) {
+                return Ok(cache.get(&cache_key).unwrap().clone());
+            }
+        }
+
+        // Otherwise lock and calculate a new value to write
+        let mut cache = self.cache.write().await;
+        let output = command.exec_capture_output().await?;
+
+        let value = if trim {
+            output_to_trimmed_string(&output.stdout)
+        } else {
+            output_to_string(&output.stdout)
+        };
+
+        cache.insert(cache_key.to_owned(), value.clone());
+
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl Vcs for Hg {
+    fn create_command(&self, args: Vec<&str>) -> Command {
+        let mut cmd = Command::new("hg");
+        cmd.args(args)
+            .cwd(&self.root)
+            // Ignore user configuration that may alter the output
+            .env("HGPLAIN", "1");
+        cmd
+    }
+
+    // Bookmarks are the closest equivalent to git branches,
+    // so prefer the active bookmark over the named branch
+    async fn get_local_branch(&self) -> VcsResult<String> {
+        let bookmark = self
+            .run_command(
+                &mut self.create_command(vec![
+                    "log",
+                    "--rev",
+                    ".",
+                    "--template",
+                    "{activebookmark}",
+                ]),
+                true,
+            )
+            .await?;
+
+        if !bookmark.is_empty() {
+            return Ok(bookmark);
+        }
+
+        self.run_command(&mut self.create_command(vec!["branch"]), true)
+            .await
+    }
+
+    async fn get_local_branch_revision(&self) -> VcsResult<String> {
+        self.get_revision_node(".").await
+    }
+
+    fn get_default_branch(&self) -> &str {
+        &self.default_branch
+    }
+
+    async fn get_default_branch_revision(&self) -> VcsResult<String> {
+        self.get_revision_node(&self.default_branch).await
+    }
+
+    async fn get_hooks_dir(&self) -> VcsResult<Option<PathBuf>> {
+        Ok(None)
+    }
+
+    async fn get_file_hashes(&self, files: &[String]) -> VcsResult<BTreeMap<String, String>> {
+        let ignored = self.get_ignored_files(files).await?;
+        let manifest = self.load_manifest().await?;
+        let modified = self.get_touched_files().await?.modified;
+        let mut map = BTreeMap::new();
+
+        // hg doesn't support hashing arbitrary files (like `git hash-object`),
+        // so unmodified files use their node in the manifest, while for other
+        // files we compute the filenode they would have once committed. This is
+        // the SHA1 of its sorted parent nodes followed by its contents, where the
+        // only parent is the current filenode (if tracked).
+        for file in files {
+            if ignored.contains(file) {
+                continue;
+            }
+
+            if let Some(hash) = manifest.get(file) {
+                if !modified.contains(file) && self.root.join(file).exists() {
+                    map.insert(file.to_owned(), hash.to_owned());
+                    continue;
+                }
+            }
+
+            if let Ok(contents) = std::fs::read(self.root.join(file)) {
+                let parent = manifest
+                    .get(file)
+                    .and_then(|hash| decode_node(hash))
+                    .unwrap_or(NULL_NODE);
+
+                let mut sha = Sha1::new();
+                sha.update(NULL_NODE);
+                sha.update(parent);
+                sha.update(&contents);
+
+                map.insert(file.to_owned(), format!("{:x}", sha.finalize()));
+            }
+        }
+
+        Ok(map)
+    }
+
+    async fn get_file_tree_hashes(&self, dir: &str) -> VcsResult<BTreeMap<String, String>> {
+        let prefix = if dir.is_empty() || dir == "." {
+            String::new()
+        } else {
+            format!("{}/", dir.trim_end_matches('/'))
+        };
+
+        Ok(self
+            .load_manifest()
+            .await?
+            .into_iter()
+            .filter(|(file, _)| file.starts_with(&prefix))
+            .collect())
+    }
+
+    // https://www.mercurial-scm.org/doc/hg.1.html#status
+    async fn get_touched_files(&self) -> VcsResult<TouchedFiles> {
+        let output = self
+            .run_command(
+                &mut self.create_command(vec![
+                    "status", "--copies",
+                    // We use this option so that file names with special characters
+                    // are displayed as-is and are not quoted/escaped
+                    "--print0",
+                ]),
+                false,
+            )
+            .await?;
+
+        Ok(Hg::process_touched_files(output))
+    }
+
+    async fn get_touched_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> VcsResult<TouchedFiles> {
+        let rev = if self.is_default_branch(revision) {
+            "."
+        } else {
+            revision
+        };
+
+        // Compares the revision against its first parent
+        let output = self
+            .run_command(
+                &mut self.create_command(vec!["status", "--copies", "--change", rev, "--print0"]),
+                false,
+            )
+            .await?;
+
+        Ok(Hg::process_touched_files(output))
+    }
+
+    async fn get_touched_files_between_revisions(
+        &self,
+        base_revision: &str,
+        revision: &str,
+    ) -> VcsResult<TouchedFiles> {
+        let base = self.get_ancestor(base_revision, revision).await?;
+
+        let output = self
+            .run_command(
+                &mut self.create_command(vec![
+                    "status", "--copies", "--rev", &base, "--rev", revision, "--print0",
+                ]),
+                false,
+            )
+            .await?;
+
+        Ok(Hg::process_touched_files(output))
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        self.default_branch == branch
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.root.join(".hg").exists()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use moon_utils::string_vec;
+    use moon_utils::test::{create_sandbox_with_hg, run_hg_command};
+
+    mod get_local_branch {
+        use super::*;
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn returns_named_branch() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let hg = Hg::new("default", fixture.path());
+
+            assert!(hg.is_enabled());
+            assert_eq!(hg.get_local_branch().await.unwrap(), "default");
+        }
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn returns_active_bookmark() {
+            let fixture = create_sandbox_with_hg("ignore");
+
+            run_hg_command(fixture.path(), |cmd| {
+                cmd.args(["bookmark", "feature"]);
+            });
+
+            let hg = Hg::new("default", fixture.path());
+
+            assert_eq!(hg.get_local_branch().await.unwrap(), "feature");
+        }
+    }
+
+    mod get_file_hashes {
+        use super::*;
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn filters_ignored_files() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let hg = Hg::new("default", fixture.path());
+
+            let hashes = hg
+                .get_file_hashes(&string_vec!["foo", "bar", "dir/baz", "dir/qux"])
+                .await
+                .unwrap();
+
+            assert_eq!(hashes.keys().collect::<Vec<_>>(), vec!["dir/qux", "foo"]);
+        }
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn matches_manifest_for_unmodified_files() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let hg = Hg::new("default", fixture.path());
+
+            std::fs::write(fixture.path().join("foo"), "changed").unwrap();
+
+            let hashes = hg
+                .get_file_hashes(&string_vec!["foo", "dir/qux"])
+                .await
+                .unwrap();
+            let tree_hashes = hg.get_file_tree_hashes(".").await.unwrap();
+
+            assert_eq!(hashes.get("dir/qux"), tree_hashes.get("dir/qux"));
+            assert_ne!(hashes.get("foo"), tree_hashes.get("foo"));
+        }
+    }
+
+    mod get_file_tree_hashes {
+        use super::*;
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn returns_tracked_files() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let hg = Hg::new("default", fixture.path());
+
+            let hashes = hg.get_file_tree_hashes(".").await.unwrap();
+
+            assert!(hashes.contains_key("dir/qux"));
+            assert!(hashes.contains_key("foo"));
+            assert!(!hashes.contains_key("bar"));
+
+            let hashes = hg.get_file_tree_hashes("dir").await.unwrap();
+
+            assert_eq!(hashes.keys().collect::<Vec<_>>(), vec!["dir/qux"]);
+        }
+    }
+
+    mod get_touched_files {
+        use super::*;
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn maps_statuses() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let dir = fixture.path();
+
+            std::fs::write(dir.join("foo"), "changed").unwrap();
+            std::fs::write(dir.join("added"), "added").unwrap();
+            std::fs::write(dir.join("untracked"), "untracked").unwrap();
+            std::fs::remove_file(dir.join("dir/qux")).unwrap();
+
+            run_hg_command(dir, |cmd| {
+                cmd.args(["add", "added"]);
+            });
+
+            let hg = Hg::new("default", dir);
+            let touched = hg.get_touched_files().await.unwrap();
+
+            assert_eq!(touched.added, HashSet::from(["added".to_owned()]));
+            assert_eq!(touched.modified, HashSet::from(["foo".to_owned()]));
+            assert_eq!(touched.deleted, HashSet::from(["dir/qux".to_owned()]));
+            assert_eq!(touched.untracked, HashSet::from(["untracked".to_owned()]));
+            assert_eq!(
+                touched.staged,
+                HashSet::from(["added".to_owned(), "foo".to_owned()])
+            );
+            assert_eq!(touched.unstaged, HashSet::from(["dir/qux".to_owned()]));
+        }
+
+        #[tokio::test]
+        #[ignore = "requires hg to be installed"]
+        async fn diffs_between_revisions() {
+            let fixture = create_sandbox_with_hg("ignore");
+            let dir = fixture.path();
+
+            std::fs::write(dir.join("foo"), "changed").unwrap();
+            run_hg_command(dir, |cmd| {
+                cmd.args(["commit", "--message", "Change"]);
+            });
+
+            let hg = Hg::new("default", dir);
+
+            let touched = hg
+                .get_touched_files_between_revisions("0", "1")
+                .await
+                .unwrap();
+
+            assert_eq!(touched.all, HashSet::from(["foo".to_owned()]));
+
+            let touched = hg
+                .get_touched_files_against_previous_revision("default")
+                .await
+                .unwrap();
+
+            assert_eq!(touched.modified, HashSet::from(["foo".to_owned()]));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::vcs::VcsResult;
+use moon_error::map_io_to_fs_error;
+use moon_logger::{color, debug, warn};
+use moon_utils::fs;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const LOG_TARGET: &str = "moon:vcs:hooks";
+
+pub const HOOK_HEADER: &str = "# Automatically generated by moon. DO NOT MODIFY!";
+
+/// Return true if the command is a target, like `project:task`,
+/// instead of an arbitrary shell command.
+fn is_target(command: &str) -> bool {
+    command.contains(':') && !command.contains(char::is_whitespace)
+}
+
+/// Create the contents of a hook script. Targets are ran through `moon run`,
+/// using the provided moon command (a binary path or package runner),
+/// while all other commands are ran as-is.
+pub fn create_hook_script(name: &str, commands: &[String], moon_command: &str) -> String {
+    let mut lines = vec![
+        "#!/bin/sh".to_owned(),
+        HOOK_HEADER.to_owned(),
+        "".to_owned(),
+        "set -e".to_owned(),
+        "".to_owned(),
+    ];
+
+    for command in commands {
+        let command = command.trim();
+
+        if is_target(command) {
+            match name {
+                // Only run affected targets based on the files being committed
+                "pre-commit" => lines.push(format!(
+                    "{} run {} --affected --status staged",
+                    moon_command, command
+                )),
+                // Only run affected targets based on the files changed
+                // between the default branch and the commits being pushed
+                "pre-push" => lines.push(format!(
+                    "{} run {} --affected --upstream",
+                    moon_command, command
+                )),
+                _ => lines.push(format!("{} run {}", moon_command, command)),
+            };
+        } else {
+            lines.push(command.to_owned());
+        }
+    }
+
+    lines.push("".to_owned());
+    lines.join("\n")
+}
+
+async fn is_generated_hook(path: &Path) -> VcsResult<bool> {
+    // Hooks directories may contain binaries, so avoid requiring UTF-8
+    let contents = tokio::fs::read(path)
+        .await
+        .map_err(|e| map_io_to_fs_error(e, path.to_path_buf()))?;
+
+    Ok(String::from_utf8_lossy(&contents)
+        .lines()
+        .any(|line| line == HOOK_HEADER))
+}
+
+#[cfg(unix)]
+async fn make_executable(path: &Path) -> VcsResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .await
+        .map_err(|e| map_io_to_fs_error(e, path.to_path_buf()))?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn make_executable(_path: &Path) -> VcsResult<()> {
+    Ok(())
+}
+
+/// Write hooks to the provided directory, and remove previously generated
+/// hooks that are no longer configured. Hooks that were not generated by moon
+/// will never be overwritten or removed.
+pub async fn sync_hooks(
+    hooks_dir: &Path,
+    hooks: &BTreeMap<String, Vec<String>>,
+    moon_command: &str,
+) -> VcsResult<Vec<String>> {
+    let mut synced = vec![];
+
+    // Remove stale hooks first
+    if hooks_dir.exists() {
+        for entry in fs::read_dir(hooks_dir).await? {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if path.is_file() && !hooks.contains_key(&name) && is_generated_hook(&path).await? {
+                debug!(
+                    target: LOG_TARGET,
+                    "Removing stale hook {}",
+                    color::path(&path)
+                );
+
+                fs::remove_file(&path).await?;
+            }
+        }
+    }
+
+    if hooks.is_empty() {
+        return Ok(synced);
+    }
+
+    fs::create_dir_all(hooks_dir).await?;
+
+    for (name, commands) in hooks {
+        let path = hooks_dir.join(name);
+
+        if path.exists() && !is_generated_hook(&path).await? {
+            warn!(
+                target: LOG_TARGET,
+                "Hook {} already exists and was not created by moon, skipping",
+                color::path(&path)
+            );
+
+            continue;
+        }
+
+        debug!(target: LOG_TARGET, "Writing hook {}", color::path(&path));
+
+        fs::write(&path, create_hook_script(name, commands, moon_command)).await?;
+        make_executable(&path).await?;
+
+        synced.push(name.to_owned());
+    }
+
+    Ok(synced)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use moon_utils::string_vec;
+    use moon_utils::test::create_sandbox;
+
+    #[test]
+    fn runs_targets_through_moon() {
+        assert_eq!(
+            create_hook_script(
+                "post-merge",
+                &string_vec!["app:lint", "echo 'done'"],
+                "moon"
+            ),
+            format!(
+                "#!/bin/sh\n{}\n\nset -e\n\nmoon run app:lint\necho 'done'\n",
+                HOOK_HEADER
+            )
+        );
+    }
+
+    #[test]
+    fn runs_affected_targets_on_pre_commit() {
+        assert_eq!(
+            create_hook_script(
+                "pre-commit",
+                &string_vec!["app:lint", "moon check app"],
+                "moon"
+            ),
+            format!(
+                "#!/bin/sh\n{}\n\nset -e\n\nmoon run app:lint --affected --status staged\nmoon check app\n",
+                HOOK_HEADER
+            )
+        );
+    }
+
+    #[test]
+    fn runs_affected_targets_against_upstream_on_pre_push() {
+        assert_eq!(
+            create_hook_script("pre-push", &string_vec!["app:test", "echo 'done'"], "moon"),
+            format!(
+                "#!/bin/sh\n{}\n\nset -e\n\nmoon run app:test --affected --upstream\necho 'done'\n",
+                HOOK_HEADER
+            )
+        );
+    }
+
+    #[test]
+    fn runs_targets_through_custom_moon_command() {
+        assert_eq!(
+            create_hook_script("post-merge", &string_vec!["app:lint"], "npx moon"),
+            format!(
+                "#!/bin/sh\n{}\n\nset -e\n\nnpx moon run app:lint\n",
+                HOOK_HEADER
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_and_removes_hooks() {
+        let fixture = create_sandbox("ignore");
+        let hooks_dir = fixture.path().join("hooks");
+
+        sync_hooks(
+            &hooks_dir,
+            &BTreeMap::from([
+                ("pre-commit".to_owned(), string_vec!["app:lint"]),
+                ("pre-push".to_owned(), string_vec!["app:test"]),
+            ]),
+            "moon",
+        )
+        .await
+        .unwrap();
+
+        assert!(hooks_dir.join("pre-commit").exists());
+        assert!(hooks_dir.join("pre-push").exists());
+
+        sync_hooks(
+            &hooks_dir,
+            &BTreeMap::from([("pre-commit".to_owned(), string_vec!["app:lint"])]),
+            "moon",
+        )
+        .await
+        .unwrap();
+
+        assert!(hooks_dir.join("pre-commit").exists());
+        assert!(!hooks_dir.join("pre-push").exists());
+    }
+
+    #[tokio::test]
+    async fn skips_hooks_not_created_by_moon() {
+        let fixture = create_sandbox("ignore");
+        let hooks_dir = fixture.path();
+
+        std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\nlint-staged\n").unwrap();
+        std::fs::write(hooks_dir.join("pre-push"), "#!/bin/sh\nnpm test\n").unwrap();
+
+        let synced = sync_hooks(
+            hooks_dir,
+            &BTreeMap::from([("pre-commit".to_owned(), string_vec!["app:lint"])]),
+            "moon",
+        )
+        .await
+        .unwrap();
+
+        assert!(synced.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap(),
+            "#!/bin/sh\nlint-staged\n"
+        );
+        assert!(hooks_dir.join("pre-push").exists());
+    }
+}
@@ -1,11 +1,16 @@
+mod batch;
 mod errors;
 mod git;
+mod hg;
+mod hooks;
 mod loader;
 mod svn;
 mod vcs;
 
 pub use errors::VcsError;
 pub use git::Git;
+pub use hg::Hg;
+pub use hooks::*;
 pub use loader::*;
 pub use svn::Svn;
 pub use vcs::*;
@@ -1,5 +1,6 @@
 use crate::errors::VcsError;
 use crate::git::Git;
+use crate::hg::Hg;
 use crate::svn::Svn;
 use crate::vcs::Vcs;
 use moon_config::{VcsManager, WorkspaceConfig};
@@ -17,6 +18,7 @@ impl VcsLoader {
         let default_branch = &vcs_config.default_branch;
 
         Ok(match manager {
+            VcsManager::Hg => Box::new(Hg::new(default_branch, working_dir)),
             VcsManager::Svn => Box::new(Svn::new(default_branch, working_dir)),
             _ => Box::new(Git::new(default_branch, working_dir)?),
         })
@@ -103,8 +103,10 @@ impl Svn {
         TouchedFiles {
             added,
             all,
+            copied: HashMap::new(),
             deleted,
             modified,
+            renamed: HashMap::new(),
             staged,
             unstaged, // svn has no concept for this
             untracked,
@@ -179,6 +181,10 @@ impl Vcs for Svn {
         Ok(self.get_revision_number("HEAD").await?)
     }
 
+    async fn get_hooks_dir(&self) -> VcsResult<Option<PathBuf>> {
+        Ok(None)
+    }
+
     async fn get_file_hashes(&self, files: &[String]) -> VcsResult<BTreeMap<String, String>> {
         let mut map = BTreeMap::new();
 
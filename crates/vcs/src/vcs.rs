@@ -1,24 +1,64 @@
 use crate::errors::VcsError;
 use async_trait::async_trait;
 use moon_utils::process::Command;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 
 pub type VcsResult<T> = Result<T, VcsError>;
 
 #[allow(dead_code)]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct TouchedFiles {
     pub added: HashSet<String>,
     pub deleted: HashSet<String>,
     pub modified: HashSet<String>,
     pub untracked: HashSet<String>,
 
-    // Will contain files from the previous fields
+    // Maps of destination files to their source files
+    pub copied: HashMap<String, String>,
+    pub renamed: HashMap<String, String>,
+
+    // Will contain files from the previous fields.
+    // For renames, both the source and destination files are included.
     pub staged: HashSet<String>,
     pub unstaged: HashSet<String>,
     pub all: HashSet<String>,
 }
 
+impl TouchedFiles {
+    /// Merge touched files from a nested repository (like a submodule)
+    /// that's located at the provided path, relative from the root.
+    pub fn merge_with_prefix(&mut self, other: TouchedFiles, prefix: &str) {
+        let with_prefix = |file: String| format!("{}/{}", prefix, file);
+
+        self.added.extend(other.added.into_iter().map(with_prefix));
+        self.deleted
+            .extend(other.deleted.into_iter().map(with_prefix));
+        self.modified
+            .extend(other.modified.into_iter().map(with_prefix));
+        self.untracked
+            .extend(other.untracked.into_iter().map(with_prefix));
+        self.staged
+            .extend(other.staged.into_iter().map(with_prefix));
+        self.unstaged
+            .extend(other.unstaged.into_iter().map(with_prefix));
+        self.all.extend(other.all.into_iter().map(with_prefix));
+
+        self.copied.extend(
+            other
+                .copied
+                .into_iter()
+                .map(|(to, from)| (with_prefix(to), with_prefix(from))),
+        );
+        self.renamed.extend(
+            other
+                .renamed
+                .into_iter()
+                .map(|(to, from)| (with_prefix(to), with_prefix(from))),
+        );
+    }
+}
+
 #[async_trait]
 pub trait Vcs {
     /// Create a process command for the underlying vcs binary.
@@ -30,12 +70,17 @@ pub trait Vcs {
     /// Get the revision hash/number of the local branch's HEAD.
     async fn get_local_branch_revision(&self) -> VcsResult<String>;
 
-    /// Get the upstream checkout default name. Typically master/main on git, and trunk on svn.
+    /// Get the upstream checkout default name. Typically master/main on git,
+    /// default on hg, and trunk on svn.
     fn get_default_branch(&self) -> &str;
 
     /// Get the revision hash/number of the default branch's HEAD.
     async fn get_default_branch_revision(&self) -> VcsResult<String>;
 
+    /// Get the absolute path to the directory that hooks are written to,
+    /// or `None` if the vcs does not support hooks.
+    async fn get_hooks_dir(&self) -> VcsResult<Option<PathBuf>>;
+
     /// Get a map of hashes for the provided files.
     /// Files are relative from the repository root.
     async fn get_file_hashes(&self, files: &[String]) -> VcsResult<BTreeMap<String, String>>;
@@ -0,0 +1,329 @@
+use crate::errors::WorkspaceError;
+use crate::workspace::{
+    find_workspace_root, load_global_project_config_file, load_scoped_config_files,
+};
+use moon_cache::CacheEngine;
+use moon_config::{
+    find_config_file, flatten_figment_errors, FigmentError, GlobalProjectConfig, ProjectConfig,
+    ProjectDependsOn, WorkspaceConfig,
+};
+use moon_constants as constants;
+use moon_error::MoonError;
+use moon_logger::{color, debug};
+use moon_project_graph::{enforce_constraints, ProjectGraph};
+use moon_task::Target;
+use moon_utils::path;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::Path;
+
+const LOG_TARGET: &str = "moon:workspace:check";
+
+#[derive(Debug, Serialize)]
+pub struct ConfigIssue {
+    /// Config file path, relative from the workspace root.
+    pub file: String,
+
+    /// Dot delimited path to the offending setting, if known.
+    pub key: Option<String>,
+
+    pub message: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ConfigCheckReport {
+    /// Config files that were loaded, relative from the workspace root.
+    pub files: Vec<String>,
+
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigCheckReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push_issue(&mut self, file: &str, key: Option<String>, message: String) {
+        self.issues.push(ConfigIssue {
+            file: file.to_owned(),
+            key,
+            message,
+        });
+    }
+
+    fn push_figment_errors(&mut self, file: &str, errors: Vec<FigmentError>) {
+        for (key, message) in flatten_figment_errors(errors) {
+            self.push_issue(file, if key.is_empty() { None } else { Some(key) }, message);
+        }
+    }
+}
+
+fn get_config_file(dir: &str, file: &str) -> String {
+    if dir.is_empty() || dir == "." {
+        file.to_owned()
+    } else {
+        format!("{}/{}", path::standardize_separators(dir), file)
+    }
+}
+
+// Find a config file in any supported format, relative from the workspace root.
+fn find_config(
+    workspace_root: &Path,
+    dir: &str,
+    file_name: &str,
+) -> Result<Option<String>, MoonError> {
+    Ok(find_config_file(
+        &workspace_root.join(path::normalize_separators(dir)),
+        file_name,
+    )?
+    .map(|config_path| get_config_file(dir, &config_path.file_name().unwrap().to_string_lossy())))
+}
+
+// .moon/project.yml, .moon/tasks/*.yml
+fn check_global_project_configs(
+    workspace_root: &Path,
+    extends_cache_ttl: u64,
+    report: &mut ConfigCheckReport,
+) -> Result<GlobalProjectConfig, WorkspaceError> {
+    let mut global_config = GlobalProjectConfig::default();
+
+    match find_config(
+        workspace_root,
+        constants::CONFIG_DIRNAME,
+        constants::CONFIG_GLOBAL_PROJECT_FILENAME,
+    ) {
+        Ok(Some(global_file)) => {
+            report.files.push(global_file.clone());
+
+            match load_global_project_config_file(
+                workspace_root.join(path::normalize_separators(&global_file)),
+                extends_cache_ttl,
+            ) {
+                Ok(config) => global_config = config,
+                Err(errors) => report.push_figment_errors(&global_file, errors),
+            };
+        }
+        Ok(None) => {}
+        Err(error) => {
+            report.push_issue(
+                &get_config_file(
+                    constants::CONFIG_DIRNAME,
+                    constants::CONFIG_GLOBAL_PROJECT_FILENAME,
+                ),
+                None,
+                error.to_string(),
+            );
+        }
+    };
+
+    let tasks_dir = workspace_root
+        .join(constants::CONFIG_DIRNAME)
+        .join(constants::CONFIG_GLOBAL_TASKS_DIRNAME);
+
+    let scoped_configs = match load_scoped_config_files(&tasks_dir, extends_cache_ttl) {
+        Ok(configs) => configs,
+        Err(WorkspaceError::Moon(error @ MoonError::MultipleConfigFormats(_, _))) => {
+            report.push_issue(
+                &format!(
+                    "{}/{}",
+                    constants::CONFIG_DIRNAME,
+                    constants::CONFIG_GLOBAL_TASKS_DIRNAME
+                ),
+                None,
+                error.to_string(),
+            );
+
+            return Ok(global_config);
+        }
+        Err(error) => return Err(error),
+    };
+
+    for scoped in scoped_configs {
+        let scoped_file = format!(
+            "{}/{}/{}",
+            constants::CONFIG_DIRNAME,
+            constants::CONFIG_GLOBAL_TASKS_DIRNAME,
+            scoped.file_name
+        );
+
+        report.files.push(scoped_file.clone());
+
+        match scoped.config {
+            Ok(config) => {
+                global_config.scoped_configs.insert(scoped.scope, config);
+            }
+            Err(errors) => report.push_figment_errors(&scoped_file, errors),
+        };
+    }
+
+    Ok(global_config)
+}
+
+/// Load and validate every configuration file in the workspace, starting from
+/// the current working directory. Unlike `Workspace::load`, this will not fail
+/// on the first invalid file, and will instead aggregate all issues. Once all
+/// files are loaded, will also verify that `dependsOn` and task `deps`
+/// reference projects and tasks that exist, and that `dependsOn` edges
+/// satisfy the workspace `constraints`.
+pub async fn check_workspace_configs() -> Result<ConfigCheckReport, WorkspaceError> {
+    let working_dir = env::current_dir().unwrap();
+    let workspace_root = match find_workspace_root(working_dir) {
+        Some(dir) => dir,
+        None => return Err(WorkspaceError::MissingConfigDir),
+    };
+    let mut report = ConfigCheckReport::default();
+
+    debug!(
+        target: LOG_TARGET,
+        "Checking configs in workspace {}",
+        color::path(&workspace_root)
+    );
+
+    // .moon/workspace.yml
+    let workspace_file = match find_config(
+        &workspace_root,
+        constants::CONFIG_DIRNAME,
+        constants::CONFIG_WORKSPACE_FILENAME,
+    ) {
+        Ok(Some(file)) => file,
+        Ok(None) => return Err(WorkspaceError::MissingWorkspaceConfigFile),
+        Err(error) => {
+            report.push_issue(
+                &get_config_file(
+                    constants::CONFIG_DIRNAME,
+                    constants::CONFIG_WORKSPACE_FILENAME,
+                ),
+                None,
+                error.to_string(),
+            );
+
+            return Ok(report);
+        }
+    };
+
+    report.files.push(workspace_file.clone());
+
+    let workspace_config = match WorkspaceConfig::load(workspace_root.join(&workspace_file)) {
+        Ok(config) => config,
+        Err(errors) => {
+            // Projects can't be located without a valid workspace config
+            report.push_figment_errors(&workspace_file, errors);
+
+            return Ok(report);
+        }
+    };
+
+    let global_config = check_global_project_configs(
+        &workspace_root,
+        workspace_config.get_extends_cache_ttl(),
+        &mut report,
+    )?;
+
+    // moon.yml
+    let cache = CacheEngine::create(&workspace_root).await?;
+    let projects =
+        ProjectGraph::create(&workspace_root, &workspace_config, global_config, &cache).await?;
+    let project_ids = projects.ids();
+    let mut loaded_projects = BTreeMap::new();
+
+    for id in &project_ids {
+        let source = projects.get_source(id).unwrap();
+        let default_file = get_config_file(source, constants::CONFIG_PROJECT_FILENAME);
+        let config_file =
+            match find_config(&workspace_root, source, constants::CONFIG_PROJECT_FILENAME) {
+                Ok(Some(config_file)) => {
+                    report.files.push(config_file.clone());
+
+                    if let Err(errors) = ProjectConfig::load(
+                        workspace_root.join(path::normalize_separators(&config_file)),
+                        workspace_config.get_extends_cache_ttl(),
+                    ) {
+                        report.push_figment_errors(&config_file, errors);
+
+                        continue;
+                    }
+
+                    config_file
+                }
+                Ok(None) => default_file,
+                Err(error) => {
+                    report.push_issue(&default_file, None, error.to_string());
+
+                    continue;
+                }
+            };
+
+        match projects.create_project(id, source) {
+            Ok(project) => {
+                loaded_projects.insert(id.to_owned(), (config_file, project));
+            }
+            Err(error) => {
+                report.push_issue(&config_file, None, error.to_string());
+            }
+        };
+    }
+
+    // Verify references between projects and tasks
+    for (config_file, project) in loaded_projects.values() {
+        for (index, dep) in project.config.depends_on.iter().enumerate() {
+            let dep_id = match dep {
+                ProjectDependsOn::String(id) => id,
+                ProjectDependsOn::Object(cfg) => &cfg.id,
+            };
+
+            let key = Some(format!("dependsOn[{}]", index));
+            let resolved_dep_id = projects.resolve_id(dep_id);
+
+            if !project_ids.contains(&resolved_dep_id) {
+                report.push_issue(config_file, key, format!("Unknown project \"{}\"", dep_id));
+            } else if let (Some(constraints), Some((_, dep_project))) = (
+                &workspace_config.constraints,
+                loaded_projects.get(&resolved_dep_id),
+            ) {
+                if let Err(error) = enforce_constraints(constraints, project, dep_project) {
+                    report.push_issue(config_file, key, error.to_string());
+                }
+            }
+        }
+
+        for (task_id, task) in &project.tasks {
+            for dep in &task.deps {
+                let key = Some(format!("tasks.{}.deps", task_id));
+                let (dep_project_id, dep_task_id) =
+                    match Target::parse(&dep.target).and_then(|t| t.ids()) {
+                        Ok(ids) => ids,
+                        Err(error) => {
+                            report.push_issue(config_file, key, error.to_string());
+                            continue;
+                        }
+                    };
+                let dep_project_id = projects.resolve_id(&dep_project_id);
+
+                if !project_ids.contains(&dep_project_id) {
+                    report.push_issue(
+                        config_file,
+                        key,
+                        format!(
+                            "Target \"{}\" references an unknown project \"{}\"",
+                            dep.target, dep_project_id
+                        ),
+                    );
+                } else if let Some((_, dep_project)) = loaded_projects.get(&dep_project_id) {
+                    if !dep_project.tasks.contains_key(&dep_task_id) {
+                        report.push_issue(
+                            config_file,
+                            key,
+                            format!(
+                                "Target \"{}\" references an unknown task \"{}\"",
+                                dep.target, dep_task_id
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,197 @@
+use crate::errors::WorkspaceError;
+use moon_config::{CodeownersConfig, CodeownersProvider};
+use moon_logger::{color, warn};
+use moon_project_graph::ProjectGraph;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:workspace:codeowners";
+
+pub const CODEOWNERS_HEADER: &str = "# Automatically generated by moon. DO NOT MODIFY!";
+
+/// Return the absolute path to the CODEOWNERS file for the configured provider.
+pub fn get_codeowners_path(workspace_root: &Path, config: &CodeownersConfig) -> PathBuf {
+    match config.provider {
+        CodeownersProvider::Bitbucket => workspace_root.join("CODEOWNERS"),
+        CodeownersProvider::GitHub => workspace_root.join(".github/CODEOWNERS"),
+        CodeownersProvider::GitLab => workspace_root.join(".gitlab/CODEOWNERS"),
+    }
+}
+
+fn normalize_source(source: &str) -> &str {
+    match source.trim_start_matches("./").trim_end_matches('/') {
+        "." => "",
+        source => source,
+    }
+}
+
+/// Format a pattern to be relative from the workspace root. Patterns that start
+/// with "/" are already relative from the root, while all others are relative
+/// from the project's source.
+fn format_pattern(source: &str, pattern: &str) -> String {
+    if pattern.starts_with('/') {
+        return pattern.to_owned();
+    }
+
+    let source = normalize_source(source);
+
+    if source.is_empty() {
+        // A lone "/" is not a valid pattern, so match everything instead
+        if pattern.is_empty() {
+            "*".to_owned()
+        } else {
+            format!("/{}", pattern)
+        }
+    } else {
+        format!("/{}/{}", source, pattern)
+    }
+}
+
+struct CodeownersEntry {
+    id: String,
+    owners: Vec<String>,
+    patterns: Vec<String>,
+    source: String,
+}
+
+/// Format entries into the contents of a CODEOWNERS file. Every provider uses
+/// "last match wins", so shallow projects must come first, otherwise they would
+/// take precedence over projects nested within them.
+///
+/// GitLab sections are not used, as GitLab applies the last match within each
+/// section, and requires approval from *every* section that matches. A root
+/// project's section would then require its owners on all nested projects.
+fn format_codeowners(mut entries: Vec<CodeownersEntry>) -> String {
+    let mut lines = vec![CODEOWNERS_HEADER.to_owned()];
+
+    entries.sort_by(|a, b| {
+        let depth = |source: &str| match normalize_source(source) {
+            "" => 0,
+            source => source.split('/').count(),
+        };
+
+        depth(&a.source)
+            .cmp(&depth(&b.source))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    for entry in entries {
+        let owners = entry.owners.join(" ");
+
+        lines.push("".to_owned());
+        lines.push(format!("# {}", entry.id));
+
+        for pattern in entry.patterns {
+            lines.push(format!("{} {}", pattern, owners));
+        }
+    }
+
+    lines.push("".to_owned());
+    lines.join("\n")
+}
+
+/// Generate the contents of a CODEOWNERS file, by mapping each project's source
+/// to its owner and maintainers, as defined in the project's metadata.
+pub fn generate_codeowners(
+    config: &CodeownersConfig,
+    projects: &ProjectGraph,
+) -> Result<String, WorkspaceError> {
+    let project_ids = projects.ids();
+    let mut entries = vec![];
+
+    for id in config.paths.keys() {
+        if !project_ids.contains(id) {
+            warn!(
+                target: LOG_TARGET,
+                "Unknown project {} configured in {}, skipping",
+                color::id(id),
+                color::symbol("codeowners.paths")
+            );
+        }
+    }
+
+    for id in project_ids {
+        let project = projects.load(&id)?;
+        let mut owners = vec![];
+
+        if let Some(metadata) = &project.config.project {
+            if !metadata.owner.is_empty() {
+                owners.push(metadata.owner.clone());
+            }
+
+            for maintainer in &metadata.maintainers {
+                if !maintainer.is_empty() && !owners.contains(maintainer) {
+                    owners.push(maintainer.clone());
+                }
+            }
+        }
+
+        if owners.is_empty() {
+            continue;
+        }
+
+        let mut patterns = vec![format_pattern(&project.source, "")];
+
+        if let Some(extra_patterns) = config.paths.get(&id) {
+            for pattern in extra_patterns {
+                patterns.push(format_pattern(&project.source, pattern));
+            }
+        }
+
+        entries.push(CodeownersEntry {
+            id,
+            owners,
+            patterns,
+            source: project.source.clone(),
+        });
+    }
+
+    Ok(format_codeowners(entries))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats_patterns() {
+        assert_eq!(format_pattern("apps/app", ""), "/apps/app/");
+        assert_eq!(format_pattern("apps/app", "*.md"), "/apps/app/*.md");
+        assert_eq!(format_pattern("./apps/app/", "src/"), "/apps/app/src/");
+        assert_eq!(format_pattern(".", "*.md"), "/*.md");
+        assert_eq!(format_pattern(".", ""), "*");
+        assert_eq!(format_pattern("./", ""), "*");
+        assert_eq!(
+            format_pattern("apps/app", "/.github/workflows/app.yml"),
+            "/.github/workflows/app.yml"
+        );
+    }
+
+    #[test]
+    fn orders_nested_projects_last_without_sections() {
+        let output = format_codeowners(vec![
+            CodeownersEntry {
+                id: "app".into(),
+                owners: vec!["@app-team".into()],
+                patterns: vec![format_pattern("apps/app", "")],
+                source: "apps/app".into(),
+            },
+            CodeownersEntry {
+                id: "root".into(),
+                owners: vec!["@infra-team".into(), "@alice".into()],
+                patterns: vec![format_pattern(".", "")],
+                source: ".".into(),
+            },
+        ]);
+
+        // A GitLab section per project would require the root owners
+        // to approve changes within every nested project
+        assert!(!output.contains('['));
+        assert_eq!(
+            output,
+            format!(
+                "{}\n\n# root\n* @infra-team @alice\n\n# app\n/apps/app/ @app-team\n",
+                CODEOWNERS_HEADER
+            )
+        );
+    }
+}
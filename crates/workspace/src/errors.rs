@@ -3,10 +3,14 @@ use moon_error::MoonError;
 use moon_project_graph::ProjectError;
 use moon_toolchain::ToolchainError;
 use moon_vcs::VcsError;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum WorkspaceError {
+    #[error("<path>{0}</path> is out of date. Run <shell>moon sync</shell> to regenerate it.")]
+    CodeownersOutOfDate(PathBuf),
+
     #[error(
         "Unable to determine workspace root. Please create a <file>{}</file> configuration folder.",
         constants::CONFIG_DIRNAME
@@ -21,18 +25,23 @@ pub enum WorkspaceError {
     MissingWorkspaceConfigFile,
 
     #[error(
-        "Failed to validate <file>{}/{}</file> configuration file.\n\n{0}",
-        constants::CONFIG_DIRNAME,
-        constants::CONFIG_WORKSPACE_FILENAME
+        "Failed to validate <file>{}/{0}</file> configuration file.\n\n{1}",
+        constants::CONFIG_DIRNAME
+    )]
+    InvalidWorkspaceConfigFile(String, String),
+
+    #[error(
+        "Failed to validate <file>{}/{0}</file> configuration file.\n\n{1}",
+        constants::CONFIG_DIRNAME
     )]
-    InvalidWorkspaceConfigFile(String),
+    InvalidGlobalProjectConfigFile(String, String),
 
     #[error(
-        "Failed to validate <file>{}/{}</file> configuration file.\n\n{0}",
+        "Failed to validate <file>{}/{}/{0}</file> configuration file.\n\n{1}",
         constants::CONFIG_DIRNAME,
-        constants::CONFIG_GLOBAL_PROJECT_FILENAME
+        constants::CONFIG_GLOBAL_TASKS_DIRNAME
     )]
-    InvalidGlobalProjectConfigFile(String),
+    InvalidScopedGlobalProjectConfigFile(String, String),
 
     #[error(transparent)]
     Moon(#[from] MoonError),
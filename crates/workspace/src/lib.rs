@@ -1,5 +1,9 @@
+mod check;
+mod codeowners;
 mod errors;
 mod workspace;
 
+pub use check::*;
+pub use codeowners::*;
 pub use errors::WorkspaceError;
 pub use workspace::Workspace;
@@ -1,12 +1,17 @@
 use crate::errors::WorkspaceError;
 use moon_cache::CacheEngine;
-use moon_config::{format_figment_errors, GlobalProjectConfig, WorkspaceConfig};
+use moon_config::{
+    find_config_file, format_figment_errors, ConfigFormat, FigmentError, GlobalProjectConfig,
+    WorkspaceConfig,
+};
 use moon_constants as constants;
+use moon_error::{map_io_to_fs_error, MoonError};
 use moon_logger::{color, debug, trace};
 use moon_project_graph::ProjectGraph;
 use moon_toolchain::Toolchain;
 use moon_utils::fs;
 use moon_vcs::{Vcs, VcsLoader};
+use std::collections::BTreeMap;
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -14,7 +19,7 @@ const LOG_TARGET: &str = "moon:workspace";
 
 /// Recursively attempt to find the workspace root by locating the ".moon"
 /// configuration folder, starting from the current working directory.
-fn find_workspace_root(current_dir: PathBuf) -> Option<PathBuf> {
+pub(crate) fn find_workspace_root(current_dir: PathBuf) -> Option<PathBuf> {
     if let Ok(root) = env::var("MOON_WORKSPACE_ROOT") {
         let root: PathBuf = root.parse().expect("Failed to parse MOON_WORKSPACE_ROOT.");
 
@@ -31,12 +36,60 @@ fn find_workspace_root(current_dir: PathBuf) -> Option<PathBuf> {
         .map(|dir| dir.parent().unwrap().to_path_buf())
 }
 
-// .moon/project.yml
-fn load_global_project_config(root_dir: &Path) -> Result<GlobalProjectConfig, WorkspaceError> {
-    let config_path = root_dir
-        .join(constants::CONFIG_DIRNAME)
-        .join(constants::CONFIG_GLOBAL_PROJECT_FILENAME);
+fn get_file_name(path: &Path) -> String {
+    path.file_name().unwrap().to_string_lossy().to_string()
+}
+
+/// Load a global or scoped project config, and track the file name it was loaded from.
+pub(crate) fn load_global_project_config_file(
+    path: PathBuf,
+    extends_cache_ttl: u64,
+) -> Result<GlobalProjectConfig, Vec<FigmentError>> {
+    let config_file = get_file_name(&path);
+    let mut cfg = GlobalProjectConfig::load(path, extends_cache_ttl)?;
+
+    cfg.file_name = Some(config_file);
+
+    Ok(cfg)
+}
+
+pub(crate) struct ScopedConfigFile {
+    pub config: Result<GlobalProjectConfig, Vec<FigmentError>>,
+    pub file_name: String,
+    pub scope: String,
+}
+
+/// Load every config file within the `.moon/tasks` folder, without failing
+/// on invalid files, so that callers can decide how to handle their errors.
+pub(crate) fn load_scoped_config_files(
+    tasks_dir: &Path,
+    extends_cache_ttl: u64,
+) -> Result<Vec<ScopedConfigFile>, WorkspaceError> {
+    if !tasks_dir.exists() {
+        return Ok(vec![]);
+    }
 
+    trace!(
+        target: LOG_TARGET,
+        "Loading scoped global project configs from {}",
+        color::path(tasks_dir)
+    );
+
+    Ok(find_scoped_config_files(tasks_dir)?
+        .into_iter()
+        .map(|(scope, path)| ScopedConfigFile {
+            file_name: get_file_name(&path),
+            config: load_global_project_config_file(path, extends_cache_ttl),
+            scope,
+        })
+        .collect())
+}
+
+// .moon/project.yml
+fn load_global_project_config(
+    root_dir: &Path,
+    extends_cache_ttl: u64,
+) -> Result<GlobalProjectConfig, WorkspaceError> {
     trace!(
         target: LOG_TARGET,
         "Attempting to find {} in {}",
@@ -48,24 +101,90 @@ fn load_global_project_config(root_dir: &Path) -> Result<GlobalProjectConfig, Wo
         color::path(root_dir)
     );
 
-    if !config_path.exists() {
-        return Ok(GlobalProjectConfig::default());
-    }
+    let config_path = match find_config_file(
+        &root_dir.join(constants::CONFIG_DIRNAME),
+        constants::CONFIG_GLOBAL_PROJECT_FILENAME,
+    )? {
+        Some(path) => path,
+        None => return Ok(GlobalProjectConfig::default()),
+    };
+
+    let config_file = get_file_name(&config_path);
 
-    match GlobalProjectConfig::load(config_path) {
+    match load_global_project_config_file(config_path, extends_cache_ttl) {
         Ok(cfg) => Ok(cfg),
         Err(errors) => Err(WorkspaceError::InvalidGlobalProjectConfigFile(
+            config_file,
             format_figment_errors(errors),
         )),
     }
 }
 
-// .moon/workspace.yml
-fn load_workspace_config(root_dir: &Path) -> Result<WorkspaceConfig, WorkspaceError> {
-    let config_path = root_dir
+// .moon/tasks/*.yml
+fn load_scoped_global_project_configs(
+    root_dir: &Path,
+    global_config: &mut GlobalProjectConfig,
+    extends_cache_ttl: u64,
+) -> Result<(), WorkspaceError> {
+    let tasks_dir = root_dir
         .join(constants::CONFIG_DIRNAME)
-        .join(constants::CONFIG_WORKSPACE_FILENAME);
+        .join(constants::CONFIG_GLOBAL_TASKS_DIRNAME);
 
+    for scoped in load_scoped_config_files(&tasks_dir, extends_cache_ttl)? {
+        match scoped.config {
+            Ok(cfg) => {
+                global_config.scoped_configs.insert(scoped.scope, cfg);
+            }
+            Err(errors) => {
+                return Err(WorkspaceError::InvalidScopedGlobalProjectConfigFile(
+                    scoped.file_name,
+                    format_figment_errors(errors),
+                ));
+            }
+        };
+    }
+
+    Ok(())
+}
+
+/// Find all config files within the `.moon/tasks` folder, keyed by scope
+/// (the file name without the extension). Errors if a scope is defined
+/// in more than one format.
+fn find_scoped_config_files(tasks_dir: &Path) -> Result<BTreeMap<String, PathBuf>, WorkspaceError> {
+    let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+    let entries =
+        std::fs::read_dir(tasks_dir).map_err(|e| map_io_to_fs_error(e, tasks_dir.to_path_buf()))?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|e| map_io_to_fs_error(e, tasks_dir.to_path_buf()))?
+            .path();
+        let file_name = get_file_name(&path);
+
+        if !path.is_file() || !ConfigFormat::is_supported(&file_name) {
+            continue;
+        }
+
+        let scope = path.file_stem().unwrap().to_string_lossy().to_string();
+
+        if let Some(existing_path) = files.get(&scope) {
+            let mut names = vec![get_file_name(existing_path), file_name];
+            names.sort();
+
+            return Err(WorkspaceError::Moon(MoonError::MultipleConfigFormats(
+                tasks_dir.to_path_buf(),
+                names.join(", "),
+            )));
+        }
+
+        files.insert(scope, path);
+    }
+
+    Ok(files)
+}
+
+// .moon/workspace.yml
+fn load_workspace_config(root_dir: &Path) -> Result<WorkspaceConfig, WorkspaceError> {
     trace!(
         target: LOG_TARGET,
         "Loading {} from {}",
@@ -77,13 +196,20 @@ fn load_workspace_config(root_dir: &Path) -> Result<WorkspaceConfig, WorkspaceEr
         color::path(root_dir)
     );
 
-    if !config_path.exists() {
-        return Err(WorkspaceError::MissingWorkspaceConfigFile);
-    }
+    let config_path = match find_config_file(
+        &root_dir.join(constants::CONFIG_DIRNAME),
+        constants::CONFIG_WORKSPACE_FILENAME,
+    )? {
+        Some(path) => path,
+        None => return Err(WorkspaceError::MissingWorkspaceConfigFile),
+    };
+
+    let config_file = get_file_name(&config_path);
 
     match WorkspaceConfig::load(config_path) {
         Ok(cfg) => Ok(cfg),
         Err(errors) => Err(WorkspaceError::InvalidWorkspaceConfigFile(
+            config_file,
             format_figment_errors(errors),
         )),
     }
@@ -130,11 +256,24 @@ impl Workspace {
         );
 
         // Load configs
-        let config = load_workspace_config(&root_dir)?;
-        let project_config = load_global_project_config(&root_dir)?;
+        let mut config = load_workspace_config(&root_dir)?;
+        let mut project_config =
+            load_global_project_config(&root_dir, config.get_extends_cache_ttl())?;
+
+        load_scoped_global_project_configs(
+            &root_dir,
+            &mut project_config,
+            config.get_extends_cache_ttl(),
+        )?;
 
         // Setup components
-        let cache = CacheEngine::create(&root_dir).await?;
+        let mut cache = CacheEngine::create(&root_dir).await?;
+        cache.archive_compression_level = config.action_runner.archive_compression_level;
+        cache.archive_format = config.action_runner.archive_format.clone();
+        cache.archive_preserve_mtimes = config.action_runner.archive_preserve_mtimes;
+
+        Toolchain::resolve_node_version(&root_dir, &mut config.node).await?;
+
         let toolchain = Toolchain::create(&root_dir, &config).await?;
         let projects = ProjectGraph::create(&root_dir, &config, project_config, &cache).await?;
         let vcs = VcsLoader::load(&root_dir, &config)?;
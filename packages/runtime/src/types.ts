@@ -19,10 +19,16 @@ export interface TaskOptions {
 	runFromWorkspaceRoot: boolean;
 }
 
+export interface TaskDependency {
+	args?: string[];
+	env?: Record<string, string>;
+	target: string;
+}
+
 export interface Task {
 	args: string[];
 	command: string;
-	deps: string[];
+	deps: (TaskDependency | string)[];
 	env: Record<string, string>;
 	inputs: string[];
 	inputGlobs: string[];
@@ -96,6 +96,7 @@ const sidebars = {
 			items: [
 				'commands/overview',
 				'commands/bin',
+				'commands/check-config',
 				'commands/ci',
 				'commands/dep-graph',
 				'commands/init',
@@ -116,7 +117,11 @@ const sidebars = {
 				{
 					type: 'category',
 					label: 'query',
-					items: ['commands/query/projects', 'commands/query/touched-files'],
+					items: [
+						'commands/query/cache',
+						'commands/query/projects',
+						'commands/query/touched-files',
+					],
 					link: {
 						type: 'generated-index',
 						title: 'query',
@@ -130,6 +135,18 @@ const sidebars = {
 				'commands/setup',
 				'commands/sync',
 				'commands/teardown',
+				{
+					type: 'category',
+					label: 'toolchain',
+					items: ['commands/toolchain/list', 'commands/toolchain/prune'],
+					link: {
+						type: 'generated-index',
+						title: 'toolchain',
+						description: 'Manage tools installed in the toolchain.',
+						slug: '/commands/toolchain',
+						keywords: ['cli', 'commands', 'toolchain'],
+					},
+				},
 			],
 			link: {
 				type: 'generated-index',